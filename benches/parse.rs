@@ -0,0 +1,112 @@
+//! Parsing throughput benchmarks.
+//!
+//! Run with `cargo bench`. Each benchmark reads a generated feed repeatedly and reports the
+//! mean time per read and the resulting throughput. The harness only uses the standard
+//! library, so numbers are indicative rather than statistically rigorous.
+
+use std::mem;
+use std::ptr;
+use std::time::{Duration, Instant};
+
+use atom_syndication::Feed;
+
+const ENTRIES: usize = 200;
+const LINKS_PER_ENTRY: usize = 20;
+const LARGE_CONTENT_ENTRIES: usize = 4;
+const PARAGRAPHS_PER_CONTENT: usize = 20_000;
+
+fn feed_with_many_links() -> String {
+    let mut xml = String::from(
+        r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>urn:feed</id><title>Links</title>"#,
+    );
+    xml.push_str("<updated>2024-01-01T00:00:00Z</updated>");
+    for i in 0..ENTRIES {
+        xml.push_str(&format!(
+            "<entry><id>urn:entry:{}</id><title>Entry</title>\
+             <updated>2024-01-01T00:00:00Z</updated>",
+            i
+        ));
+        for j in 0..LINKS_PER_ENTRY {
+            xml.push_str(&format!(
+                r#"<link xmlns="http://www.w3.org/2005/Atom" href="https://example.com/{}/{}" rel="related" type="text/html" hreflang="en" title="Link {}" data-extra="x"/>"#,
+                i, j, j
+            ));
+            xml.push_str(r#"<category term="tag" scheme="https://example.com/tags" label="Tag"/>"#);
+        }
+        xml.push_str("</entry>");
+    }
+    xml.push_str("</feed>");
+    xml
+}
+
+/// A feed with a few entries whose content is megabytes long, as HTML, which has to be
+/// unescaped, or as XHTML, which is read as it appears in the input.
+fn feed_with_large_content(content_type: &str) -> String {
+    let paragraph = if content_type == "xhtml" {
+        "<p>Lorem ipsum dolor sit amet, consectetur adipiscing elit.</p>"
+    } else {
+        "&lt;p&gt;Lorem ipsum dolor sit amet, consectetur adipiscing elit.&lt;/p&gt;"
+    };
+
+    let mut xml = String::from(
+        r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>urn:feed</id><title>Content</title>"#,
+    );
+    xml.push_str("<updated>2024-01-01T00:00:00Z</updated>");
+    for i in 0..LARGE_CONTENT_ENTRIES {
+        xml.push_str(&format!(
+            "<entry><id>urn:entry:{}</id><title>Entry</title>\
+             <updated>2024-01-01T00:00:00Z</updated><content type=\"{}\">",
+            i, content_type
+        ));
+        if content_type == "xhtml" {
+            xml.push_str(r#"<div xmlns="http://www.w3.org/1999/xhtml">"#);
+        }
+        for _ in 0..PARAGRAPHS_PER_CONTENT {
+            xml.push_str(paragraph);
+        }
+        if content_type == "xhtml" {
+            xml.push_str("</div>");
+        }
+        xml.push_str("</content></entry>");
+    }
+    xml.push_str("</feed>");
+    xml
+}
+
+/// Keep the optimizer from discarding a value that is never used.
+fn black_box<T>(value: T) -> T {
+    // SAFETY: `value` is read once and then forgotten, so it is neither dropped twice nor used
+    // after the read.
+    unsafe {
+        let result = ptr::read_volatile(&value);
+        mem::forget(value);
+        result
+    }
+}
+
+fn bench(name: &str, input: &str) {
+    // Warm up, then run for at least a second.
+    for _ in 0..3 {
+        black_box(input.parse::<Feed>().unwrap());
+    }
+
+    let mut iterations = 0u32;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(1) {
+        black_box(input.parse::<Feed>().unwrap());
+        iterations += 1;
+    }
+    let per_read = start.elapsed() / iterations;
+    let throughput = input.len() as f64 / per_read.as_secs_f64() / (1024.0 * 1024.0);
+
+    println!(
+        "{:<24} {:>10.2?}/read {:>8.1} MiB/s ({} iterations)",
+        name, per_read, throughput, iterations
+    );
+}
+
+fn main() {
+    bench("many_links", &feed_with_many_links());
+    bench("large_html_content", &feed_with_large_content("html"));
+    bench("large_xhtml_content", &feed_with_large_content("xhtml"));
+}
@@ -0,0 +1,18 @@
+#![no_main]
+
+use atom_syndication::Feed;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(feed) = Feed::read_from(data) {
+        let _ = feed.to_string();
+        let _ = feed.byte_size_estimate();
+        let _ = feed.validate();
+
+        for entry in feed.entries() {
+            let _ = entry.word_count();
+            let _ = entry.image_url();
+            let _ = entry.replies_link();
+        }
+    }
+});
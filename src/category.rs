@@ -3,16 +3,15 @@ use std::io::{BufRead, Write};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Reader;
-use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
+use crate::fromxml::{FromXml, XmlReader};
+use crate::toxml::{ToXml, XmlWriter};
+use crate::util::unknown_attr;
 
 /// Represents a category in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -29,6 +28,9 @@ pub struct Category {
     pub scheme: Option<String>,
     /// A human-readable label for display.
     pub label: Option<String>,
+    /// Attributes that are not otherwise recognized, in the order they appeared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown_attrs: Vec<(String, String)>,
 }
 
 impl Category {
@@ -107,7 +109,6 @@ impl Category {
     /// category.set_scheme("Technology".to_string());
     /// assert_eq!(category.scheme(), Some("Technology"));
     /// ```
-
     pub fn label(&self) -> Option<&str> {
         self.label.as_deref()
     }
@@ -128,21 +129,88 @@ impl Category {
     {
         self.label = label.into();
     }
+
+    /// Return the label to display for this category: its `label` if it is set and not
+    /// empty, otherwise its `term`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// category.set_term("technology");
+    /// assert_eq!(category.display_label(), "technology");
+    ///
+    /// category.set_label("Technology".to_string());
+    /// assert_eq!(category.display_label(), "Technology");
+    /// ```
+    pub fn display_label(&self) -> &str {
+        match self.label.as_deref() {
+            Some(label) if !label.trim().is_empty() => label,
+            _ => &self.term,
+        }
+    }
+
+    /// Return the attributes of this category that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// category.set_unknown_attrs(vec![("v:weight".to_string(), "0.5".to_string())]);
+    /// assert_eq!(category.unknown_attrs(), &[("v:weight".to_string(), "0.5".to_string())]);
+    /// ```
+    pub fn unknown_attrs(&self) -> &[(String, String)] {
+        &self.unknown_attrs
+    }
+
+    /// Set the attributes of this category that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// category.set_unknown_attrs(vec![("v:weight".to_string(), "0.5".to_string())]);
+    /// ```
+    pub fn set_unknown_attrs<V>(&mut self, unknown_attrs: V)
+    where
+        V: Into<Vec<(String, String)>>,
+    {
+        self.unknown_attrs = unknown_attrs.into();
+    }
+}
+
+/// Return whether any of the categories has the given term. An empty term never matches.
+pub(crate) fn has_term(categories: &[Category], term: &str, ignore_case: bool) -> bool {
+    !term.is_empty()
+        && categories.iter().any(|category| {
+            if ignore_case {
+                category.term.to_lowercase() == term.to_lowercase()
+            } else {
+                category.term == term
+            }
+        })
 }
 
 impl FromXml for Category {
     fn from_xml<B: BufRead>(
-        reader: &mut Reader<B>,
+        reader: &mut XmlReader<B>,
         mut atts: Attributes<'_>,
     ) -> Result<Self, Error> {
         let mut category = Category::default();
 
         for att in atts.with_checks(false).flatten() {
             match att.key {
-                b"term" => category.term = att.unescape_and_decode_value(reader)?,
-                b"scheme" => category.scheme = Some(att.unescape_and_decode_value(reader)?),
-                b"label" => category.label = Some(att.unescape_and_decode_value(reader)?),
-                _ => {}
+                b"term" => category.term = reader.unescape_attr(&att)?,
+                b"scheme" => category.scheme = Some(reader.unescape_attr(&att)?),
+                b"label" => category.label = Some(reader.unescape_attr(&att)?),
+                b"xmlns" => {}
+                _ => category.unknown_attrs.push(unknown_attr(reader, &att)?),
             }
         }
 
@@ -153,19 +221,25 @@ impl FromXml for Category {
 }
 
 impl ToXml for Category {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError> {
         let name = b"category";
         let mut element = BytesStart::borrowed(name, name.len());
         element.push_attribute(("term", &*self.term));
 
-        if let Some(ref scheme) = self.scheme {
-            element.push_attribute(("scheme", &**scheme));
+        if let Some(scheme) = self.scheme.as_deref().filter(|scheme| !scheme.is_empty()) {
+            element.push_attribute(("scheme", scheme));
         }
 
-        if let Some(ref label) = self.label {
-            element.push_attribute(("label", &**label));
+        if let Some(label) = self.label.as_deref().filter(|label| !label.is_empty()) {
+            element.push_attribute(("label", label));
         }
 
+        element.extend_attributes(
+            self.unknown_attrs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
         writer.write_event(Event::Empty(element))?;
 
         Ok(())
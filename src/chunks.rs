@@ -0,0 +1,184 @@
+use std::io::{self, BufRead, Read};
+
+use crate::feed::SourcePosition;
+
+/// A `BufRead` adapter over an iterator of byte chunks, such as the body of an HTTP response
+/// received piece by piece.
+///
+/// Each chunk is read in place as it is produced, so the whole input is never held in memory
+/// at once. Any type that can be viewed as a byte slice works as a chunk, including `Vec<u8>`
+/// and `bytes::Bytes`. The first error produced by the iterator is returned from the read
+/// that reaches it.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use atom_syndication::{ChunkReader, Feed};
+///
+/// let chunks = vec![
+///     Ok::<_, io::Error>(b"<feed><title>Ti".to_vec()),
+///     Ok(b"tle</title></feed>".to_vec()),
+/// ];
+///
+/// let feed = Feed::read_from(ChunkReader::new(chunks)).unwrap();
+/// assert_eq!(feed.title(), "Title");
+/// ```
+#[derive(Debug)]
+pub struct ChunkReader<I, T> {
+    chunks: I,
+    current: Option<T>,
+    pos: usize,
+}
+
+impl<I, T> ChunkReader<I, T> {
+    /// Create a reader yielding the bytes of `chunks` in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{self, Read};
+    /// use atom_syndication::ChunkReader;
+    ///
+    /// let mut reader = ChunkReader::new(vec![Ok::<_, io::Error>("ab"), Ok(""), Ok("c")]);
+    /// let mut text = String::new();
+    /// reader.read_to_string(&mut text).unwrap();
+    /// assert_eq!(text, "abc");
+    /// ```
+    pub fn new<C, E>(chunks: C) -> Self
+    where
+        C: IntoIterator<IntoIter = I, Item = Result<T, E>>,
+        T: AsRef<[u8]>,
+        E: Into<io::Error>,
+    {
+        ChunkReader {
+            chunks: chunks.into_iter(),
+            current: None,
+            pos: 0,
+        }
+    }
+}
+
+impl<I, T, E> BufRead for ChunkReader<I, T>
+where
+    I: Iterator<Item = Result<T, E>>,
+    T: AsRef<[u8]>,
+    E: Into<io::Error>,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        loop {
+            let exhausted = match self.current {
+                Some(ref chunk) => self.pos >= chunk.as_ref().len(),
+                None => true,
+            };
+            if !exhausted {
+                break;
+            }
+
+            match self.chunks.next() {
+                Some(Ok(chunk)) => {
+                    self.current = Some(chunk);
+                    self.pos = 0;
+                }
+                Some(Err(err)) => return Err(err.into()),
+                None => {
+                    self.current = None;
+                    break;
+                }
+            }
+        }
+
+        Ok(match self.current {
+            Some(ref chunk) => &chunk.as_ref()[self.pos..],
+            None => &[],
+        })
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+impl<I, T, E> Read for ChunkReader<I, T>
+where
+    I: Iterator<Item = Result<T, E>>,
+    T: AsRef<[u8]>,
+    E: Into<io::Error>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+/// A `BufRead` adapter recording the byte offset of every line feed read through it, to turn
+/// byte offsets into lines and columns.
+pub(crate) struct LineIndex<B> {
+    inner: B,
+    offset: usize,
+    newlines: Vec<usize>,
+}
+
+impl<B: BufRead> LineIndex<B> {
+    pub fn new(inner: B) -> Self {
+        LineIndex {
+            inner,
+            offset: 0,
+            newlines: Vec::new(),
+        }
+    }
+
+    /// Return the 1-based line and column of a byte offset that has been read, with the column
+    /// counted in bytes.
+    pub fn position(&self, offset: usize) -> SourcePosition {
+        // The number of newlines before the offset; a newline at the offset ends its line.
+        let line = match self.newlines.binary_search(&offset) {
+            Ok(line) | Err(line) => line,
+        };
+        let line_start = match line {
+            0 => 0,
+            _ => self.newlines[line - 1] + 1,
+        };
+
+        SourcePosition {
+            line: line + 1,
+            column: offset - line_start + 1,
+            offset,
+        }
+    }
+}
+
+impl<B: BufRead> BufRead for LineIndex<B> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The bytes being consumed are the start of the buffer returned by the last
+        // `fill_buf`, which is returned again without reading while it is not empty.
+        let offset = self.offset;
+        if let Ok(buf) = self.inner.fill_buf() {
+            let newlines = buf[..amt.min(buf.len())]
+                .iter()
+                .enumerate()
+                .filter(|(_, &b)| b == b'\n')
+                .map(|(index, _)| offset + index);
+            self.newlines.extend(newlines);
+        }
+        self.offset += amt;
+        self.inner.consume(amt);
+    }
+}
+
+impl<B: BufRead> Read for LineIndex<B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
@@ -3,17 +3,17 @@ use std::io::{BufRead, Write};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Reader;
-use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
-use crate::util::{atom_text, atom_xhtml};
+use crate::fromxml::{FromXml, XmlReader};
+#[cfg(feature = "html")]
+use crate::html::collapse_whitespace;
+use crate::toxml::{ToXml, XmlWriter};
+use crate::util::{atom_text, atom_xhtml, unknown_attr};
 
 /// Represents the content of an Atom entry
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -34,6 +34,9 @@ pub struct Content {
     pub src: Option<String>,
     /// Either "text", "html", "xhtml", or the MIME type of the content.
     pub content_type: Option<String>,
+    /// Attributes that are not otherwise recognized, in the order they appeared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown_attrs: Vec<(String, String)>,
 }
 
 impl Content {
@@ -130,6 +133,52 @@ impl Content {
         self.src = src.into();
     }
 
+    /// Return whether this content is stored out of line at the URI in `src`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// assert!(!content.is_external());
+    ///
+    /// content.set_src("http://example.com/image.jpg".to_string());
+    /// assert!(content.is_external());
+    /// ```
+    pub fn is_external(&self) -> bool {
+        self.src.is_some()
+    }
+
+    /// Return the URI and the media type of content stored out of line, or `None` if the
+    /// content is inline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = r#"
+    ///     <feed>
+    ///         <entry>
+    ///             <content type="image/jpeg" src="http://example.com/image.jpg"/>
+    ///         </entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let content = feed.entries()[0].content().unwrap();
+    /// assert_eq!(content.value(), None);
+    /// assert_eq!(
+    ///     content.external_ref(),
+    ///     Some(("http://example.com/image.jpg", Some("image/jpeg")))
+    /// );
+    /// ```
+    pub fn external_ref(&self) -> Option<(&str, Option<&str>)> {
+        self.src
+            .as_deref()
+            .map(|src| (src, self.content_type.as_deref()))
+    }
+
     /// Return the type of the content.
     ///
     /// The type is either `"text"`, `"html"`, `"xhtml"`, or the MIME type of the content.
@@ -164,36 +213,119 @@ impl Content {
     {
         self.content_type = content_type.into();
     }
+
+    /// Return the attributes of this content that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_unknown_attrs(vec![("v:format".to_string(), "md".to_string())]);
+    /// assert_eq!(content.unknown_attrs(), &[("v:format".to_string(), "md".to_string())]);
+    /// ```
+    pub fn unknown_attrs(&self) -> &[(String, String)] {
+        &self.unknown_attrs
+    }
+
+    /// Set the attributes of this content that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_unknown_attrs(vec![("v:format".to_string(), "md".to_string())]);
+    /// ```
+    pub fn set_unknown_attrs<V>(&mut self, unknown_attrs: V)
+    where
+        V: Into<Vec<(String, String)>>,
+    {
+        self.unknown_attrs = unknown_attrs.into();
+    }
+
+    /// Return the serialized XML of this content if its type is an XML media type.
+    ///
+    /// Content with a type of `application/xml`, `text/xml` or ending in `+xml` keeps its child
+    /// elements as markup instead of being reduced to text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = r#"
+    ///     <feed>
+    ///         <entry>
+    ///             <content type="image/svg+xml"><svg xmlns="http://www.w3.org/2000/svg"><text>a &amp; b</text></svg></content>
+    ///         </entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let content = feed.entries()[0].content().unwrap();
+    /// assert_eq!(
+    ///     content.xml_value(),
+    ///     Some(r#"<svg xmlns="http://www.w3.org/2000/svg"><text>a &amp; b</text></svg>"#)
+    /// );
+    /// ```
+    pub fn xml_value(&self) -> Option<&str> {
+        if self.content_type.as_deref().into_iter().any(is_xml_type) {
+            self.value.as_deref()
+        } else {
+            None
+        }
+    }
+}
+
+/// Return `true` if the content type is an XML media type.
+fn is_xml_type(content_type: &str) -> bool {
+    let content_type = content_type.trim();
+    content_type.eq_ignore_ascii_case("application/xml")
+        || content_type.eq_ignore_ascii_case("text/xml")
+        || content_type.to_ascii_lowercase().ends_with("+xml")
 }
 
 impl FromXml for Content {
     fn from_xml<B: BufRead>(
-        reader: &mut Reader<B>,
+        reader: &mut XmlReader<B>,
         mut atts: Attributes<'_>,
     ) -> Result<Self, Error> {
         let mut content = Content::default();
 
         for att in atts.with_checks(false).flatten() {
             match att.key {
-                b"xml:base" => content.base = Some(att.unescape_and_decode_value(reader)?),
-                b"xml:lang" => content.lang = Some(att.unescape_and_decode_value(reader)?),
-                b"type" => content.content_type = Some(att.unescape_and_decode_value(reader)?),
-                b"src" => content.src = Some(att.unescape_and_decode_value(reader)?),
-                _ => {}
+                b"xml:base" => content.base = Some(reader.unescape_attr(&att)?),
+                b"xml:lang" => content.lang = Some(reader.unescape_attr(&att)?),
+                b"type" => content.content_type = Some(reader.unescape_attr(&att)?),
+                b"src" => content.src = Some(reader.unescape_attr(&att)?),
+                b"xmlns" => {}
+                _ => content.unknown_attrs.push(unknown_attr(reader, &att)?),
             }
         }
 
         content.value = match content.content_type {
-            Some(ref t) if t == "xhtml" => atom_xhtml(reader)?,
+            Some(ref t) if t == "xhtml" || is_xml_type(t) => atom_xhtml(reader)?,
             _ => atom_text(reader)?,
         };
 
+        // Content stored out of line must be empty, so whitespace around it is not a value.
+        if content.src.is_some()
+            && content
+                .value
+                .as_deref()
+                .map_or(false, |v| v.trim().is_empty())
+        {
+            content.value = None;
+        }
+
         Ok(content)
     }
 }
 
 impl ToXml for Content {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError> {
         let name = b"content";
         let mut element = BytesStart::borrowed(name, name.len());
 
@@ -217,16 +349,25 @@ impl ToXml for Content {
             element.push_attribute(("src", &**src));
         }
 
+        element.extend_attributes(
+            self.unknown_attrs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
         writer.write_event(Event::Start(element))?;
 
         if let Some(ref value) = self.value {
-            writer.write_event(Event::Text(
-                if self.content_type.as_deref() == Some("xhtml") {
+            writer.write_event(Event::Text(match self.content_type.as_deref() {
+                Some(t) if t == "xhtml" || is_xml_type(t) => {
                     BytesText::from_escaped(value.as_bytes())
-                } else {
-                    BytesText::from_plain(value.as_bytes())
-                },
-            ))?;
+                }
+                #[cfg(feature = "html")]
+                Some("html") if writer.options().collapse_html_whitespace => {
+                    BytesText::from_plain_str(&collapse_whitespace(value)).into_owned()
+                }
+                _ => BytesText::from_plain(value.as_bytes()),
+            }))?;
         }
 
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
@@ -245,8 +386,12 @@ impl ContentBuilder {
 
 #[cfg(test)]
 mod test {
+    use quick_xml::Writer;
+
     use super::*;
     use crate::error::Error;
+    use crate::fromxml::xml_reader;
+    use crate::options::{ReadOptions, WriteOptions};
 
     fn lines(text: &str) -> Vec<&str> {
         text.lines()
@@ -257,14 +402,15 @@ mod test {
 
     fn to_xml(content: &Content) -> String {
         let mut buffer = Vec::new();
-        let mut writer = Writer::new_with_indent(&mut buffer, b' ', 4);
-        content.to_xml(&mut writer).unwrap();
+        let writer = Writer::new_with_indent(&mut buffer, b' ', 4);
+        content
+            .to_xml(&mut XmlWriter::new(writer, WriteOptions::default()))
+            .unwrap();
         String::from_utf8(buffer).unwrap()
     }
 
     fn from_xml(xml: &str) -> Result<Content, Error> {
-        let mut reader = Reader::from_reader(xml.as_bytes());
-        reader.expand_empty_elements(true);
+        let mut reader = xml_reader(xml.as_bytes(), ReadOptions::default());
 
         loop {
             let mut buf = Vec::new();
@@ -0,0 +1,107 @@
+use std::io::BufRead;
+
+use quick_xml::events::{BytesStart, Event};
+
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::feed::Feed;
+use crate::fromxml::{xml_reader, FromXml, XmlReader};
+use crate::options::ReadOptions;
+
+/// An iterator over the entries of a feed whose metadata has been read, pulling each entry
+/// from the underlying reader on demand.
+///
+/// Returned by [`Feed::read_header`]. Each call to `next` parses one entry. Iteration ends
+/// after the closing `feed` tag or the first error. Feed-level elements that appear after
+/// the first entry are skipped.
+pub struct EntryCursor<B: BufRead> {
+    reader: XmlReader<B>,
+    next: Option<BytesStart<'static>>,
+    done: bool,
+}
+
+impl<B: BufRead> EntryCursor<B> {
+    /// Read the entry after the ones already read, or return `None` after the last one.
+    fn read_entry(&mut self) -> Result<Option<Entry>, Error> {
+        if let Some(element) = self.next.take() {
+            return Entry::from_xml(&mut self.reader, element.attributes()).map(Some);
+        }
+
+        let mut buf = Vec::new();
+        loop {
+            match self.reader.read_event(&mut buf)? {
+                Event::Start(element) => {
+                    if *self.reader.element_name(element.name()) == *b"entry" {
+                        return Entry::from_xml(&mut self.reader, element.attributes()).map(Some);
+                    }
+                    self.reader.read_to_end(element.name(), &mut Vec::new())?;
+                }
+                Event::End(_) => return Ok(None),
+                Event::Eof => return Err(Error::Eof),
+                _ => {}
+            }
+
+            buf.clear();
+        }
+    }
+}
+
+impl<B: BufRead> Iterator for EntryCursor<B> {
+    type Item = Result<Entry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.read_entry();
+        match result {
+            Ok(Some(_)) => {}
+            _ => self.done = true,
+        }
+        result.transpose()
+    }
+}
+
+impl Feed {
+    /// Read the metadata of an Atom feed from the reader, returning it together with a cursor
+    /// over its entries.
+    ///
+    /// Reading stops at the first `entry` start tag, so the returned feed holds the elements
+    /// that appear before it and no entries. The cursor parses the entries from the same
+    /// reader only as it is advanced, so an unwanted feed can be dropped after reading just
+    /// its header. Feed-level elements that follow the first entry are skipped rather than
+    /// read into the metadata, unlike [`Feed::read_from`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"
+    ///     <feed>
+    ///         <title>Feed Title</title>
+    ///         <entry><id>urn:1</id></entry>
+    ///         <entry><id>urn:2</id></entry>
+    ///     </feed>
+    /// "#;
+    /// let (feed, entries) = Feed::read_header(xml.as_bytes()).unwrap();
+    /// assert_eq!(feed.title(), "Feed Title");
+    ///
+    /// let ids = entries
+    ///     .map(|entry| entry.map(|entry| entry.id().to_string()))
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// assert_eq!(ids, vec!["urn:1", "urn:2"]);
+    /// ```
+    pub fn read_header<B: BufRead>(reader: B) -> Result<(Feed, EntryCursor<B>), Error> {
+        let mut reader = xml_reader(reader, ReadOptions::default());
+        reader.set_stop_at_entry(true);
+        let mut feed = Feed::default();
+        feed.read_document(&mut reader)?;
+
+        let next = reader.take_stopped_at();
+        let done = next.is_none();
+        Ok((feed, EntryCursor { reader, next, done }))
+    }
+}
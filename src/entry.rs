@@ -1,27 +1,52 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::io::{BufRead, Write};
+use std::time::{Duration, SystemTime};
 
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Reader;
-use quick_xml::Writer;
 
-use crate::category::Category;
+use crate::category::{has_term, Category};
 use crate::content::Content;
 use crate::error::Error;
-use crate::extension::util::{extension_name, parse_extension};
-use crate::extension::ExtensionMap;
-use crate::fromxml::FromXml;
-use crate::link::Link;
+use crate::extension::util::{
+    extension_name, extension_prefixes, find_extensions, parse_extension, well_known_namespace,
+    DUBLIN_CORE_NAMESPACE,
+};
+use crate::extension::{Extension, ExtensionMap};
+use crate::feed::Feed;
+use crate::fromxml::{FromXml, XmlReader};
+use crate::html::{find_tags, strip_tags, text_to_html, truncate_words, unwrap_xhtml_div};
+use crate::link::{dedup_links, find_link, replace_link, Link, RepliesLink, THREADING_NAMESPACE};
+use crate::options::{ReadOptions, WriteOptions};
 use crate::person::Person;
 use crate::source::Source;
-use crate::text::Text;
-use crate::toxml::{ToXml, WriterExt};
-use crate::util::{atom_datetime, atom_text, default_fixed_datetime, FixedDateTime};
+use crate::tag::{parse_tag_uri, TagUri};
+use crate::text::{Text, TextType};
+use crate::toxml::{ToXml, WriterExt, XmlWriter};
+use crate::util::{
+    atom_datetime, atom_epoch, atom_text, default_fixed_datetime, from_system_time, parse_duration,
+    rfc822, rss_date, rss_datetime, skip_unknown, system_time, unknown_attr, FixedDateTime,
+};
+
+/// The namespace of the Slash extension.
+const SLASH_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/slash/";
+
+/// The namespace of the iTunes podcast extension.
+const ITUNES_NAMESPACE: &str = "http://www.itunes.com/dtds/podcast-1.0.dtd";
+
+/// The namespace of the Media RSS extension.
+const MEDIA_NAMESPACE: &str = "http://search.yahoo.com/mrss/";
 
 /// Represents an entry in an Atom feed
+///
+/// Equality and hashing take every field into account, including the timestamps, extensions,
+/// unknown attributes and preserved comments, so two copies of an entry fetched at different
+/// times are usually not equal. Timestamps are compared as instants, ignoring their UTC offset.
+/// Use [`Entry::id_hash`] to deduplicate entries by identity.
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -63,6 +88,29 @@ pub struct Entry {
     /// The extensions for this entry.
     #[cfg_attr(feature = "builders", builder(setter(each = "extension")))]
     pub extensions: ExtensionMap,
+    /// The namespaces bound on an enclosing element, such as the feed, to the prefixes of the
+    /// extensions of this entry, keyed by prefix. Namespaces declared on the entry itself are
+    /// kept among the unknown attributes.
+    #[cfg_attr(feature = "builders", builder(setter(each = "namespace")))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub namespaces: BTreeMap<String, String>,
+    /// Base URL for resolving any relative references found in the element.
+    pub base: Option<String>,
+    /// Indicates the natural language for the element.
+    pub lang: Option<String>,
+    /// Attributes that are not otherwise recognized, in the order they appeared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown_attrs: Vec<(String, String)>,
+    /// XML comments inside the entry, kept when reading with
+    /// `ReadOptions::preserve_comments`.
+    #[cfg_attr(feature = "builders", builder(setter(each = "comment")))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub comments: Vec<String>,
+    /// XML comments directly before the entry, kept when reading with
+    /// `ReadOptions::preserve_comments`.
+    #[cfg_attr(feature = "builders", builder(setter(each = "leading_comment")))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub leading_comments: Vec<String>,
 }
 
 impl Entry {
@@ -98,6 +146,24 @@ impl Entry {
         self.title = title.into();
     }
 
+    /// Set the title of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let entry = Entry::default().with_title("Entry Title");
+    /// assert_eq!(entry.title(), "Entry Title");
+    /// ```
+    pub fn with_title<V>(mut self, title: V) -> Self
+    where
+        V: Into<Text>,
+    {
+        self.set_title(title);
+        self
+    }
+
     /// Return the unique URI of this entry.
     ///
     /// # Examples
@@ -130,6 +196,24 @@ impl Entry {
         self.id = id.into();
     }
 
+    /// Set the ID of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let entry = Entry::default().with_id("urn:uuid:4ae8550b-2987-49fa-9f8c-54c180c418ac");
+    /// assert_eq!(entry.id(), "urn:uuid:4ae8550b-2987-49fa-9f8c-54c180c418ac");
+    /// ```
+    pub fn with_id<V>(mut self, id: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_id(id);
+        self
+    }
+
     /// Return the last time that this entry was modified.
     ///
     /// # Examples
@@ -147,6 +231,24 @@ impl Entry {
         &self.updated
     }
 
+    /// Return the last time this entry was modified formatted as in RFC 822, as used by the RSS
+    /// `pubDate` element, e.g. `Sat, 1 May 2021 12:00:00 +0000`.
+    ///
+    /// An error is returned if the year is negative or has more than four digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, FixedDateTime};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_updated(FixedDateTime::parse_from_rfc3339("2021-05-01T12:00:00-04:00").unwrap());
+    /// assert_eq!(entry.updated_rfc822().unwrap(), "Sat, 1 May 2021 12:00:00 -0400");
+    /// ```
+    pub fn updated_rfc822(&self) -> Result<String, Error> {
+        rfc822(&self.updated)
+    }
+
     /// Set the last time that this entry was modified.
     ///
     /// # Examples
@@ -166,6 +268,24 @@ impl Entry {
         self.updated = updated.into();
     }
 
+    /// Set the last modified timestamp of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, FixedDateTime};
+    ///
+    /// let entry = Entry::default().with_updated(FixedDateTime::parse_from_rfc3339("2017-06-03T15:15:44-05:00").unwrap());
+    /// assert_eq!(entry.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn with_updated<V>(mut self, updated: V) -> Self
+    where
+        V: Into<FixedDateTime>,
+    {
+        self.set_updated(updated);
+        self
+    }
+
     /// Return the authors of this entry.
     ///
     /// # Examples
@@ -198,6 +318,47 @@ impl Entry {
         self.authors = authors.into();
     }
 
+    /// Set the authors of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Person};
+    ///
+    /// let entry = Entry::default().with_authors(vec![Person::default()]);
+    /// assert_eq!(entry.authors().len(), 1);
+    /// ```
+    pub fn with_authors<V>(mut self, authors: V) -> Self
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.set_authors(authors);
+        self
+    }
+
+    /// Return the names of the authors of this entry, skipping authors without a name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Person};
+    ///
+    /// let mut entry = Entry::default();
+    /// let jane = Person {
+    ///     name: "Jane Doe".to_string(),
+    ///     ..Person::default()
+    /// };
+    /// entry.set_authors(vec![jane, Person::default()]);
+    /// assert_eq!(entry.author_names(), vec!["Jane Doe"]);
+    /// ```
+    pub fn author_names(&self) -> Vec<&str> {
+        self.authors
+            .iter()
+            .map(Person::name)
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
     /// Return the categories this entry belongs to.
     ///
     /// # Examples
@@ -230,6 +391,90 @@ impl Entry {
         self.categories = categories.into();
     }
 
+    /// Set the categories of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Entry};
+    ///
+    /// let entry = Entry::default().with_categories(vec![Category::default()]);
+    /// assert_eq!(entry.categories().len(), 1);
+    /// ```
+    pub fn with_categories<V>(mut self, categories: V) -> Self
+    where
+        V: Into<Vec<Category>>,
+    {
+        self.set_categories(categories);
+        self
+    }
+
+    /// Set the categories of this entry to one category per term, with only the term set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_categories_from_terms(vec!["rust", "atom"]);
+    /// assert_eq!(entry.categories()[1].term(), "atom");
+    /// ```
+    pub fn set_categories_from_terms<I, S>(&mut self, terms: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.categories = terms
+            .into_iter()
+            .map(|term| Category {
+                term: term.into(),
+                ..Category::default()
+            })
+            .collect();
+    }
+
+    /// Return whether this entry has a category with the given term.
+    ///
+    /// Terms are compared case-sensitively and an empty term never matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Entry};
+    ///
+    /// let mut category = Category::default();
+    /// category.set_term("rust");
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_categories(vec![category]);
+    /// assert!(entry.has_category("rust"));
+    /// assert!(!entry.has_category("Rust"));
+    /// ```
+    pub fn has_category(&self, term: &str) -> bool {
+        has_term(&self.categories, term, false)
+    }
+
+    /// Return whether this entry has a category with the given term, ignoring case.
+    ///
+    /// An empty term never matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Entry};
+    ///
+    /// let mut category = Category::default();
+    /// category.set_term("rust");
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_categories(vec![category]);
+    /// assert!(entry.has_category_ci("Rust"));
+    /// ```
+    pub fn has_category_ci(&self, term: &str) -> bool {
+        has_term(&self.categories, term, true)
+    }
+
     /// Return the contributors to this entry.
     ///
     /// # Examples
@@ -262,6 +507,24 @@ impl Entry {
         self.contributors = contributors.into();
     }
 
+    /// Set the contributors of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Person};
+    ///
+    /// let entry = Entry::default().with_contributors(vec![Person::default()]);
+    /// assert_eq!(entry.contributors().len(), 1);
+    /// ```
+    pub fn with_contributors<V>(mut self, contributors: V) -> Self
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.set_contributors(contributors);
+        self
+    }
+
     /// Return the links for this entry.
     ///
     /// # Examples
@@ -294,6 +557,66 @@ impl Entry {
         self.links = links.into();
     }
 
+    /// Set the links of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let entry = Entry::default().with_links(vec![Link::default()]);
+    /// assert_eq!(entry.links().len(), 1);
+    /// ```
+    pub fn with_links<V>(mut self, links: V) -> Self
+    where
+        V: Into<Vec<Link>>,
+    {
+        self.set_links(links);
+        self
+    }
+
+    /// Append a link to this entry, keeping the existing ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.add_link(Link::alternate("http://example.com/"));
+    /// entry.add_link(Link::alternate("http://example.com/en/"));
+    /// assert_eq!(entry.links().len(), 2);
+    /// ```
+    pub fn add_link(&mut self, link: Link) {
+        self.links.push(link);
+    }
+
+    /// Set a link of this entry, replacing every existing link with the same relation type.
+    ///
+    /// The link takes the place of the first replaced one, or is appended when there is none.
+    /// Relation types are compared as returned by [`Link::normalized_rel`], so `self` and its
+    /// IANA registry URI are the same relation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![
+    ///     Link::alternate("http://example.com/old"),
+    ///     Link::self_link("http://example.com/feed"),
+    ///     Link::alternate("http://example.com/older"),
+    /// ]);
+    /// entry.set_link(Link::alternate("http://example.com/new"));
+    ///
+    /// let hrefs = entry.links().iter().map(Link::href).collect::<Vec<_>>();
+    /// assert_eq!(hrefs, vec!["http://example.com/new", "http://example.com/feed"]);
+    /// ```
+    pub fn set_link(&mut self, link: Link) {
+        replace_link(&mut self.links, link);
+    }
+
     /// Return the time that this entry was initially created or first made available.
     ///
     /// # Examples
@@ -330,191 +653,1824 @@ impl Entry {
         self.published = published.into();
     }
 
-    /// Return the information about the rights held in and over this entry.
+    /// Set the publication timestamp of this entry and return the entry.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Text};
+    /// use atom_syndication::{Entry, FixedDateTime};
     ///
-    /// let mut entry = Entry::default();
-    /// entry.set_rights(Text::from("© 2017 John Doe"));
-    /// assert_eq!(entry.rights().map(Text::as_str), Some("© 2017 John Doe"));
+    /// let entry = Entry::default().with_published(FixedDateTime::parse_from_rfc3339("2017-06-03T15:15:44-05:00").unwrap());
+    /// assert!(entry.published().is_some());
     /// ```
-    pub fn rights(&self) -> Option<&Text> {
-        self.rights.as_ref()
+    pub fn with_published<V>(mut self, published: V) -> Self
+    where
+        V: Into<Option<FixedDateTime>>,
+    {
+        self.set_published(published);
+        self
     }
 
-    /// Set the information about the rights held in and over this entry.
+    /// Return the time this entry was first published, or the last time it was modified if the
+    /// publication time is unknown.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Text};
+    /// use atom_syndication::{Entry, FixedDateTime};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_rights(Text::from("© 2017 John Doe"));
+    /// entry.set_updated(FixedDateTime::parse_from_rfc3339("2017-06-03T15:15:44-05:00").unwrap());
+    /// assert_eq!(entry.published_or_updated(), entry.updated());
+    ///
+    /// let published = FixedDateTime::parse_from_rfc3339("2017-06-01T09:00:00-05:00").unwrap();
+    /// entry.set_published(published);
+    /// assert_eq!(entry.published_or_updated(), &published);
     /// ```
-    pub fn set_rights<V>(&mut self, rights: V)
-    where
-        V: Into<Option<Text>>,
-    {
-        self.rights = rights.into();
+    pub fn published_or_updated(&self) -> &FixedDateTime {
+        self.published.as_ref().unwrap_or(&self.updated)
     }
 
-    /// Return the source of this entry if it was copied from another feed.
+    /// Return the last time this entry was modified as a `SystemTime`.
+    ///
+    /// The UTC offset and fractional seconds of the timestamp are taken into account. An error
+    /// is returned if the timestamp cannot be represented as a `SystemTime` on this platform.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Source};
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use atom_syndication::{Entry, FixedDateTime};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_source(Source::default());
-    /// assert!(entry.source().is_some());
+    /// entry.set_updated(FixedDateTime::parse_from_rfc3339("1970-01-01T01:00:01+01:00").unwrap());
+    /// assert_eq!(entry.updated_system_time().unwrap(), UNIX_EPOCH + Duration::from_secs(1));
     /// ```
-    pub fn source(&self) -> Option<&Source> {
-        self.source.as_ref()
+    pub fn updated_system_time(&self) -> Result<SystemTime, Error> {
+        system_time(&self.updated)
     }
 
-    /// Set the source of this entry if it was copied from another feed.
+    /// Set the last time this entry was modified from a `SystemTime`.
+    ///
+    /// The time is stored in UTC with its fractional seconds, so it is written as an RFC 3339
+    /// timestamp such as `2021-05-01T12:00:00.5+00:00`.
+    ///
+    /// Fails with [`Error::WrongDatetime`] if the time is more than about 200,000 years away
+    /// from the Unix epoch, leaving the current time unchanged.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Source};
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use atom_syndication::Entry;
+    ///
+    /// let time = UNIX_EPOCH + Duration::from_millis(1_500);
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_source(Source::default());
+    /// entry.set_updated_time(time).unwrap();
+    /// assert_eq!(entry.updated().to_rfc3339(), "1970-01-01T00:00:01.500+00:00");
+    /// assert_eq!(entry.updated_system_time().unwrap(), time);
     /// ```
-    pub fn set_source<V>(&mut self, source: V)
-    where
-        V: Into<Option<Source>>,
-    {
-        self.source = source.into()
+    pub fn set_updated_time(&mut self, time: SystemTime) -> Result<(), Error> {
+        self.updated = from_system_time(time)?;
+        Ok(())
     }
 
-    /// Return the summary of this entry.
+    /// Return the time this entry was first published as a `SystemTime`.
+    ///
+    /// See [`Entry::updated_system_time`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Text};
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use atom_syndication::{Entry, FixedDateTime};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_summary(Text::from("Entry summary."));
-    /// assert_eq!(entry.summary().map(Text::as_str), Some("Entry summary."));
+    /// assert!(entry.published_system_time().unwrap().is_none());
+    ///
+    /// entry.set_published(FixedDateTime::parse_from_rfc3339("1970-01-01T00:01:00Z").unwrap());
+    /// assert_eq!(
+    ///     entry.published_system_time().unwrap(),
+    ///     Some(UNIX_EPOCH + Duration::from_secs(60))
+    /// );
     /// ```
-    pub fn summary(&self) -> Option<&Text> {
-        self.summary.as_ref()
+    pub fn published_system_time(&self) -> Result<Option<SystemTime>, Error> {
+        self.published.as_ref().map(system_time).transpose()
     }
 
-    /// Set the summary of this entry.
+    /// Return whether this entry was last modified strictly after `time`.
+    ///
+    /// Entries whose `updated` date cannot be represented as a `SystemTime` are never considered
+    /// updated after `time`, see [`Entry::updated_system_time`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Text};
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use atom_syndication::{Entry, FixedDateTime};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_summary(Text::from("Entry summary."));
+    /// entry.set_updated(FixedDateTime::parse_from_rfc3339("1970-01-01T00:01:00Z").unwrap());
+    /// assert!(entry.is_updated_after(UNIX_EPOCH));
+    /// assert!(!entry.is_updated_after(UNIX_EPOCH + Duration::from_secs(60)));
     /// ```
-    pub fn set_summary<V>(&mut self, summary: V)
-    where
-        V: Into<Option<Text>>,
-    {
-        self.summary = summary.into();
+    pub fn is_updated_after(&self, time: SystemTime) -> bool {
+        self.updated_system_time()
+            .map(|updated| updated > time)
+            .unwrap_or(false)
     }
 
-    /// Return the content of this entry.
+    /// Return the information about the rights held in and over this entry.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Content};
+    /// use atom_syndication::{Entry, Text};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_content(Content::default());
-    /// assert!(entry.content().is_some());
+    /// entry.set_rights(Text::from("© 2017 John Doe"));
+    /// assert_eq!(entry.rights().map(Text::as_str), Some("© 2017 John Doe"));
     /// ```
-    pub fn content(&self) -> Option<&Content> {
-        self.content.as_ref()
+    pub fn rights(&self) -> Option<&Text> {
+        self.rights.as_ref()
     }
 
-    /// Set the content of this entry.
+    /// Set the information about the rights held in and over this entry.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Entry, Content};
+    /// use atom_syndication::{Entry, Text};
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_content(Content::default());
-    /// assert!(entry.content().is_some());
+    /// entry.set_rights(Text::from("© 2017 John Doe"));
     /// ```
-    pub fn set_content<V>(&mut self, content: V)
+    pub fn set_rights<V>(&mut self, rights: V)
     where
-        V: Into<Option<Content>>,
+        V: Into<Option<Text>>,
     {
-        self.content = content.into();
+        self.rights = rights.into();
     }
 
-    /// Return the extensions for this entry.
+    /// Set the rights of this entry and return the entry.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::BTreeMap;
-    /// use atom_syndication::Entry;
-    /// use atom_syndication::extension::{ExtensionMap, Extension};
-    ///
-    /// let extension = Extension::default();
+    /// use atom_syndication::{Entry, Text};
     ///
-    /// let mut item_map = BTreeMap::<String, Vec<Extension>>::new();
-    /// item_map.insert("ext:name".to_string(), vec![extension]);
+    /// let entry = Entry::default().with_rights(Text::from("© 2017 John Doe"));
+    /// assert_eq!(entry.rights().map(Text::as_str), Some("© 2017 John Doe"));
+    /// ```
+    pub fn with_rights<V>(mut self, rights: V) -> Self
+    where
+        V: Into<Option<Text>>,
+    {
+        self.set_rights(rights);
+        self
+    }
+
+    /// Return the source of this entry if it was copied from another feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Source};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_source(Source::default());
+    /// assert!(entry.source().is_some());
+    /// ```
+    pub fn source(&self) -> Option<&Source> {
+        self.source.as_ref()
+    }
+
+    /// Set the source of this entry if it was copied from another feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Source};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_source(Source::default());
+    /// ```
+    pub fn set_source<V>(&mut self, source: V)
+    where
+        V: Into<Option<Source>>,
+    {
+        self.source = source.into()
+    }
+
+    /// Set the source of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Source};
+    ///
+    /// let entry = Entry::default().with_source(Source::default());
+    /// assert!(entry.source().is_some());
+    /// ```
+    pub fn with_source<V>(mut self, source: V) -> Self
+    where
+        V: Into<Option<Source>>,
+    {
+        self.set_source(source);
+        self
+    }
+
+    /// Return the summary of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Text};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_summary(Text::from("Entry summary."));
+    /// assert_eq!(entry.summary().map(Text::as_str), Some("Entry summary."));
+    /// ```
+    pub fn summary(&self) -> Option<&Text> {
+        self.summary.as_ref()
+    }
+
+    /// Set the summary of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Text};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_summary(Text::from("Entry summary."));
+    /// ```
+    pub fn set_summary<V>(&mut self, summary: V)
+    where
+        V: Into<Option<Text>>,
+    {
+        self.summary = summary.into();
+    }
+
+    /// Set the summary of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Text};
+    ///
+    /// let entry = Entry::default().with_summary(Text::from("Entry summary."));
+    /// assert_eq!(entry.summary().map(Text::as_str), Some("Entry summary."));
+    /// ```
+    pub fn with_summary<V>(mut self, summary: V) -> Self
+    where
+        V: Into<Option<Text>>,
+    {
+        self.set_summary(summary);
+        self
+    }
+
+    /// Set the summary of this entry to plain text (`type="text"`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, TextType};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_summary_text("Entry summary.");
+    /// assert_eq!(entry.summary().unwrap().r#type, TextType::Text);
+    /// ```
+    pub fn set_summary_text<V>(&mut self, text: V)
+    where
+        V: Into<String>,
+    {
+        self.summary = Some(Text::plain(text));
+    }
+
+    /// Set the summary of this entry to HTML (`type="html"`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, TextType};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_summary_html("<p>Entry summary.</p>");
+    /// assert_eq!(entry.summary().unwrap().r#type, TextType::Html);
+    /// ```
+    pub fn set_summary_html<V>(&mut self, html: V)
+    where
+        V: Into<String>,
+    {
+        self.summary = Some(Text::html(html));
+    }
+
+    /// Return the content of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Content};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content(Content::default());
+    /// assert!(entry.content().is_some());
+    /// ```
+    pub fn content(&self) -> Option<&Content> {
+        self.content.as_ref()
+    }
+
+    /// Set the content of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Content};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content(Content::default());
+    /// assert!(entry.content().is_some());
+    /// ```
+    pub fn set_content<V>(&mut self, content: V)
+    where
+        V: Into<Option<Content>>,
+    {
+        self.content = content.into();
+    }
+
+    /// Set the content of this entry and return the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry};
+    ///
+    /// let entry = Entry::default().with_content(Content::default());
+    /// assert!(entry.content().is_some());
+    /// ```
+    pub fn with_content<V>(mut self, content: V) -> Self
+    where
+        V: Into<Option<Content>>,
+    {
+        self.set_content(content);
+        self
+    }
+
+    /// Set the content of this entry to plain text (`type="text"`), replacing any existing
+    /// content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content_text("Entry content.");
+    /// assert_eq!(entry.content().unwrap().content_type(), Some("text"));
+    /// ```
+    pub fn set_content_text<V>(&mut self, text: V)
+    where
+        V: Into<String>,
+    {
+        self.set_content_typed("text", text.into());
+    }
+
+    /// Set the content of this entry to HTML (`type="html"`), replacing any existing content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content_html("<p>Entry content.</p>");
+    /// assert_eq!(entry.content().unwrap().content_type(), Some("html"));
+    /// ```
+    pub fn set_content_html<V>(&mut self, html: V)
+    where
+        V: Into<String>,
+    {
+        self.set_content_typed("html", html.into());
+    }
+
+    /// Set the content of this entry to XHTML (`type="xhtml"`), replacing any existing content.
+    ///
+    /// The markup is wrapped in the `<div>` in the XHTML namespace that Atom requires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content_xhtml("<p>Entry content.</p>");
+    ///
+    /// let content = entry.content().unwrap();
+    /// assert_eq!(content.content_type(), Some("xhtml"));
+    /// assert_eq!(
+    ///     content.value(),
+    ///     Some(r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>Entry content.</p></div>"#)
+    /// );
+    /// ```
+    pub fn set_content_xhtml<V>(&mut self, xhtml: V)
+    where
+        V: Into<String>,
+    {
+        let value = format!(
+            r#"<div xmlns="http://www.w3.org/1999/xhtml">{}</div>"#,
+            xhtml.into()
+        );
+        self.set_content_typed("xhtml", value);
+    }
+
+    fn set_content_typed(&mut self, content_type: &str, value: String) {
+        let mut content = Content::default();
+        content.set_content_type(content_type.to_string());
+        content.set_value(value);
+        self.content = Some(content);
+    }
+
+    /// Return the extensions for this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::extension::{ExtensionMap, Extension};
+    ///
+    /// let extension = Extension::default();
+    ///
+    /// let mut item_map = BTreeMap::<String, Vec<Extension>>::new();
+    /// item_map.insert("ext:name".to_string(), vec![extension]);
+    ///
+    /// let mut extension_map = ExtensionMap::default();
+    /// extension_map.insert("ext".to_string(), item_map);
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_extensions(extension_map);
+    /// assert_eq!(entry.extensions()
+    ///                 .get("ext")
+    ///                 .and_then(|m| m.get("ext:name"))
+    ///                 .map(|v| v.len()),
+    ///            Some(1));
+    /// ```
+    pub fn extensions(&self) -> &ExtensionMap {
+        &self.extensions
+    }
+
+    /// Set the extensions for this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::extension::ExtensionMap;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_extensions(ExtensionMap::default());
+    /// ```
+    pub fn set_extensions<V>(&mut self, extensions: V)
+    where
+        V: Into<ExtensionMap>,
+    {
+        self.extensions = extensions.into()
+    }
+
+    /// Return the namespaces bound outside of this entry to the prefixes of its extensions,
+    /// keyed by prefix.
+    ///
+    /// When reading, these are the namespaces the feed containing the entry binds to the
+    /// prefixes its extensions use; namespaces declared on the entry itself are kept among the
+    /// [unknown attributes](Entry::unknown_attrs). When writing, the ones not already declared
+    /// on the feed are declared on the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = r#"
+    ///     <feed xmlns:media="http://search.yahoo.com/mrss/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+    ///         <entry><media:thumbnail url="http://example.com/1.jpg"/></entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    /// let namespaces = feed.entries()[0].namespaces();
+    /// assert_eq!(namespaces.get("media").map(String::as_str), Some("http://search.yahoo.com/mrss/"));
+    /// assert_eq!(namespaces.get("dc"), None);
+    /// ```
+    pub fn namespaces(&self) -> &BTreeMap<String, String> {
+        &self.namespaces
+    }
+
+    /// Set the namespaces bound outside of this entry to the prefixes of its extensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_namespaces(BTreeMap::new());
+    /// ```
+    pub fn set_namespaces<V>(&mut self, namespaces: V)
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.namespaces = namespaces.into()
+    }
+
+    /// Return the base URL of this entry, given by its `xml:base` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_base("http://example.com/posts/".to_string());
+    /// assert_eq!(entry.base(), Some("http://example.com/posts/"));
+    /// ```
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_deref()
+    }
+
+    /// Set the base URL of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_base("http://example.com/posts/".to_string());
+    /// ```
+    pub fn set_base<V>(&mut self, base: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.base = base.into();
+    }
+
+    /// Return the natural language of this entry, given by its `xml:lang` attribute.
+    ///
+    /// See [`Entry::effective_lang`] for the language inherited from the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_lang("de".to_string());
+    /// assert_eq!(entry.lang(), Some("de"));
+    /// ```
+    pub fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    /// Set the natural language of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_lang("de".to_string());
+    /// ```
+    pub fn set_lang<V>(&mut self, lang: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.lang = lang.into();
+    }
+
+    /// Return the attributes of this entry that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_unknown_attrs(vec![("v:id".to_string(), "7".to_string())]);
+    /// assert_eq!(entry.unknown_attrs(), &[("v:id".to_string(), "7".to_string())]);
+    /// ```
+    pub fn unknown_attrs(&self) -> &[(String, String)] {
+        &self.unknown_attrs
+    }
+
+    /// Set the attributes of this entry that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_unknown_attrs(vec![("v:id".to_string(), "7".to_string())]);
+    /// ```
+    pub fn set_unknown_attrs<V>(&mut self, unknown_attrs: V)
+    where
+        V: Into<Vec<(String, String)>>,
+    {
+        self.unknown_attrs = unknown_attrs.into();
+    }
+
+    /// Return the XML comments inside this entry.
+    ///
+    /// They are written at the start of the entry. A comment that XML does not allow, because
+    /// it contains `--` or ends with `-`, is written with a space after each such hyphen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_comments(vec![" editorial note ".to_string()]);
+    /// assert_eq!(entry.comments(), &[" editorial note ".to_string()]);
+    /// ```
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    /// Set the XML comments inside this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_comments(vec![" editorial note ".to_string()]);
+    /// ```
+    pub fn set_comments<V>(&mut self, comments: V)
+    where
+        V: Into<Vec<String>>,
+    {
+        self.comments = comments.into();
+    }
+
+    /// Return the XML comments directly before this entry.
+    ///
+    /// They are written before the `<entry>` tag, escaped like [`Entry::comments`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_leading_comments(vec![" first post ".to_string()]);
+    /// assert_eq!(entry.leading_comments(), &[" first post ".to_string()]);
+    /// ```
+    pub fn leading_comments(&self) -> &[String] {
+        &self.leading_comments
+    }
+
+    /// Set the XML comments directly before this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_leading_comments(vec![" first post ".to_string()]);
+    /// ```
+    pub fn set_leading_comments<V>(&mut self, leading_comments: V)
+    where
+        V: Into<Vec<String>>,
+    {
+        self.leading_comments = leading_comments.into();
+    }
+
+    /// Remove the links of this entry that repeat an earlier link, keeping the first one.
+    ///
+    /// Links are duplicates when their `href`, relation type and media type are equal. Relation
+    /// types are compared as returned by [`Link::normalized_rel`], so `self` and its IANA
+    /// registry URI are the same relation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![
+    ///     Link::alternate("http://example.com/1"),
+    ///     Link::related("http://example.com/1"),
+    ///     Link::alternate("http://example.com/1"),
+    /// ]);
+    /// entry.dedup_links();
+    /// assert_eq!(entry.links().len(), 2);
+    /// ```
+    pub fn dedup_links(&mut self) {
+        dedup_links(&mut self.links);
+    }
+
+    /// Return the link used to edit this entry with the Atom Publishing Protocol
+    /// (`rel="edit"`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("edit");
+    /// link.set_href("http://example.com/entries/1");
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![link]);
+    /// assert_eq!(entry.edit_link().map(Link::href), Some("http://example.com/entries/1"));
+    /// ```
+    pub fn edit_link(&self) -> Option<&Link> {
+        find_link(&self.links, "edit")
+    }
+
+    /// Return the link used to edit the media resource associated with this entry with the
+    /// Atom Publishing Protocol (`rel="edit-media"`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("edit-media");
+    /// link.set_href("http://example.com/media/1.png");
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![link]);
+    /// assert_eq!(entry.edit_media_link().map(Link::href), Some("http://example.com/media/1.png"));
+    /// ```
+    pub fn edit_media_link(&self) -> Option<&Link> {
+        find_link(&self.links, "edit-media")
+    }
+
+    /// Return the link to the license of this entry (`rel="license"`, RFC 4946).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("license");
+    /// link.set_href("https://creativecommons.org/licenses/by/4.0/");
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![link]);
+    /// assert_eq!(
+    ///     entry.license_link().map(Link::href),
+    ///     Some("https://creativecommons.org/licenses/by/4.0/")
+    /// );
+    /// ```
+    pub fn license_link(&self) -> Option<&Link> {
+        find_link(&self.links, "license")
+    }
+
+    /// Return the URL that best identifies this entry on the web.
+    ///
+    /// This is the `href` of the first alternate link (`rel="alternate"`, or no `rel` at all)
+    /// with a non-empty `href`. If there is none, the `id` is returned when it looks like a web
+    /// URL, that is when it starts with `http://` or `https://` in any case, since many feeds
+    /// use the permalink of an entry as its id. Otherwise `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_id("https://example.com/posts/1");
+    /// assert_eq!(entry.canonical_url(), Some("https://example.com/posts/1"));
+    ///
+    /// entry.set_links(vec![Link::alternate("https://example.com/1")]);
+    /// assert_eq!(entry.canonical_url(), Some("https://example.com/1"));
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_id("urn:uuid:60a76c80-d399-11d9-b93c-0003939e0af6");
+    /// assert_eq!(entry.canonical_url(), None);
+    /// ```
+    pub fn canonical_url(&self) -> Option<&str> {
+        let alternate = self
+            .links
+            .iter()
+            .find(|link| link.is_alternate() && !link.href.is_empty());
+        if let Some(link) = alternate {
+            return Some(&link.href);
+        }
+
+        let id = self.id.as_str();
+        let is_web_url = ["http://", "https://"].iter().any(|scheme| {
+            id.len() > scheme.len()
+                && id.is_char_boundary(scheme.len())
+                && id[..scheme.len()].eq_ignore_ascii_case(scheme)
+        });
+        if is_web_url {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    /// Return the authors of this entry, or the authors of `feed` if this entry has none.
+    ///
+    /// RFC 4287 specifies that an entry without authors inherits the authors of its feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, Person};
+    ///
+    /// let mut author = Person::default();
+    /// author.set_name("John Doe");
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_authors(vec![author]);
+    ///
+    /// let entry = Entry::default();
+    /// assert_eq!(entry.effective_authors(&feed)[0].name(), "John Doe");
+    /// ```
+    pub fn effective_authors<'a>(&'a self, feed: &'a Feed) -> &'a [Person] {
+        if self.authors.is_empty() {
+            &feed.authors
+        } else {
+            &self.authors
+        }
+    }
+
+    /// Return the authors of this entry, or the authors of its source, or the authors of `feed`.
+    ///
+    /// The precedence is entry, then source, then feed: RFC 4287 specifies that an entry
+    /// without authors takes those of its `source` element if it has one, and only otherwise
+    /// those of its feed, so an entry copied from another feed stays attributed to the
+    /// original authors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, Person, Source};
+    ///
+    /// let mut original = Person::default();
+    /// original.set_name("Original Author");
+    /// let mut source = Source::default();
+    /// source.set_authors(vec![original]);
+    ///
+    /// let mut aggregator = Person::default();
+    /// aggregator.set_name("Aggregator");
+    /// let mut feed = Feed::default();
+    /// feed.set_authors(vec![aggregator]);
+    ///
+    /// let mut entry = Entry::default();
+    /// assert_eq!(entry.effective_authors_with_source(&feed)[0].name(), "Aggregator");
+    ///
+    /// entry.set_source(source);
+    /// assert_eq!(entry.effective_authors_with_source(&feed)[0].name(), "Original Author");
+    /// ```
+    pub fn effective_authors_with_source<'a>(&'a self, feed: &'a Feed) -> &'a [Person] {
+        if !self.authors.is_empty() {
+            return &self.authors;
+        }
+
+        match self.source {
+            Some(ref source) if !source.authors.is_empty() => &source.authors,
+            _ => &feed.authors,
+        }
+    }
+
+    /// Return the natural language of this entry: its `xml:lang` attribute, or the language of
+    /// `feed` if the entry has none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_lang("en".to_string());
+    ///
+    /// let mut entry = Entry::default();
+    /// assert_eq!(entry.effective_lang(&feed), Some("en"));
+    ///
+    /// entry.set_lang("de".to_string());
+    /// assert_eq!(entry.effective_lang(&feed), Some("de"));
+    /// ```
+    pub fn effective_lang<'a>(&'a self, feed: &'a Feed) -> Option<&'a str> {
+        self.lang().or_else(|| feed.lang())
+    }
+
+    /// Return the rights held in and over this entry, or the rights of `feed` if the entry has
+    /// none.
+    ///
+    /// RFC 4287 specifies that the rights of a feed apply to an entry without its own `rights`
+    /// element, so this is the value to show in a copyright notice for the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, Text};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_rights(Text::from("© 2021 Feed Owner"));
+    ///
+    /// let mut entry = Entry::default();
+    /// assert_eq!(entry.effective_rights(&feed), Some("© 2021 Feed Owner"));
+    ///
+    /// entry.set_rights(Text::from("CC BY 4.0"));
+    /// assert_eq!(entry.effective_rights(&feed), Some("CC BY 4.0"));
+    /// ```
+    pub fn effective_rights<'a>(&'a self, feed: &'a Feed) -> Option<&'a str> {
+        self.rights
+            .as_ref()
+            .or(feed.rights.as_ref())
+            .map(Text::as_str)
+    }
+
+    /// Return the rights held in and over this entry, or the rights of its source, or the rights
+    /// of `feed`.
+    ///
+    /// The precedence is entry, then source, then feed, as for
+    /// [`Entry::effective_authors_with_source`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, Source, Text};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_rights(Text::from("© 2021 Aggregator"));
+    ///
+    /// let mut source = Source::default();
+    /// source.set_rights(Text::from("© 2021 Original Publisher"));
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_source(source);
+    /// assert_eq!(
+    ///     entry.effective_rights_with_source(&feed),
+    ///     Some("© 2021 Original Publisher")
+    /// );
+    /// ```
+    pub fn effective_rights_with_source<'a>(&'a self, feed: &'a Feed) -> Option<&'a str> {
+        self.rights
+            .as_ref()
+            .or_else(|| self.source.as_ref()?.rights.as_ref())
+            .or(feed.rights.as_ref())
+            .map(Text::as_str)
+    }
+
+    /// Set the id of this entry to a `urn:uuid:` URI derived from `seed` if it has no id.
+    ///
+    /// The URI holds a version 5 UUID of the seed in the URL namespace
+    /// (`6ba7b811-9dad-11d1-80b4-00c04fd430c8`), so the same seed always produces the same id.
+    /// Returns `true` if the id was set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// assert!(entry.ensure_id_from("http://example.com/posts/1"));
+    /// assert_eq!(entry.id(), "urn:uuid:3a3d7ccf-0982-563c-8240-971a567a25c0");
+    ///
+    /// assert!(!entry.ensure_id_from("http://example.com/posts/2"));
+    /// ```
+    #[cfg(feature = "uuid")]
+    pub fn ensure_id_from(&mut self, seed: &str) -> bool {
+        if !self.id.trim().is_empty() {
+            return false;
+        }
+
+        self.id = crate::util::uuid_urn(seed);
+        true
+    }
+
+    /// Return the `rel="related"` links of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("related");
+    /// link.set_href("http://example.com/other");
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![Link::default(), link]);
+    /// assert_eq!(entry.related_links().len(), 1);
+    /// assert_eq!(entry.related_links()[0].href(), "http://example.com/other");
+    /// ```
+    pub fn related_links(&self) -> Vec<&Link> {
+        self.links
+            .iter()
+            .filter(|link| link.relation() == "related")
+            .collect()
+    }
+
+    /// Return the links of this entry whose `type` is the given MIME type.
+    ///
+    /// Types are compared case-insensitively and parameters such as `; charset=utf-8` are
+    /// ignored. A `mime` ending in `/` matches every subtype, so `image/` matches `image/png`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![
+    ///     Link::enclosure("http://example.com/1.png", "image/png", 10),
+    ///     Link::enclosure("http://example.com/1.pdf", "application/pdf", 20),
+    /// ]);
+    /// assert_eq!(entry.links_of_type("application/pdf")[0].href(), "http://example.com/1.pdf");
+    /// assert_eq!(entry.links_of_type("image/")[0].href(), "http://example.com/1.png");
+    /// ```
+    pub fn links_of_type(&self, mime: &str) -> Vec<&Link> {
+        let mime = mime.trim().to_ascii_lowercase();
+        self.links
+            .iter()
+            .filter(|link| {
+                let link_type = match link.mime_type {
+                    Some(ref link_type) => link_type,
+                    None => return false,
+                };
+                let essence = link_type.split(';').next().unwrap_or_default().trim();
+                let essence = essence.to_ascii_lowercase();
+                if mime.ends_with('/') {
+                    essence.starts_with(&mime) && essence.len() > mime.len()
+                } else {
+                    essence == mime
+                }
+            })
+            .collect()
+    }
+
+    /// Return the `rel="replies"` link of this entry along with the reply count and date from
+    /// the threading extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("replies");
+    /// link.set_href("http://example.com/comments");
+    /// link.set_thr_count("5".to_string());
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![link]);
+    ///
+    /// let replies = entry.replies_link().unwrap();
+    /// assert_eq!(replies.href, "http://example.com/comments");
+    /// assert_eq!(replies.count, Some(5));
+    /// assert_eq!(replies.updated, None);
+    /// ```
+    pub fn replies_link(&self) -> Option<RepliesLink> {
+        find_link(&self.links, "replies").map(RepliesLink::from)
+    }
+
+    /// Return the URL of the image that best represents this entry.
+    ///
+    /// The image is looked up, in order, as a Media RSS `<media:thumbnail>` (directly on the
+    /// entry or inside `<media:group>` or `<media:content>`), a `rel="enclosure"` link with an
+    /// `image/*` type, and the first `<img src>` in HTML or XHTML content. Media RSS elements are
+    /// found by namespace, whatever prefix the feed or entry binds it to, see
+    /// [`Entry::namespaces`] and [`Entry::unknown_attrs`]; the `media` prefix is assumed when it
+    /// is not declared. With the
+    /// `url` feature an image found in the content is resolved against the `xml:base` of the
+    /// content and of the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("enclosure");
+    /// link.set_mime_type("image/png".to_string());
+    /// link.set_href("http://example.com/image.png");
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content_html(r#"<p><img src="http://example.com/inline.png"></p>"#);
+    /// assert_eq!(entry.image_url().as_deref(), Some("http://example.com/inline.png"));
+    ///
+    /// entry.set_links(vec![link]);
+    /// assert_eq!(entry.image_url().as_deref(), Some("http://example.com/image.png"));
+    /// ```
+    pub fn image_url(&self) -> Option<String> {
+        self.media_thumbnail()
+            .or_else(|| {
+                self.links
+                    .iter()
+                    .find(|link| {
+                        link.is_enclosure()
+                            && link
+                                .mime_type
+                                .as_ref()
+                                .into_iter()
+                                .any(|mime_type| mime_type.starts_with("image/"))
+                    })
+                    .map(|link| link.href.clone())
+            })
+            .or_else(|| self.content_image())
+    }
+
+    /// Return the URL of every `<a href>` in the HTML or XHTML content and summary of this
+    /// entry.
+    ///
+    /// Each URL is returned once, in the order it first appears, and fragment-only links such
+    /// as `#section` are skipped. With the `url` feature relative URLs are resolved against the
+    /// `xml:base` of the content or summary and of the entry; without a base they are returned
+    /// as they appear.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Text};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content_html(
+    ///     r##"<p><a href="http://example.com/a">A</a> <a href="#top">Top</a></p>"##,
+    /// );
+    /// entry.set_summary(Text::html(r#"<a href="http://example.com/a">A</a>"#));
+    /// assert_eq!(entry.content_links(), vec!["http://example.com/a"]);
+    /// ```
+    #[cfg(feature = "html")]
+    pub fn content_links(&self) -> Vec<String> {
+        let content = self.content.as_ref().and_then(|content| {
+            match content.content_type.as_deref() {
+                Some("html") | Some("xhtml") | Some("text/html") => {}
+                _ => return None,
+            }
+            Some((content.value.as_deref()?, content.base.as_deref()))
+        });
+        let summary = self
+            .summary
+            .as_ref()
+            .filter(|summary| summary.r#type != TextType::Text)
+            .map(|summary| (summary.value.as_str(), summary.base.as_deref()));
+
+        let mut links = Vec::new();
+        for (html, base) in content.into_iter().chain(summary) {
+            for tag in find_tags(html, "a") {
+                let href = match tag.attr("href").map(str::trim) {
+                    Some(href) if !href.is_empty() && !href.starts_with('#') => href,
+                    _ => continue,
+                };
+                let href = self.resolve_href(base, href);
+                if !links.contains(&href) {
+                    links.push(href);
+                }
+            }
+        }
+        links
+    }
+
+    /// Resolve a URI reference found in an element with the `xml:base` `base` against the bases
+    /// in scope, or return it unchanged without the `url` feature or an absolute base.
+    #[cfg_attr(not(feature = "url"), allow(unused_variables))]
+    fn resolve_href(&self, base: Option<&str>, href: &str) -> String {
+        #[cfg(feature = "url")]
+        {
+            let entry_base = self
+                .base
+                .as_deref()
+                .and_then(|base| url::Url::parse(base.trim()).ok());
+            let base = match (entry_base, base) {
+                (Some(entry_base), Some(base)) => entry_base.join(base.trim()).ok(),
+                (None, Some(base)) => url::Url::parse(base.trim()).ok(),
+                (entry_base, None) => entry_base,
+            };
+            if let Some(url) = base.and_then(|base| base.join(href).ok()) {
+                return url.into();
+            }
+        }
+        href.to_string()
+    }
+
+    /// Return the number of comments on this entry.
     ///
-    /// let mut extension_map = ExtensionMap::default();
-    /// extension_map.insert("ext".to_string(), item_map);
+    /// The count is read from the Slash extension's `slash:comments` element, falling back to
+    /// the threading extension's `thr:total` element. The elements are found by namespace,
+    /// whatever prefix the feed or entry binds it to, see [`Entry::namespaces`]; the `slash` and
+    /// `thr` prefixes are assumed when they are not declared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = r#"
+    ///     <feed xmlns:slash="http://purl.org/rss/1.0/modules/slash/">
+    ///         <entry><slash:comments>42</slash:comments></entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.entries()[0].comment_count(), Some(42));
+    /// ```
+    pub fn comment_count(&self) -> Option<u32> {
+        let namespaces = self.namespaces_in_scope();
+        let count = |namespace: &str, prefix: &str, name: &str| {
+            find_extensions(&self.extensions, &namespaces, namespace, prefix, name)
+                .iter()
+                .find_map(|extension| extension.value.as_deref()?.trim().parse().ok())
+        };
+
+        count(SLASH_NAMESPACE, "slash", "comments")
+            .or_else(|| count(THREADING_NAMESPACE, "thr", "total"))
+    }
+
+    /// Return the playing time of the media of this entry, such as a podcast episode.
+    ///
+    /// The duration is read from the iTunes extension's `itunes:duration` element, falling
+    /// back to the `duration` attribute of a Media RSS `<media:content>` element, directly on
+    /// the entry or inside `<media:group>`. Values may be given as seconds or as `MM:SS` or
+    /// `HH:MM:SS`, with an optional fraction of a second; values in any other format are
+    /// skipped. The elements are found by namespace, whatever prefix the feed or entry binds
+    /// it to, see [`Entry::namespaces`]; the `itunes` and `media` prefixes are assumed when
+    /// they are not declared. Enclosure links are not consulted, since their `length`
+    /// is a size in bytes rather than a duration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = r#"
+    ///     <feed xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+    ///         <entry><itunes:duration>1:02:03</itunes:duration></entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.entries()[0].media_duration(), Some(Duration::from_secs(3723)));
+    /// ```
+    pub fn media_duration(&self) -> Option<Duration> {
+        let namespaces = self.namespaces_in_scope();
+        let find = |namespace: &str, prefix: &str, name: &str| {
+            find_extensions(&self.extensions, &namespaces, namespace, prefix, name)
+        };
+
+        let itunes = find(ITUNES_NAMESPACE, "itunes", "duration")
+            .into_iter()
+            .filter_map(|extension| extension.value.as_deref());
+        let contents = find(MEDIA_NAMESPACE, "media", "content").into_iter().chain(
+            find(MEDIA_NAMESPACE, "media", "group")
+                .into_iter()
+                .flat_map(|group| group.children.get("content").into_iter().flatten()),
+        );
+        let media =
+            contents.filter_map(|content| content.attrs.get("duration").map(String::as_str));
+
+        itunes.chain(media).find_map(parse_duration)
+    }
+
+    /// Return the namespaces declared on this entry, keyed by prefix.
+    fn declared_namespaces(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.unknown_attrs.iter().filter_map(|(key, value)| {
+            if key.starts_with("xmlns:") {
+                Some((&key["xmlns:".len()..], value.as_str()))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Return the namespaces bound to prefixes in this entry, by the entry itself or outside of
+    /// it, keyed by prefix.
+    pub(crate) fn namespaces_in_scope(&self) -> BTreeMap<String, String> {
+        let mut namespaces = self.namespaces.clone();
+        for (prefix, namespace) in self.declared_namespaces() {
+            namespaces.insert(prefix.to_string(), namespace.to_string());
+        }
+        namespaces
+    }
+
+    fn media_thumbnail(&self) -> Option<String> {
+        let namespaces = self.namespaces_in_scope();
+        let find = |name: &str| {
+            find_extensions(
+                &self.extensions,
+                &namespaces,
+                MEDIA_NAMESPACE,
+                "media",
+                name,
+            )
+        };
+        let thumbnail_url = |thumbnails: Option<&Vec<Extension>>| {
+            thumbnails
+                .into_iter()
+                .flatten()
+                .find_map(|thumbnail| thumbnail.attrs.get("url"))
+                .cloned()
+        };
+
+        find("thumbnail")
+            .into_iter()
+            .find_map(|thumbnail| thumbnail.attrs.get("url").cloned())
+            .or_else(|| {
+                find("group")
+                    .into_iter()
+                    .chain(find("content"))
+                    .find_map(|parent| thumbnail_url(parent.children.get("thumbnail")))
+            })
+    }
+
+    fn content_image(&self) -> Option<String> {
+        let content = self.content.as_ref()?;
+        match content.content_type.as_deref() {
+            Some("html") | Some("xhtml") | Some("text/html") => {}
+            _ => return None,
+        }
+
+        let value = content.value.as_deref()?;
+        let src = find_tags(value, "img")
+            .iter()
+            .filter_map(|tag| tag.attr("src"))
+            .map(str::trim)
+            .find(|src| !src.is_empty())?
+            .to_string();
+
+        Some(self.resolve_href(content.base.as_deref(), &src))
+    }
+
+    /// Return the inline content of this entry exactly as it is stored, whatever its type.
+    ///
+    /// This is the decoded but unsanitized value: character and entity references of the
+    /// feed are resolved, unless the feed was read with
+    /// [`ReadOptions::raw_text`](crate::ReadOptions::raw_text), and nothing else is changed.
+    /// HTML is neither escaped nor sanitized, unlike [`Entry::content_html`], so this suits
+    /// running an HTML processor of one's own. Only `Feed::sanitize_html`, available with the
+    /// `html` feature, changes the stored value. `None` is returned when the entry has no
+    /// content or its content is referenced by `src`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = r#"
+    ///     <feed>
+    ///         <entry>
+    ///             <content type="html">&lt;p onclick="x()"&gt;Fish &amp;amp; Chips&lt;/p&gt;</content>
+    ///         </entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let entry = &feed.entries()[0];
+    /// assert_eq!(entry.content_raw(), Some(r#"<p onclick="x()">Fish &amp; Chips</p>"#));
+    /// ```
+    pub fn content_raw(&self) -> Option<&str> {
+        self.content.as_ref()?.value.as_deref()
+    }
+
+    /// Return the content of this entry as an HTML fragment, whatever its type.
+    ///
+    /// Plain text content (`type="text"`, or no type) is HTML-escaped and its line breaks are
+    /// turned into `<br>`, while HTML content is returned as is. XHTML content is returned
+    /// without the `div` element that wraps it, which is recognized by its local name whether
+    /// it declares the XHTML namespace as the default or uses a prefix such as `xhtml:div`;
+    /// only that one level is removed. Content of any other media type and content referenced
+    /// by `src` return `None`. The markup is returned as found in the feed, so it must be
+    /// sanitized before rendering if the feed is untrusted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_extensions(extension_map);
-    /// assert_eq!(entry.extensions()
-    ///                 .get("ext")
-    ///                 .and_then(|m| m.get("ext:name"))
-    ///                 .map(|v| v.len()),
-    ///            Some(1));
+    /// entry.set_content_text("Fish & Chips\nSalt <optional>");
+    /// assert_eq!(
+    ///     entry.content_html().as_deref(),
+    ///     Some("Fish &amp; Chips<br>Salt &lt;optional&gt;")
+    /// );
+    ///
+    /// entry.set_content_html("<p>Fish &amp; Chips</p>");
+    /// assert_eq!(entry.content_html().as_deref(), Some("<p>Fish &amp; Chips</p>"));
     /// ```
-    pub fn extensions(&self) -> &ExtensionMap {
-        &self.extensions
+    pub fn content_html(&self) -> Option<String> {
+        let content = self.content.as_ref()?;
+        let value = content.value.as_deref()?;
+        match content.content_type.as_deref() {
+            None | Some("text") | Some("text/plain") => Some(text_to_html(value)),
+            Some("html") | Some("text/html") => Some(value.to_string()),
+            Some("xhtml") => Some(unwrap_xhtml_div(value).to_string()),
+            Some(_) => None,
+        }
     }
 
-    /// Set the extensions for this entry.
+    /// Return the summary of this entry as an HTML fragment, whatever its type.
+    ///
+    /// See [`Entry::content_html`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Text};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_summary(Text::plain("1 < 2"));
+    /// assert_eq!(entry.summary_html().as_deref(), Some("1 &lt; 2"));
+    ///
+    /// entry.set_summary(Text::html("<b>Bold</b>"));
+    /// assert_eq!(entry.summary_html().as_deref(), Some("<b>Bold</b>"));
+    /// ```
+    pub fn summary_html(&self) -> Option<String> {
+        let summary = self.summary.as_ref()?;
+        match summary.r#type {
+            TextType::Text => Some(text_to_html(&summary.value)),
+            TextType::Html => Some(summary.value.clone()),
+            TextType::Xhtml => Some(unwrap_xhtml_div(&summary.value).to_string()),
+        }
+    }
+
+    /// Return the readable text of this entry: its content if it has textual inline content,
+    /// otherwise its summary, with any markup removed.
+    fn plain_text(&self) -> Option<String> {
+        self.content_text().or_else(|| self.summary_text())
+    }
+
+    /// Return the textual inline content of this entry with any markup removed.
+    fn content_text(&self) -> Option<String> {
+        let content = self.content.as_ref()?;
+        let value = content.value.as_deref()?;
+        match content.content_type.as_deref() {
+            None | Some("text") | Some("text/plain") => Some(value.to_string()),
+            Some("html") | Some("xhtml") | Some("text/html") => Some(strip_tags(value)),
+            Some(_) => None,
+        }
+    }
+
+    /// Return the summary of this entry with any markup removed.
+    fn summary_text(&self) -> Option<String> {
+        self.summary.as_ref().map(|summary| match summary.r#type {
+            TextType::Text => summary.value.clone(),
+            TextType::Html | TextType::Xhtml => strip_tags(&summary.value),
+        })
+    }
+
+    /// Return a plain text teaser for this entry: its summary if it has one, otherwise the start
+    /// of its content, shortened to at most `max_chars` characters.
+    ///
+    /// Markup is removed and runs of whitespace are collapsed to a single space. A summary is
+    /// returned whole, while content longer than `max_chars` is cut at the last word boundary
+    /// that fits and ends with `…`, which counts towards the limit. Characters are counted as
+    /// Unicode scalar values, so the text is never cut inside a character, although a
+    /// combining sequence may be split. An entry with neither a summary nor textual content
+    /// returns an empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Text};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content_html("<p>The quick brown fox</p> <p>jumps over the lazy dog.</p>");
+    /// assert_eq!(entry.generate_summary(24), "The quick brown fox…");
+    ///
+    /// entry.set_summary(Text::html("<b>A fox</b> jumps."));
+    /// assert_eq!(entry.generate_summary(5), "A fox jumps.");
+    /// ```
+    pub fn generate_summary(&self, max_chars: usize) -> String {
+        match self
+            .summary_text()
+            .filter(|summary| !summary.trim().is_empty())
+        {
+            Some(summary) => summary.split_whitespace().collect::<Vec<_>>().join(" "),
+            None => truncate_words(&self.content_text().unwrap_or_default(), max_chars),
+        }
+    }
+
+    /// Return the components of the id of this entry when it is a `tag:` URI (RFC 4151).
+    ///
+    /// `None` is returned for other ids and for malformed `tag:` URIs, such as one without a
+    /// date. Surrounding whitespace of the id is ignored.
     ///
     /// # Examples
     ///
     /// ```
     /// use atom_syndication::Entry;
-    /// use atom_syndication::extension::ExtensionMap;
+    ///
+    /// let entry = Entry::default().with_id("tag:example.com,2005-06:/blog/1");
+    /// let tag = entry.id_tag_parts().unwrap();
+    /// assert_eq!(tag.authority, "example.com");
+    /// assert_eq!(tag.date, "2005-06");
+    /// assert_eq!(tag.specific, "/blog/1");
+    ///
+    /// let entry = Entry::default().with_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// assert_eq!(entry.id_tag_parts(), None);
+    /// ```
+    pub fn id_tag_parts(&self) -> Option<TagUri> {
+        parse_tag_uri(self.id.trim())
+    }
+
+    /// Return the number of words in the content of this entry, or in its summary when the
+    /// entry has no textual content. Markup is not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry};
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("html".to_string());
+    /// content.set_value("<p>Three <em>short</em> words</p>".to_string());
     ///
     /// let mut entry = Entry::default();
-    /// entry.set_extensions(ExtensionMap::default());
+    /// entry.set_content(content);
+    /// assert_eq!(entry.word_count(), 3);
     /// ```
-    pub fn set_extensions<V>(&mut self, extensions: V)
-    where
-        V: Into<ExtensionMap>,
-    {
-        self.extensions = extensions.into()
+    pub fn word_count(&self) -> usize {
+        self.plain_text()
+            .map_or(0, |text| text.split_whitespace().count())
+    }
+
+    /// Return the estimated time in minutes needed to read this entry at the given number of
+    /// words per minute, rounded up. An entry without words takes zero minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Text};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_summary(Text::plain("one two three four five"));
+    /// assert_eq!(entry.reading_time_minutes(2), 3);
+    /// ```
+    pub fn reading_time_minutes(&self, wpm: usize) -> usize {
+        if wpm == 0 {
+            return 0;
+        }
+
+        let words = self.word_count();
+        match words % wpm {
+            0 => words / wpm,
+            _ => words / wpm + 1,
+        }
     }
+
+    /// Return a hash of the title, content and links of this entry.
+    ///
+    /// The hash covers the title text, the content's value, `src` and type, and the `href` of
+    /// every link in order. Ids, timestamps, authors and other metadata are left out, so the
+    /// same article republished under a different id has the same hash. The hash is stable
+    /// across runs and releases, so it can be persisted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut first = Entry::default();
+    /// first.set_id("urn:first");
+    /// first.set_title("Same article");
+    ///
+    /// let mut second = first.clone();
+    /// second.set_id("urn:second");
+    /// assert_eq!(first.content_hash(), second.content_hash());
+    ///
+    /// second.set_title("Other article");
+    /// assert_ne!(first.content_hash(), second.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        hasher.write_str(&self.title.value);
+
+        match self.content {
+            Some(ref content) => {
+                hasher.write_str(content.value.as_deref().unwrap_or_default());
+                hasher.write_str(content.src.as_deref().unwrap_or_default());
+                hasher.write_str(content.content_type.as_deref().unwrap_or_default());
+            }
+            None => hasher.write_str(""),
+        }
+
+        for link in &self.links {
+            hasher.write_str(&link.href);
+        }
+
+        hasher.finish()
+    }
+
+    /// Return a hash of the id of this entry.
+    ///
+    /// Unlike the `Hash` implementation, which covers every field, this only identifies the
+    /// entry, so an entry whose content or timestamps changed keeps the same hash. The hash is
+    /// stable across runs and releases, so it can be persisted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut first = Entry::default();
+    /// first.set_id("urn:entry");
+    ///
+    /// let mut second = first.clone();
+    /// second.set_title("Edited");
+    /// assert_ne!(first, second);
+    /// assert_eq!(first.id_hash(), second.id_hash());
+    /// ```
+    pub fn id_hash(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        hasher.write_str(&self.id);
+        hasher.finish()
+    }
+
+    /// Drop the content and summary of this entry, keeping only its metadata.
+    ///
+    /// `content` and `summary` are set to `None`. Every other field is retained: the id, title,
+    /// updated and published dates, authors, contributors, categories, links, rights, source,
+    /// extensions, unknown attributes and leading and inner comments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, Entry, Text};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_title("Entry Title");
+    /// entry.set_summary(Some(Text::plain("Entry summary")));
+    /// entry.set_content(Some(Content::default()));
+    ///
+    /// entry.strip_to_metadata();
+    /// assert_eq!(entry.title(), "Entry Title");
+    /// assert!(entry.summary().is_none());
+    /// assert!(entry.content().is_none());
+    /// ```
+    pub fn strip_to_metadata(&mut self) {
+        self.content = None;
+        self.summary = None;
+    }
+
+    /// Consume this entry and return it without its content and summary.
+    ///
+    /// See [`Entry::strip_to_metadata`] for the fields that are retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Text};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_id("urn:uuid:4ae8550b-2987-49fa-9f8c-54c180c418ac");
+    /// entry.set_summary(Some(Text::plain("Entry summary")));
+    ///
+    /// let entry = entry.into_metadata();
+    /// assert_eq!(entry.id(), "urn:uuid:4ae8550b-2987-49fa-9f8c-54c180c418ac");
+    /// assert!(entry.summary().is_none());
+    /// ```
+    pub fn into_metadata(mut self) -> Self {
+        self.strip_to_metadata();
+        self
+    }
+
+    /// Consume this entry and return a builder holding its values, to change some of them and
+    /// build a new entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_id("urn:entry");
+    /// entry.set_title("Old");
+    ///
+    /// let entry = entry.into_builder().title("New").build();
+    /// assert_eq!(entry.title(), "New");
+    /// assert_eq!(entry.id(), "urn:entry");
+    /// ```
+    #[cfg(feature = "builders")]
+    pub fn into_builder(self) -> EntryBuilder {
+        let Entry {
+            title,
+            id,
+            updated,
+            authors,
+            categories,
+            contributors,
+            links,
+            published,
+            rights,
+            source,
+            summary,
+            content,
+            extensions,
+            namespaces,
+            base,
+            lang,
+            unknown_attrs,
+            comments,
+            leading_comments,
+        } = self;
+
+        let mut builder = EntryBuilder::default();
+        builder
+            .title(title)
+            .id(id)
+            .updated(updated)
+            .authors(authors)
+            .categories(categories)
+            .contributors(contributors)
+            .links(links)
+            .published(published)
+            .rights(rights)
+            .source(source)
+            .summary(summary)
+            .content(content)
+            .extensions(extensions)
+            .namespaces(namespaces)
+            .base(base)
+            .lang(lang)
+            .unknown_attrs(unknown_attrs)
+            .comments(comments)
+            .leading_comments(leading_comments);
+        builder
+    }
+}
+
+/// A 64 bit FNV-1a hasher whose output does not depend on the Rust version or platform.
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Write a string prefixed with its length so adjacent fields cannot run into each other.
+    fn write_str(&mut self, value: &str) {
+        self.write(&(value.len() as u64).to_le_bytes());
+        self.write(value.as_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Return `true` if the prefix and local name are those of the Dublin Core `date` element,
+/// matching the `dc` prefix when it is not declared.
+fn is_dc_date<B: BufRead>(reader: &XmlReader<B>, prefix: &[u8], local_name: &[u8]) -> bool {
+    local_name == b"date"
+        && match reader.resolve_prefix(prefix) {
+            Some(namespace) => namespace == DUBLIN_CORE_NAMESPACE.as_bytes(),
+            None => prefix == b"dc",
+        }
+}
+
+/// Return `true` if `name` is the element chosen in the read options to hold a timestamp in
+/// epoch seconds.
+fn is_epoch_element<B, F>(reader: &XmlReader<B>, name: &[u8], option: F) -> bool
+where
+    B: BufRead,
+    F: FnOnce(&ReadOptions) -> &Option<String>,
+{
+    option(reader.options())
+        .as_deref()
+        .map_or(false, |element| element.as_bytes() == name)
 }
 
 impl FromXml for Entry {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes<'_>) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut XmlReader<B>,
+        mut atts: Attributes<'_>,
+    ) -> Result<Self, Error> {
         let mut entry = Entry::default();
         let mut buf = Vec::new();
+        let mut rss_published = None;
+        let mut rss_updated = None;
+
+        for att in atts.with_checks(false).flatten() {
+            match att.key {
+                b"xml:base" => entry.base = Some(reader.unescape_attr(&att)?),
+                b"xml:lang" => entry.lang = Some(reader.unescape_attr(&att)?),
+                b"xmlns" => {}
+                _ => entry.unknown_attrs.push(unknown_attr(reader, &att)?),
+            }
+        }
 
         loop {
             match reader.read_event(&mut buf)? {
-                Event::Start(element) => match element.name() {
+                Event::Start(element) => match &*reader.element_name(element.name()) {
                     b"id" => entry.id = atom_text(reader)?.unwrap_or_default(),
                     b"title" => entry.title = Text::from_xml(reader, element.attributes())?,
                     b"updated" => {
@@ -532,7 +2488,7 @@ impl FromXml for Entry {
                         .push(Person::from_xml(reader, element.attributes())?),
                     b"link" => entry
                         .links
-                        .push(Link::from_xml(reader, element.attributes())?),
+                        .extend(Link::read_xml(reader, element.attributes())?),
                     b"published" => entry.published = atom_datetime(reader)?,
                     b"rights" => entry.rights = Some(Text::from_xml(reader, element.attributes())?),
                     b"source" => {
@@ -544,20 +2500,45 @@ impl FromXml for Entry {
                     b"content" => {
                         entry.content = Some(Content::from_xml(reader, element.attributes())?)
                     }
-                    n => {
+                    _ if reader.options().map_rss_dates && element.name() == b"pubDate" => {
+                        rss_published = rss_datetime(reader, "pubDate")?.or(rss_published)
+                    }
+                    _ if is_epoch_element(reader, element.name(), |o| {
+                        &o.epoch_published_element
+                    }) =>
+                    {
+                        entry.published = atom_epoch(reader)?
+                    }
+                    _ if is_epoch_element(reader, element.name(), |o| &o.epoch_updated_element) => {
+                        entry.updated = atom_epoch(reader)?.unwrap_or_else(default_fixed_datetime)
+                    }
+                    _ => {
                         if let Some((ns, name)) = extension_name(element.name()) {
-                            parse_extension(
+                            let dc_date =
+                                reader.options().map_rss_dates && is_dc_date(reader, ns, name);
+                            let position = reader.buffer_position();
+                            let extension = parse_extension(
                                 reader,
                                 element.attributes(),
                                 ns,
                                 name,
                                 &mut entry.extensions,
                             )?;
+                            if dc_date {
+                                if let Some(value) = extension.value.clone() {
+                                    let name = reader.decode(element.name()).into_owned();
+                                    rss_updated =
+                                        rss_date(reader, &name, &value, position).or(rss_updated);
+                                }
+                            }
                         } else {
-                            reader.read_to_end(n, &mut Vec::new())?;
+                            skip_unknown(reader, element.name(), "entry")?;
                         }
                     }
                 },
+                Event::Comment(text) if reader.options().preserve_comments => {
+                    entry.comments.push(reader.decode(&text).into_owned());
+                }
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
                 _ => {}
@@ -566,27 +2547,133 @@ impl FromXml for Entry {
             buf.clear();
         }
 
+        // With the entry ended, only the namespaces bound outside of it are left in scope.
+        for prefix in extension_prefixes(&entry.extensions) {
+            if entry
+                .declared_namespaces()
+                .any(|(declared, _)| declared == prefix)
+            {
+                continue;
+            }
+            if let Some(namespace) = reader.resolve_prefix(prefix.as_bytes()) {
+                let namespace = reader.decode(namespace).into_owned();
+                entry.namespaces.insert(prefix.to_string(), namespace);
+            }
+        }
+
+        if entry.published.is_none() {
+            entry.published = rss_published;
+        }
+        if let Some(updated) = rss_updated {
+            if entry.updated == default_fixed_datetime() {
+                entry.updated = updated;
+            }
+        }
+
         Ok(entry)
     }
 }
 
+impl Entry {
+    /// Return the id to write for this entry, at `index` of its feed, see
+    /// [`WriteOptions::generate_missing_ids`].
+    #[cfg_attr(not(feature = "uuid"), allow(unused_variables))]
+    fn written_id(&self, options: &WriteOptions<'_>, index: usize) -> Cow<'_, str> {
+        #[cfg(feature = "uuid")]
+        if options.generate_missing_ids && self.id.trim().is_empty() {
+            let href = find_link(&self.links, "alternate").map_or("", |link| link.href.as_str());
+            let title = self.title.as_str();
+            let published = self.published.map(|published| published.to_rfc3339());
+            // The lengths keep the link and title apart whatever characters they contain.
+            let seed = format!(
+                "{}:{}\n{}:{}\n{}\n{}\n{}",
+                href.len(),
+                href,
+                title.len(),
+                title,
+                self.updated.to_rfc3339(),
+                published.unwrap_or_default(),
+                index
+            );
+            return Cow::Owned(crate::util::uuid_urn(&seed));
+        }
+
+        Cow::Borrowed(&self.id)
+    }
+}
+
 impl ToXml for Entry {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError> {
+        let index = writer.start_entry();
         let name = b"entry";
-        writer.write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
+        let mut element = BytesStart::borrowed(name, name.len());
+        let namespaces = self.namespaces_in_scope();
+        for (prefix, namespace) in &self.namespaces {
+            if !writer.is_declared(prefix, namespace)
+                && !self
+                    .declared_namespaces()
+                    .any(|(declared, _)| declared == prefix)
+            {
+                element
+                    .push_attribute((format!("xmlns:{}", prefix).as_bytes(), namespace.as_bytes()));
+            }
+        }
+
+        // Declare the well-known prefixes of extensions not declared on the feed, such as those
+        // of entries written with a `FeedWriter`.
+        for prefix in extension_prefixes(&self.extensions) {
+            if writer.is_prefix_declared(prefix) || namespaces.contains_key(prefix) {
+                continue;
+            }
+            if let Some(namespace) = well_known_namespace(prefix) {
+                element
+                    .push_attribute((format!("xmlns:{}", prefix).as_bytes(), namespace.as_bytes()));
+            }
+        }
+
+        if let Some(ref base) = self.base {
+            element.push_attribute(("xml:base", base.as_str()));
+        }
+
+        if let Some(ref lang) = self.lang {
+            element.push_attribute(("xml:lang", lang.as_str()));
+        }
+
+        element.extend_attributes(
+            self.unknown_attrs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
+        for comment in &self.leading_comments {
+            writer.write_comment(comment)?;
+        }
+
+        writer.write_event(Event::Start(element))?;
+
+        for comment in &self.comments {
+            writer.write_comment(comment)?;
+        }
+
+        let id = self.written_id(writer.options(), index);
+        writer.write_text_element(b"id", &*id)?;
         writer.write_object_named(&self.title, b"title")?;
-        writer.write_text_element(b"id", &*self.id)?;
         writer.write_text_element(b"updated", &*self.updated.to_rfc3339())?;
+
+        if let Some(ref published) = self.published {
+            writer.write_text_element(b"published", published.to_rfc3339())?;
+        }
+
         writer.write_objects_named(&self.authors, "author")?;
         writer.write_objects(&self.categories)?;
         writer.write_objects_named(&self.contributors, "contributor")?;
         writer.write_objects(&self.links)?;
 
-        if let Some(ref published) = self.published {
-            writer.write_text_element(b"published", &published.to_rfc3339())?;
-        }
-
-        if let Some(ref rights) = self.rights {
+        if let Some(rights) = self
+            .rights
+            .as_ref()
+            .filter(|rights| !rights.value.is_empty())
+        {
             writer.write_object_named(rights, b"rights")?;
         }
 
@@ -594,7 +2681,11 @@ impl ToXml for Entry {
             writer.write_object(source)?;
         }
 
-        if let Some(ref summary) = self.summary {
+        if let Some(summary) = self
+            .summary
+            .as_ref()
+            .filter(|summary| !summary.value.is_empty())
+        {
             writer.write_object_named(summary, b"summary")?;
         }
 
@@ -630,6 +2721,12 @@ impl Default for Entry {
             summary: None,
             content: None,
             extensions: ExtensionMap::default(),
+            namespaces: BTreeMap::new(),
+            base: None,
+            lang: None,
+            unknown_attrs: Vec::new(),
+            comments: Vec::new(),
+            leading_comments: Vec::new(),
         }
     }
 }
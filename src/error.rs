@@ -1,5 +1,6 @@
 use std::error::Error as StdError;
 use std::fmt;
+use std::io;
 use std::str::Utf8Error;
 
 use quick_xml::Error as XmlError;
@@ -9,7 +10,12 @@ use quick_xml::Error as XmlError;
 #[non_exhaustive]
 pub enum Error {
     /// Unable to parse XML.
+    ///
+    /// I/O failures reported by quick-xml are returned as [`Error::Io`] rather than as
+    /// `Error::Xml(quick_xml::Error::Io(_))`.
     Xml(XmlError),
+    /// Unable to read from or write to the underlying reader or writer.
+    Io(io::Error),
     /// Unable to parse UTF8 in to a string.
     Utf8(Utf8Error),
     /// Input did not begin with an opening feed tag.
@@ -25,17 +31,109 @@ pub enum Error {
         /// Invalid value.
         value: String,
     },
+    /// A value that must be a URI reference is not one.
+    InvalidUri(String),
+    /// Elements are nested deeper than the configured maximum depth.
+    MaxDepthExceeded,
+    /// Reading took longer than the configured maximum duration.
+    Timeout,
+    /// A text value is longer than the configured maximum length.
+    TextTooLong,
+    /// The `rel="next"` links of a paged feed lead back to the page with this URI.
+    PagingCycle(String),
+    /// Required elements of a feed being written, or read with `require_fields`, are empty,
+    /// named like `title` for the feed and `entry[0] id` for its entries.
+    MissingRequiredField(Vec<String>),
+    /// A `link` element has no `href` attribute, which is rejected in strict mode.
+    MissingLinkHref,
+    /// An extension element of a feed being written uses a namespace prefix that is neither
+    /// declared in the namespaces of the feed or its entry nor one of the well-known prefixes
+    /// such as `dc` and `itunes`.
+    ///
+    /// Formatting a feed with `Display`, as `to_string()` does, cannot fail, so it writes such
+    /// elements as they are.
+    UndeclaredPrefix(String),
+    /// The document type declaration declares entities, which are rejected to guard against
+    /// XML external entity (XXE) attacks unless allowed in the read options.
+    ExternalEntity,
+    /// The input is an HTML page, such as an error page served instead of the feed.
+    NotAFeed {
+        /// The name of the root element.
+        found_root: String,
+    },
+}
+
+/// The category of an [`Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The input is not well-formed XML or ends early.
+    Xml,
+    /// The input is well-formed XML, but not a valid Atom document, or a feed being written
+    /// lacks required elements, see [`Error::MissingRequiredField`].
+    Structure,
+    /// The underlying reader or writer failed.
+    Io,
+    /// The input exceeded a limit set in the read options.
+    Limit,
+}
+
+impl Error {
+    /// Return the category of this error.
+    ///
+    /// This tells input that is not XML at all, such as a truncated or garbled response, apart
+    /// from well-formed XML that is not an Atom feed, and both apart from I/O failures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{ErrorKind, Feed};
+    ///
+    /// let error = Feed::read_from(&b"<feed><title>Title</feed>"[..]).unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::Xml);
+    ///
+    /// let error = Feed::read_from(&b"<rss></rss>"[..]).unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::Structure);
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match *self {
+            Error::Xml(_) | Error::Utf8(_) | Error::Eof => ErrorKind::Xml,
+            Error::Io(_) => ErrorKind::Io,
+            Error::InvalidStartTag
+            | Error::NotAFeed { .. }
+            | Error::WrongDatetime(_)
+            | Error::WrongAttribute { .. }
+            | Error::InvalidUri(_)
+            | Error::PagingCycle(_)
+            | Error::MissingRequiredField(_)
+            | Error::MissingLinkHref
+            | Error::UndeclaredPrefix(_)
+            | Error::ExternalEntity => ErrorKind::Structure,
+            Error::MaxDepthExceeded | Error::Timeout | Error::TextTooLong => ErrorKind::Limit,
+        }
+    }
 }
 
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match *self {
             Error::Xml(ref err) => Some(err),
+            Error::Io(ref err) => Some(err),
             Error::Utf8(ref err) => Some(err),
             Error::InvalidStartTag => None,
             Error::Eof => None,
             Error::WrongDatetime(_) => None,
             Error::WrongAttribute { .. } => None,
+            Error::InvalidUri(_) => None,
+            Error::MaxDepthExceeded => None,
+            Error::Timeout => None,
+            Error::TextTooLong => None,
+            Error::PagingCycle(_) => None,
+            Error::NotAFeed { .. } => None,
+            Error::MissingRequiredField(_) => None,
+            Error::MissingLinkHref => None,
+            Error::UndeclaredPrefix(_) => None,
+            Error::ExternalEntity => None,
         }
     }
 }
@@ -44,6 +142,7 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Error::Xml(ref err) => fmt::Display::fmt(err, f),
+            Error::Io(ref err) => fmt::Display::fmt(err, f),
             Error::Utf8(ref err) => fmt::Display::fmt(err, f),
             Error::InvalidStartTag => write!(f, "input did not begin with an opening feed tag"),
             Error::Eof => write!(f, "unexpected end of input"),
@@ -60,13 +159,44 @@ impl fmt::Display for Error {
                 "Unsupported value of attribute {}: '{}'.",
                 attribute, value
             ),
+            Error::InvalidUri(ref uri) => write!(f, "'{}' is not a valid URI reference", uri),
+            Error::MaxDepthExceeded => write!(f, "elements are nested too deeply"),
+            Error::Timeout => write!(f, "reading took longer than the maximum duration"),
+            Error::TextTooLong => write!(f, "a text value is longer than the maximum length"),
+            Error::PagingCycle(ref uri) => {
+                write!(f, "the next links of the paged feed loop back to '{}'", uri)
+            }
+            Error::MissingRequiredField(ref fields) => {
+                write!(f, "required fields are empty: {}", fields.join(", "))
+            }
+            Error::MissingLinkHref => write!(f, "a link element has no href attribute"),
+            Error::UndeclaredPrefix(ref prefix) => {
+                write!(f, "the namespace prefix '{}' is not declared", prefix)
+            }
+            Error::ExternalEntity => {
+                write!(f, "the document type declaration declares entities")
+            }
+            Error::NotAFeed { ref found_root } => write!(
+                f,
+                "expected an Atom feed but got an HTML page with root element <{}>",
+                found_root
+            ),
         }
     }
 }
 
 impl From<XmlError> for Error {
     fn from(err: XmlError) -> Error {
-        Error::Xml(err)
+        match err {
+            XmlError::Io(err) => Error::Io(err),
+            err => Error::Xml(err),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
     }
 }
 
@@ -4,9 +4,8 @@ use std::str;
 
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Writer;
 
-use crate::toxml::ToXml;
+use crate::toxml::{ToXml, XmlWriter};
 
 pub(crate) mod util;
 
@@ -15,7 +14,7 @@ pub type ExtensionMap = BTreeMap<String, BTreeMap<String, Vec<Extension>>>;
 
 /// A namespaced extension.
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -181,10 +180,13 @@ impl Extension {
 }
 
 impl ToXml for Extension {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError> {
         let name = self.name.as_bytes();
         let mut element = BytesStart::borrowed(name, name.len());
         element.extend_attributes(self.attrs.iter().map(|a| (a.0.as_bytes(), a.1.as_bytes())));
+        if self.value.is_none() && self.children.is_empty() {
+            return writer.write_event(Event::Empty(element));
+        }
         writer.write_event(Event::Start(element))?;
 
         if let Some(value) = self.value.as_ref() {
@@ -1,13 +1,67 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::BufRead;
 use std::str;
 
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::Event;
-use quick_xml::Reader;
 
 use crate::error::Error;
 use crate::extension::{Extension, ExtensionMap};
+use crate::fromxml::XmlReader;
+
+/// The Dublin Core namespace, conventionally bound to the `dc` prefix.
+pub const DUBLIN_CORE_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+/// Return the namespace conventionally bound to an extension prefix, so extensions built
+/// without declaring their namespace can still be written as well-formed XML.
+pub fn well_known_namespace(prefix: &str) -> Option<&'static str> {
+    match prefix {
+        "dc" => Some(DUBLIN_CORE_NAMESPACE),
+        "fh" => Some("http://purl.org/syndication/history/1.0"),
+        "itunes" => Some("http://www.itunes.com/dtds/podcast-1.0.dtd"),
+        "media" => Some("http://search.yahoo.com/mrss/"),
+        "slash" => Some("http://purl.org/rss/1.0/modules/slash/"),
+        "thr" => Some("http://purl.org/syndication/thread/1.0"),
+        _ => None,
+    }
+}
+
+/// Return the prefixes of the names of the extension elements in the map and of their
+/// children, except those declared by an `xmlns:` attribute of the element or its ancestors.
+pub fn extension_prefixes(extensions: &ExtensionMap) -> BTreeSet<&str> {
+    fn add<'e>(
+        extension: &'e Extension,
+        declared: &mut Vec<&'e str>,
+        prefixes: &mut BTreeSet<&'e str>,
+    ) {
+        let len = declared.len();
+        declared.extend(extension.attrs.keys().filter_map(|key| {
+            if key.starts_with("xmlns:") {
+                Some(&key["xmlns:".len()..])
+            } else {
+                None
+            }
+        }));
+
+        if let Some(colon) = extension.name.find(':') {
+            let prefix = &extension.name[..colon];
+            if !declared.contains(&prefix) {
+                prefixes.insert(prefix);
+            }
+        }
+        for child in extension.children.values().flatten() {
+            add(child, declared, prefixes);
+        }
+
+        declared.truncate(len);
+    }
+
+    let mut prefixes = BTreeSet::new();
+    for extension in extensions.values().flat_map(BTreeMap::values).flatten() {
+        add(extension, &mut Vec::new(), &mut prefixes);
+    }
+    prefixes
+}
 
 pub fn extension_name(element_name: &[u8]) -> Option<(&[u8], &[u8])> {
     let mut split = element_name.splitn(2, |b| *b == b':');
@@ -17,13 +71,14 @@ pub fn extension_name(element_name: &[u8]) -> Option<(&[u8], &[u8])> {
     }
 }
 
-pub fn parse_extension<R>(
-    reader: &mut Reader<R>,
+/// Read an extension element into the map and return it.
+pub fn parse_extension<'m, R>(
+    reader: &mut XmlReader<R>,
     atts: Attributes<'_>,
     ns: &[u8],
     name: &[u8],
-    extensions: &mut ExtensionMap,
-) -> Result<(), Error>
+    extensions: &'m mut ExtensionMap,
+) -> Result<&'m Extension, Error>
 where
     R: BufRead,
 {
@@ -31,31 +86,18 @@ where
     let name = str::from_utf8(name)?;
     let ext = parse_extension_element(reader, atts)?;
 
-    if !extensions.contains_key(ns) {
-        extensions.insert(ns.to_string(), BTreeMap::new());
-    }
-
-    let map = match extensions.get_mut(ns) {
-        Some(map) => map,
-        None => unreachable!(),
-    };
-
-    if !map.contains_key(name) {
-        map.insert(name.to_string(), Vec::new());
-    }
-
-    let items = match map.get_mut(name) {
-        Some(items) => items,
-        None => unreachable!(),
-    };
-
-    items.push(ext);
+    let elements = extensions
+        .entry(ns.to_string())
+        .or_default()
+        .entry(name.to_string())
+        .or_default();
+    elements.push(ext);
 
-    Ok(())
+    Ok(&elements[elements.len() - 1])
 }
 
 fn parse_extension_element<R: BufRead>(
-    reader: &mut Reader<R>,
+    reader: &mut XmlReader<R>,
     mut atts: Attributes<'_>,
 ) -> Result<Extension, Error> {
     let mut extension = Extension::default();
@@ -63,7 +105,7 @@ fn parse_extension_element<R: BufRead>(
 
     for attr in atts.with_checks(false).flatten() {
         let key = str::from_utf8(attr.key)?;
-        let value = attr.unescape_and_decode_value(reader)?;
+        let value = reader.unescape_attr(&attr)?;
         extension.attrs.insert(key.to_string(), value);
     }
 
@@ -73,22 +115,17 @@ fn parse_extension_element<R: BufRead>(
                 let ext = parse_extension_element(reader, element.attributes())?;
                 let name = str::from_utf8(element.local_name())?;
 
-                if !extension.children.contains_key(name) {
-                    extension.children.insert(name.to_string(), Vec::new());
-                }
-
-                let items = match extension.children.get_mut(name) {
-                    Some(items) => items,
-                    None => unreachable!(),
-                };
-
-                items.push(ext);
+                extension
+                    .children
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(ext);
             }
             Event::CData(element) => {
                 extension.value = Some(reader.decode(&element).into());
             }
             Event::Text(element) => {
-                extension.value = Some(element.unescape_and_decode(reader)?.trim().to_string());
+                extension.value = Some(reader.unescape_text(&element)?.trim().to_string());
             }
             Event::End(element) => {
                 extension.name = reader.decode(element.name()).into();
@@ -103,3 +140,31 @@ fn parse_extension_element<R: BufRead>(
 
     Ok(extension)
 }
+
+/// Return the extension elements with the given local name in the given namespace.
+///
+/// They are looked up under every prefix bound to the namespace in `namespaces`, and under
+/// `default_prefix` unless it is bound to another namespace, so extensions added without
+/// declaring their namespace are found as well.
+pub fn find_extensions<'a>(
+    extensions: &'a ExtensionMap,
+    namespaces: &BTreeMap<String, String>,
+    namespace: &str,
+    default_prefix: &str,
+    name: &str,
+) -> Vec<&'a Extension> {
+    let mut prefixes = namespaces
+        .iter()
+        .filter(|(_, uri)| uri.as_str() == namespace)
+        .map(|(prefix, _)| prefix.as_str())
+        .collect::<Vec<_>>();
+    if !namespaces.contains_key(default_prefix) {
+        prefixes.push(default_prefix);
+    }
+
+    prefixes
+        .into_iter()
+        .filter_map(|prefix| extensions.get(prefix)?.get(name))
+        .flatten()
+        .collect()
+}
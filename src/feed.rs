@@ -1,29 +1,209 @@
-use std::collections::BTreeMap;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+#[cfg(feature = "compression")]
+use std::io::{self, BufReader, Read};
 use std::io::{BufRead, Write};
 use std::str::{self, FromStr};
+use std::time::{Duration, SystemTime};
 
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Reader;
 use quick_xml::Writer;
 
-use crate::category::Category;
+use crate::category::{has_term, Category};
+use crate::chunks::{ChunkReader, LineIndex};
 use crate::entry::Entry;
-use crate::error::Error;
-use crate::extension::util::{extension_name, parse_extension};
-use crate::extension::ExtensionMap;
-use crate::fromxml::FromXml;
+#[cfg(feature = "builders")]
+use crate::entry::EntryBuilder;
+use crate::error::{Error, ErrorKind};
+use crate::extension::util::{
+    extension_name, extension_prefixes, find_extensions, parse_extension, well_known_namespace,
+};
+use crate::extension::{Extension, ExtensionMap};
+use crate::fromxml::{xml_reader, FromXml, XmlReader, ATOM_NAMESPACE};
 use crate::generator::Generator;
-use crate::link::Link;
+#[cfg(feature = "ammonia")]
+use crate::html::unwrap_xhtml_div;
+use crate::link::{dedup_links, find_link, replace_link, Link};
+use crate::options::{ReadOptions, WriteOptions};
 use crate::person::Person;
+use crate::size::SizeEstimate;
 use crate::text::Text;
-use crate::toxml::{ToXml, WriterExt};
-use crate::util::{atom_datetime, atom_text, default_fixed_datetime, FixedDateTime};
+#[cfg(feature = "ammonia")]
+use crate::text::TextType;
+use crate::toxml::{ToXml, WriterExt, XmlWriter};
+use crate::util::{
+    atom_datetime, atom_text, default_fixed_datetime, from_system_time, parse_datetime, rfc822,
+    skip_unknown, system_time, unknown_attr, FixedDateTime,
+};
+use crate::warning::Warning;
+
+/// The namespace of the Feed Paging and Archiving extension (RFC 5005).
+const FEED_HISTORY_NAMESPACE: &str = "http://purl.org/syndication/history/1.0";
+
+/// How a feed relates to the other documents of its history, as described by RFC 5005.
+///
+/// Returned by [`Feed::paging_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PagingState {
+    /// The feed is marked with `<fh:complete/>` and contains every entry.
+    Complete,
+    /// The feed is marked with `<fh:archive/>` and is an archive document that does not change.
+    Archive,
+    /// The feed links to other pages of the same feed.
+    Paged,
+    /// The feed has no paging markers or links.
+    Unknown,
+}
+
+/// The shape an image of a feed is meant to have, following the guidance of RFC 4287.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ImageAspect {
+    /// A square image, such as the `icon` of a feed, which should have an aspect ratio of 1:1.
+    Square,
+    /// A wide image, such as the `logo` of a feed, which should have an aspect ratio of 2:1.
+    Wide,
+}
+
+/// An image of a feed, with the aspect ratio the specification recommends for it.
+///
+/// Returned by [`Feed::icon_image`] and [`Feed::logo_image`]. The aspect is a hint derived
+/// from where the image appears; the image itself is not inspected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    /// The URI of the image.
+    pub url: String,
+    /// The aspect ratio the image is meant to have.
+    pub aspect: ImageAspect,
+}
+
+/// A timestamp together with details of how it was written.
+///
+/// Returned by [`Feed::updated_parsed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParsedTimestamp {
+    /// The timestamp, in the offset it was written with.
+    pub datetime: FixedDateTime,
+    /// The UTC offset the timestamp was written with, such as `-05:00`.
+    pub offset: chrono::FixedOffset,
+    /// Whether the timestamp has a non-zero fraction of a second.
+    pub had_fractional_seconds: bool,
+}
+
+/// Which of the optional elements of a feed are present.
+///
+/// Returned by [`Feed::present_elements`]. A text or URI that is empty or only whitespace
+/// counts as absent, as does an empty list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PresentElements {
+    /// Whether the feed has an author.
+    pub authors: bool,
+    /// Whether the feed has a category.
+    pub categories: bool,
+    /// Whether the feed has a contributor.
+    pub contributors: bool,
+    /// Whether the feed names the software that generated it.
+    pub generator: bool,
+    /// Whether the feed has an icon.
+    pub icon: bool,
+    /// Whether the feed has a link.
+    pub links: bool,
+    /// Whether the feed has a logo.
+    pub logo: bool,
+    /// Whether the feed has a rights statement.
+    pub rights: bool,
+    /// Whether the feed has a subtitle.
+    pub subtitle: bool,
+    /// Whether the feed has an entry.
+    pub entries: bool,
+}
+
+impl PresentElements {
+    /// Return the number of optional elements that are present, out of 10.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// assert_eq!(feed.present_elements().count(), 0);
+    ///
+    /// feed.set_icon("http://example.com/icon.png".to_string());
+    /// assert_eq!(feed.present_elements().count(), 1);
+    /// ```
+    pub fn count(&self) -> usize {
+        [
+            self.authors,
+            self.categories,
+            self.contributors,
+            self.generator,
+            self.icon,
+            self.links,
+            self.logo,
+            self.rights,
+            self.subtitle,
+            self.entries,
+        ]
+        .iter()
+        .filter(|present| **present)
+        .count()
+    }
+}
+
+/// The position of an element in the input a feed was read from.
+///
+/// Returned by [`Feed::read_from_with_positions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SourcePosition {
+    /// The 1-based line of the `<` that starts the element.
+    pub line: usize,
+    /// The 1-based column of the `<` that starts the element, counted in bytes from the start
+    /// of the line.
+    pub column: usize,
+    /// The 0-based byte offset of the `<` that starts the element.
+    pub offset: usize,
+}
+
+/// Statistics about the input collected while reading a feed.
+///
+/// Returned by [`Feed::read_from_with_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseStats {
+    /// The number of entries read.
+    pub entries: usize,
+    /// The number of elements that were skipped because they are neither Atom elements nor
+    /// extension elements, including their children.
+    pub unknown_elements: usize,
+    /// The number of warnings about problems in the input that were recovered from.
+    pub warnings: usize,
+    /// The number of bytes of input consumed, up to the end of the `feed` element.
+    pub bytes: usize,
+    /// The number of chunks of text that contained no character or entity references, so they
+    /// could have been borrowed from the input rather than allocated.
+    pub borrowable_texts: usize,
+    /// The number of chunks of text that contained character or entity references, so they
+    /// had to be unescaped into a new allocation.
+    pub unescaped_texts: usize,
+    /// The `encoding` named in the XML declaration, such as `ISO-8859-1`, exactly as written.
+    ///
+    /// This is `None` when the input has no XML declaration or the declaration names no
+    /// encoding. The text of the feed is always decoded to UTF-8 regardless.
+    pub declared_encoding: Option<String>,
+}
 
 /// Represents an Atom feed
+///
+/// Equality and hashing take every field into account, including all entries. Timestamps are
+/// compared as instants, ignoring their UTC offset.
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -63,7 +243,7 @@ pub struct Feed {
     /// A human-readable description or subtitle for the feed.
     pub subtitle: Option<Text>,
     /// The entries contained in the feed.
-    #[cfg_attr(feature = "builders", builder(setter(each = "entry")))]
+    #[cfg_attr(feature = "builders", builder(setter(custom)))]
     pub entries: Vec<Entry>,
     /// The extensions for the feed.
     #[cfg_attr(feature = "builders", builder(setter(each = "extension")))]
@@ -75,9 +255,235 @@ pub struct Feed {
     pub base: Option<String>,
     /// Indicates the natural language for the element.
     pub lang: Option<String>,
+    /// Attributes that are not otherwise recognized, in the order they appeared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown_attrs: Vec<(String, String)>,
+    /// The contents of the `xml-stylesheet` processing instructions written before the `feed`
+    /// element, e.g. `type="text/xsl" href="/feed.xsl"`.
+    #[cfg_attr(feature = "builders", builder(setter(each = "stylesheet")))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stylesheets: Vec<String>,
+    /// The `version` of the XML declaration, written as `1.0` when unset.
+    pub xml_version: Option<String>,
+    /// The `standalone` pseudo-attribute of the XML declaration, omitted when unset.
+    pub standalone: Option<bool>,
+}
+
+/// A [`Feed`] split into its metadata and its entries, as returned by [`Feed::into_parts`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedParts {
+    /// The feed without its entries.
+    pub metadata: Feed,
+    /// The entries of the feed.
+    pub entries: Vec<Entry>,
 }
 
 impl Feed {
+    /// Create a feed with its `id`, `title` and `updated` elements set.
+    ///
+    /// Only these three elements are filled in; the feed has no entries, authors or links.
+    /// As it stands it passes [`Feed::validate`], but nothing keeps it valid: entries added
+    /// later still need their own ids, titles, timestamps, authors and unique alternate
+    /// links. `Feed::default()` remains available for building a feed incrementally, but
+    /// leaves the required elements empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FixedDateTime};
+    /// use std::str::FromStr;
+    ///
+    /// let updated = FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap();
+    /// let feed = Feed::new_valid("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6", "Feed Title", updated);
+    /// assert!(feed.validate().is_ok());
+    /// ```
+    pub fn new_valid<I, T, U>(id: I, title: T, updated: U) -> Feed
+    where
+        I: Into<String>,
+        T: Into<Text>,
+        U: Into<FixedDateTime>,
+    {
+        Feed {
+            id: id.into(),
+            title: title.into(),
+            updated: updated.into(),
+            ..Feed::default()
+        }
+    }
+
+    /// Create a feed from `(title, url, date)` tuples, one entry per tuple, in order.
+    ///
+    /// Each entry gets the title, an `alternate` link to the URL, and the date as both its
+    /// `updated` and `published` date. The id of each entry is its URL, which is a valid Atom id
+    /// as long as the URL is absolute and stays the same for the life of the entry. The date is
+    /// parsed like the dates of a feed being read, so RFC 3339, RFC 2822 and RFC 3339 without a
+    /// UTC offset, which is taken to be UTC, are accepted. The `updated` date of the feed is the
+    /// most recent date of its entries. No authors are set, so one must be added for the feed
+    /// to be valid Atom.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::WrongDatetime`] with the value of the first date that cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::from_items(
+    ///     "Reading List",
+    ///     "urn:example:reading-list",
+    ///     vec![(
+    ///         "First Article".to_string(),
+    ///         "http://example.com/first".to_string(),
+    ///         "2021-05-01T12:00:00Z".to_string(),
+    ///     )],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(feed.entries()[0].id(), "http://example.com/first");
+    /// assert_eq!(feed.updated().to_rfc3339(), "2021-05-01T12:00:00+00:00");
+    /// ```
+    pub fn from_items<T, I, It>(title: T, id: I, items: It) -> Result<Feed, Error>
+    where
+        T: Into<Text>,
+        I: Into<String>,
+        It: IntoIterator<Item = (String, String, String)>,
+    {
+        let mut feed = Feed {
+            title: title.into(),
+            id: id.into(),
+            ..Feed::default()
+        };
+
+        for (title, url, date) in items {
+            let updated = parse_datetime(&date).ok_or(Error::WrongDatetime(date))?;
+            feed.entries.push(Entry {
+                title: title.into(),
+                id: url.trim().to_string(),
+                updated,
+                published: Some(updated),
+                links: vec![Link::alternate(url)],
+                ..Entry::default()
+            });
+        }
+
+        feed.update_from_entries();
+        Ok(feed)
+    }
+
+    /// Split this feed into its metadata and its entries without cloning them.
+    ///
+    /// The other fields of a feed are public and can be moved out of
+    /// [`FeedParts::metadata`] directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default().with_entries(vec![Entry::default()]);
+    /// feed.set_title("Feed Title");
+    ///
+    /// let mut parts = feed.into_parts();
+    /// assert!(parts.metadata.entries().is_empty());
+    /// parts.entries.clear();
+    /// let feed = Feed::from_parts(parts);
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// ```
+    pub fn into_parts(mut self) -> FeedParts {
+        let entries = std::mem::take(&mut self.entries);
+        FeedParts {
+            metadata: self,
+            entries,
+        }
+    }
+
+    /// Reassemble a feed from its metadata and its entries.
+    ///
+    /// Any entries left in the metadata are replaced by [`FeedParts::entries`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let parts = Feed::default().into_parts();
+    /// assert_eq!(Feed::from_parts(parts), Feed::default());
+    /// ```
+    pub fn from_parts(parts: FeedParts) -> Feed {
+        let FeedParts {
+            mut metadata,
+            entries,
+        } = parts;
+        metadata.entries = entries;
+        metadata
+    }
+
+    /// Consume this feed and return a builder holding its values, to change some of them and
+    /// build a new feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = "<feed><title>Old</title><id>urn:feed</id></feed>".parse::<Feed>().unwrap();
+    /// let feed = feed.into_builder().title("New").build();
+    /// assert_eq!(feed.title(), "New");
+    /// assert_eq!(feed.id(), "urn:feed");
+    /// ```
+    #[cfg(feature = "builders")]
+    pub fn into_builder(self) -> FeedBuilder {
+        let Feed {
+            title,
+            id,
+            updated,
+            authors,
+            categories,
+            contributors,
+            generator,
+            icon,
+            links,
+            logo,
+            rights,
+            subtitle,
+            entries,
+            extensions,
+            namespaces,
+            base,
+            lang,
+            unknown_attrs,
+            stylesheets,
+            xml_version,
+            standalone,
+        } = self;
+
+        let mut builder = FeedBuilder::default();
+        builder
+            .title(title)
+            .id(id)
+            .updated(updated)
+            .authors(authors)
+            .categories(categories)
+            .contributors(contributors)
+            .generator(generator)
+            .icon(icon)
+            .links(links)
+            .logo(logo)
+            .rights(rights)
+            .subtitle(subtitle)
+            .entries(entries)
+            .extensions(extensions)
+            .namespaces(namespaces)
+            .base(base)
+            .lang(lang)
+            .unknown_attrs(unknown_attrs)
+            .stylesheets(stylesheets)
+            .xml_version(xml_version)
+            .standalone(standalone);
+        builder
+    }
+
     /// Attempt to read an Atom feed from the reader.
     ///
     /// # Examples
@@ -91,20 +497,288 @@ impl Feed {
     /// let feed = Feed::read_from(BufReader::new(file)).unwrap();
     /// ```
     pub fn read_from<B: BufRead>(reader: B) -> Result<Feed, Error> {
-        let mut reader = Reader::from_reader(reader);
-        reader.expand_empty_elements(true);
+        Feed::read_with(reader, ReadOptions::default())
+    }
+
+    /// Attempt to read an Atom feed from the reader using the given options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadOptions};
+    ///
+    /// let mut options = ReadOptions::default();
+    /// options.strict = true;
+    ///
+    /// let xml = "<feed><author><name>Jane</name><uri>https://example.com/jane</uri></author></feed>";
+    /// let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+    /// assert_eq!(feed.authors()[0].uri(), Some("https://example.com/jane"));
+    /// ```
+    pub fn read_with<B: BufRead>(reader: B, options: ReadOptions) -> Result<Feed, Error> {
+        let mut feed = Feed::default();
+        feed.read_document(&mut xml_reader(reader, options))?;
+        Ok(feed)
+    }
+
+    /// Attempt to read an Atom feed from the reader using the given options, returning the
+    /// warnings about problems in the input that were recovered from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ReadOptions};
+    ///
+    /// let xml = "<feed><updated>2021-05-01T12:00:00</updated></feed>";
+    /// let (feed, warnings) = Feed::read_with_warnings(xml.as_bytes(), ReadOptions::default())
+    ///     .unwrap();
+    /// assert_eq!(feed.updated().to_rfc3339(), "2021-05-01T12:00:00+00:00");
+    /// assert_eq!(warnings.len(), 1);
+    /// ```
+    pub fn read_with_warnings<B: BufRead>(
+        reader: B,
+        options: ReadOptions,
+    ) -> Result<(Feed, Vec<Warning>), Error> {
+        let mut reader = xml_reader(reader, options);
+        let mut feed = Feed::default();
+        feed.read_document(&mut reader)?;
+        Ok((feed, reader.into_warnings()))
+    }
+
+    /// Attempt to read an Atom feed from the reader, returning statistics about the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = "<feed><unknown/><entry><id>1</id></entry></feed>";
+    /// let (feed, stats) = Feed::read_from_with_stats(xml.as_bytes()).unwrap();
+    /// assert_eq!(stats.entries, 1);
+    /// assert_eq!(stats.unknown_elements, 1);
+    /// assert_eq!(stats.bytes, xml.len());
+    /// ```
+    pub fn read_from_with_stats<B: BufRead>(reader: B) -> Result<(Feed, ParseStats), Error> {
+        let mut reader = xml_reader(reader, ReadOptions::default());
+        let mut feed = Feed::default();
+        feed.read_document(&mut reader)?;
+
+        let (borrowable_texts, unescaped_texts) = reader.text_counts();
+        let stats = ParseStats {
+            entries: feed.entries.len(),
+            unknown_elements: reader.skipped_elements(),
+            warnings: reader.warnings().len(),
+            bytes: reader.buffer_position(),
+            borrowable_texts,
+            unescaped_texts,
+            declared_encoding: reader.declared_encoding().map(str::to_string),
+        };
+        Ok((feed, stats))
+    }
+
+    /// Attempt to read an Atom feed from the reader, returning the position in the input of the
+    /// start tag of each entry.
+    ///
+    /// The position at index `i` belongs to the entry at index `i` of the returned feed. Lines
+    /// and columns are 1-based and computed from the byte offset by counting the line feeds
+    /// before it, so a column counts bytes rather than characters, and a carriage return
+    /// before a line feed counts towards the column of the end of the line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = "<feed>\n  <entry><id>1</id></entry><entry><id>2</id></entry>\n</feed>";
+    /// let (feed, positions) = Feed::read_from_with_positions(xml.as_bytes()).unwrap();
+    /// assert_eq!(feed.entries().len(), 2);
+    /// assert_eq!((positions[0].line, positions[0].column), (2, 3));
+    /// assert_eq!((positions[1].line, positions[1].column), (2, 28));
+    /// ```
+    pub fn read_from_with_positions<B: BufRead>(
+        reader: B,
+    ) -> Result<(Feed, Vec<SourcePosition>), Error> {
+        let mut reader = xml_reader(LineIndex::new(reader), ReadOptions::default());
+        reader.record_entry_starts();
+        let mut feed = Feed::default();
+        feed.read_document(&mut reader)?;
+
+        let starts = reader.take_entry_starts();
+        let lines = reader.into_inner();
+        let positions = starts
+            .into_iter()
+            .map(|start| lines.position(start))
+            .collect();
+        Ok((feed, positions))
+    }
+
+    /// Attempt to read only the metadata of an Atom feed from the reader.
+    ///
+    /// Every `entry` element is skipped without being parsed, so `entries()` of the returned
+    /// feed is always empty. This is much faster than [`Feed::read_from`] on large feeds when
+    /// only the feed-level elements are needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"
+    ///     <feed>
+    ///         <title>Feed Title</title>
+    ///         <entry><id>1</id><title>Entry Title</title></entry>
+    ///         <subtitle>Feed Subtitle</subtitle>
+    ///     </feed>
+    /// "#;
+    /// let feed = Feed::read_metadata_only(xml.as_bytes()).unwrap();
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// assert_eq!(feed.subtitle().map(|s| s.as_str()), Some("Feed Subtitle"));
+    /// assert!(feed.entries().is_empty());
+    /// ```
+    pub fn read_metadata_only<B: BufRead>(reader: B) -> Result<Feed, Error> {
+        let mut reader = xml_reader(reader, ReadOptions::default());
+        reader.set_skip_entries(true);
+        let mut feed = Feed::default();
+        feed.read_document(&mut reader)?;
+        Ok(feed)
+    }
+
+    /// Read an Atom feed from the reader, keeping everything parsed before the first error.
+    ///
+    /// Parsing stops at the first fatal error and the remainder of the input is skipped. The
+    /// returned feed holds the metadata and the complete entries read up to that point, and the
+    /// error is returned alongside it. An entry that was being read when the error occurred is
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = "<feed><title>Partial</title><entry><id>1</id></entry><entry><id>2</i";
+    /// let (feed, error) = Feed::read_from_best_effort(xml.as_bytes());
+    /// assert_eq!(feed.title(), "Partial");
+    /// assert_eq!(feed.entries().len(), 1);
+    /// assert!(error.is_some());
+    /// ```
+    pub fn read_from_best_effort<B: BufRead>(reader: B) -> (Feed, Option<Error>) {
+        let mut feed = Feed::default();
+        let error = feed
+            .read_document(&mut xml_reader(reader, ReadOptions::default()))
+            .err();
+        (feed, error)
+    }
+
+    /// Attempt to read every Atom feed from a reader holding several concatenated documents.
+    ///
+    /// Each document may start with its own XML declaration, and whitespace, comments and
+    /// processing instructions between documents are ignored. Reading stops at the end of the
+    /// input, so an input without any feed returns an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"
+    ///     <?xml version="1.0" encoding="utf-8"?>
+    ///     <feed><title>First</title></feed>
+    ///     <?xml version="1.0" encoding="utf-8"?>
+    ///     <feed><title>Second</title></feed>
+    /// "#;
+    /// let feeds = Feed::read_all(xml.as_bytes()).unwrap();
+    /// assert_eq!(feeds.len(), 2);
+    /// assert_eq!(feeds[1].title(), "Second");
+    /// ```
+    pub fn read_all<B: BufRead>(reader: B) -> Result<Vec<Feed>, Error> {
+        let mut reader = xml_reader(reader, ReadOptions::default());
+        let mut feeds = Vec::new();
+
+        loop {
+            let mut feed = Feed::default();
+            if !feed.read_next_document(&mut reader)? {
+                return Ok(feeds);
+            }
+            feeds.push(feed);
+        }
+    }
+
+    /// Read the document from the reader into this feed.
+    pub(crate) fn read_document<B: BufRead>(
+        &mut self,
+        reader: &mut XmlReader<B>,
+    ) -> Result<(), Error> {
+        if self.read_next_document(reader)? {
+            Ok(())
+        } else {
+            Err(Error::Eof)
+        }
+    }
 
+    /// Read the next document from the reader into this feed, returning `false` if the input
+    /// ends before the next document starts.
+    fn read_next_document<B: BufRead>(&mut self, reader: &mut XmlReader<B>) -> Result<bool, Error> {
         let mut buf = Vec::new();
+        let mut html_doctype = false;
 
         loop {
             match reader.read_event(&mut buf)? {
                 Event::Start(element) => {
-                    if element.name() == b"feed" {
-                        return Feed::from_xml(&mut reader, element.attributes());
+                    if *reader.element_name(element.name()) == *b"feed" {
+                        self.read_xml(reader, element.attributes())?;
+                        if reader.options().require_fields {
+                            let missing = self.missing_required_fields();
+                            if !missing.is_empty() {
+                                return Err(Error::MissingRequiredField(missing));
+                            }
+                        }
+                        #[cfg(feature = "url")]
+                        if let Some(ref base) = reader.options().resolve_links {
+                            self.resolve_links(base);
+                        }
+                        return Ok(true);
+                    } else if html_doctype || element.local_name().eq_ignore_ascii_case(b"html") {
+                        return Err(Error::NotAFeed {
+                            found_root: reader.decode(element.name()).into_owned(),
+                        });
                     } else {
                         return Err(Error::InvalidStartTag);
                     }
                 }
+                Event::Decl(decl) => {
+                    let encoding = match decl.encoding() {
+                        Some(Ok(encoding)) => Some(reader.decode(&encoding).into_owned()),
+                        _ => None,
+                    };
+                    reader.set_declared_encoding(encoding);
+
+                    if reader.options().preserve_declaration {
+                        if let Ok(version) = decl.version() {
+                            self.xml_version = Some(reader.decode(&version).into_owned());
+                        }
+                        self.standalone = match decl.standalone() {
+                            Some(Ok(standalone)) => Some(&*standalone == b"yes"),
+                            _ => None,
+                        };
+                    }
+                }
+                Event::DocType(text) => {
+                    let doctype = reader.decode(&text);
+                    if !reader.options().allow_entity_declarations && declares_entities(&doctype) {
+                        return Err(Error::ExternalEntity);
+                    }
+                    html_doctype = doctype
+                        .trim_start()
+                        .get(..4)
+                        .map_or(false, |name| name.eq_ignore_ascii_case("html"));
+                }
+                Event::PI(text) if reader.options().preserve_stylesheets => {
+                    let pi = reader.decode(&text);
+                    let target = "xml-stylesheet";
+                    if pi.starts_with(target) && pi[target.len()..].starts_with(char::is_whitespace)
+                    {
+                        self.stylesheets.push(pi[target.len()..].trim().to_string());
+                    }
+                }
                 Event::Eof => break,
                 _ => {}
             }
@@ -112,11 +786,110 @@ impl Feed {
             buf.clear();
         }
 
-        Err(Error::Eof)
+        Ok(false)
+    }
+
+    /// Attempt to read an Atom feed from an iterator of byte chunks, such as the body of an
+    /// HTTP response received piece by piece.
+    ///
+    /// The chunks are parsed as they are produced, without collecting the whole input first.
+    /// This is a shorthand for [`Feed::read_from`] with a [`ChunkReader`]; an error produced by
+    /// the iterator is returned as [`Error::Io`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use atom_syndication::Feed;
+    ///
+    /// let body = b"<feed><title>Chunked</title></feed>";
+    /// let chunks = body.chunks(4).map(Ok::<_, io::Error>);
+    /// let feed = Feed::read_from_chunks(chunks).unwrap();
+    /// assert_eq!(feed.title(), "Chunked");
+    /// ```
+    pub fn read_from_chunks<I, T, E>(chunks: I) -> Result<Feed, Error>
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+        T: AsRef<[u8]>,
+        E: Into<std::io::Error>,
+    {
+        Feed::read_from(ChunkReader::new(chunks))
+    }
+
+    /// Attempt to read a gzip compressed Atom feed from the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use atom_syndication::Feed;
+    ///
+    /// let file = File::open("example.xml.gz").unwrap();
+    /// let feed = Feed::read_gzip(file).unwrap();
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn read_gzip<R: Read>(reader: R) -> Result<Feed, Error> {
+        Feed::read_from(BufReader::new(flate2::read::GzDecoder::new(reader)))
+    }
+
+    /// Attempt to read a deflate compressed Atom feed from the reader.
+    ///
+    /// The input is expected in the zlib format, as sent with `Content-Encoding: deflate`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use atom_syndication::Feed;
+    ///
+    /// let file = File::open("example.xml.zz").unwrap();
+    /// let feed = Feed::read_deflate(file).unwrap();
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn read_deflate<R: Read>(reader: R) -> Result<Feed, Error> {
+        Feed::read_from(BufReader::new(flate2::read::ZlibDecoder::new(reader)))
+    }
+
+    /// Attempt to read an Atom feed from the reader, decompressing it first if it starts with
+    /// the gzip magic bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::read_auto("<feed><title>Plain</title></feed>".as_bytes()).unwrap();
+    /// assert_eq!(feed.title(), "Plain");
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn read_auto<R: Read>(mut reader: R) -> Result<Feed, Error> {
+        let mut magic = [0; 2];
+        let mut len = 0;
+        while len < magic.len() {
+            match reader.read(&mut magic[len..]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(Error::Io(err)),
+            }
+        }
+
+        let reader = io::Cursor::new(magic).take(len as u64).chain(reader);
+        if magic[..len] == [0x1f, 0x8b] {
+            Feed::read_gzip(reader)
+        } else {
+            Feed::read_from(BufReader::new(reader))
+        }
     }
 
     /// Attempt to write this Atom feed to a writer.
     ///
+    /// The feed is checked first: if its `id` or `title`, or those of any of its entries, are
+    /// empty, nothing is written and [`Error::MissingRequiredField`] lists them. This catches a
+    /// partially built feed, such as `Feed::default()`, being serialized by mistake. Use
+    /// [`Feed::write_unchecked`] to write the feed as it is, or [`Feed::write_with`] to choose
+    /// with [`WriteOptions::require_fields`].
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -129,613 +902,3011 @@ impl Feed {
     /// let out = File::create("out.xml").unwrap();
     /// feed.write_to(out).unwrap();
     /// ```
+    ///
+    /// ```
+    /// use atom_syndication::{Error, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    /// match feed.write_to(Vec::new()) {
+    ///     Err(Error::MissingRequiredField(fields)) => assert_eq!(fields, vec!["id"]),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
     pub fn write_to<W: Write>(&self, writer: W) -> Result<W, Error> {
-        let mut writer = Writer::new(writer);
-        writer.write_event(Event::Decl(BytesDecl::new(b"1.0", None, None)))?;
-        writer.write_event(Event::Text(BytesText::from_escaped("\n".as_bytes())))?;
-        self.to_xml(&mut writer)?;
-        Ok(writer.into_inner())
+        self.write_with(writer, WriteOptions::checked())
+    }
+
+    /// Attempt to write this Atom feed to a writer without checking that its required elements
+    /// are present.
+    ///
+    /// Unlike [`Feed::write_to`], empty required elements are written as they are, which
+    /// produces an invalid Atom document. [`Feed::write_with`] and [`FeedWriter`] only check
+    /// the feed if [`WriteOptions::require_fields`] is set, and `to_string()` never does.
+    ///
+    /// [`FeedWriter`]: crate::FeedWriter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = Feed::default().write_unchecked(Vec::new()).unwrap();
+    /// assert!(String::from_utf8(xml).unwrap().contains("<id></id>"));
+    /// ```
+    pub fn write_unchecked<W: Write>(&self, writer: W) -> Result<W, Error> {
+        self.write_with(writer, WriteOptions::default())
+    }
+
+    /// Attempt to write this Atom feed to a writer using the given options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, WriteOptions};
+    ///
+    /// let mut options = WriteOptions::default();
+    /// options.namespace_prefix = Some("atom");
+    ///
+    /// let xml = Feed::default().write_with(Vec::new(), options).unwrap();
+    /// let xml = String::from_utf8(xml).unwrap();
+    /// assert!(xml.contains(r#"<atom:feed xmlns:atom="http://www.w3.org/2005/Atom">"#));
+    /// assert!(xml.contains("<atom:title></atom:title>"));
+    /// ```
+    pub fn write_with<W: Write>(&self, writer: W, options: WriteOptions<'_>) -> Result<W, Error> {
+        self.check_write(&options, true)?;
+        self.write_xml(writer, options)
+    }
+
+    /// Write the XML declaration and this feed without checking it first.
+    fn write_xml<W: Write>(&self, writer: W, options: WriteOptions<'_>) -> Result<W, Error> {
+        let mut writer = XmlWriter::new(Writer::new(writer), options);
+        self.write_prolog(&mut writer, None)?;
+        self.to_xml(&mut writer)?;
+        Ok(writer.into_inner())
+    }
+
+    /// Attempt to write this Atom feed to a writer in the given character encoding.
+    ///
+    /// The XML declaration names the encoding. Characters the encoding cannot represent are
+    /// written as numeric character references such as `&#8364;`, which XML parsers resolve in
+    /// text and attribute values; such characters in element names, comments or processing
+    /// instructions cannot be represented and produce invalid XML. Note that the WHATWG
+    /// Encoding Standard treats the `ISO-8859-1` label as `windows-1252`, and that UTF-16
+    /// encodings are written as UTF-8, as for [`Encoding::output_encoding`]. The feed is
+    /// checked first, as by [`Feed::write_to`].
+    ///
+    /// [`Encoding::output_encoding`]: encoding_rs::Encoding::output_encoding
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_id("urn:feed");
+    /// feed.set_title("Café €");
+    ///
+    /// let xml = feed.write_to_encoding(Vec::new(), encoding_rs::WINDOWS_1252).unwrap();
+    /// assert!(xml.starts_with(br#"<?xml version="1.0" encoding="windows-1252"?>"#));
+    ///
+    /// let xml = feed.write_to_encoding(Vec::new(), encoding_rs::ISO_8859_2).unwrap();
+    /// let expected = b"<title>Caf\xe9 &#8364;</title>";
+    /// assert!(xml.windows(expected.len()).any(|w| w == expected));
+    /// ```
+    #[cfg(feature = "encoding_rs")]
+    pub fn write_to_encoding<W: Write>(
+        &self,
+        mut writer: W,
+        encoding: &'static encoding_rs::Encoding,
+    ) -> Result<W, Error> {
+        let encoding = encoding.output_encoding();
+        self.check_write(&WriteOptions::checked(), true)?;
+
+        let mut xml = XmlWriter::new(Writer::new(Vec::new()), WriteOptions::default());
+        self.write_prolog(&mut xml, Some(encoding.name().as_bytes()))?;
+        self.to_xml(&mut xml)?;
+
+        let xml = String::from_utf8(xml.into_inner()).map_err(|err| err.utf8_error())?;
+        let (encoded, _, _) = encoding.encode(&xml);
+        writer.write_all(&encoded)?;
+        Ok(writer)
+    }
+
+    /// Attempt to write this Atom feed to an asynchronous writer.
+    ///
+    /// The feed is checked first, as by [`Feed::write_to`], then serialized incrementally: the
+    /// metadata, every entry and the closing tag are each written to the writer as soon as they
+    /// are serialized, so the whole document is never buffered in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// # async fn run() -> Result<(), atom_syndication::Error> {
+    /// let mut feed = Feed::default();
+    /// feed.set_id("urn:feed");
+    /// feed.set_title("Feed Title");
+    ///
+    /// let mut xml = Vec::new();
+    /// feed.write_to_async(&mut xml).await?;
+    /// assert!(String::from_utf8(xml).unwrap().ends_with("</feed>"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn write_to_async<W>(&self, mut writer: W) -> Result<(), Error>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        async fn flush<W>(buf: &mut Vec<u8>, writer: &mut W) -> Result<(), Error>
+        where
+            W: tokio::io::AsyncWrite + Unpin,
+        {
+            writer.write_all(buf).await?;
+            buf.clear();
+            Ok(())
+        }
+
+        self.check_write(&WriteOptions::checked(), true)?;
+
+        let mut xml = XmlWriter::new(Writer::new(Vec::new()), WriteOptions::default());
+        self.write_prolog(&mut xml, None)?;
+        self.write_head(&mut xml)?;
+        flush(xml.inner(), &mut writer).await?;
+
+        for entry in &self.entries {
+            xml.write_object(entry)?;
+            flush(xml.inner(), &mut writer).await?;
+        }
+
+        Feed::write_tail(&self.extensions, &mut xml)?;
+        flush(xml.inner(), &mut writer).await?;
+        writer.flush().await?;
+
+        Ok(())
+    }
+
+    /// Return the title of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// ```
+    pub fn title(&self) -> &Text {
+        &self.title
+    }
+
+    /// Set the title of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    /// ```
+    pub fn set_title<V>(&mut self, title: V)
+    where
+        V: Into<Text>,
+    {
+        self.title = title.into();
+    }
+
+    /// Set the title of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_title("Feed Title");
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// ```
+    pub fn with_title<V>(mut self, title: V) -> Self
+    where
+        V: Into<Text>,
+    {
+        self.set_title(title);
+        self
+    }
+
+    /// Return the unique URI of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// assert_eq!(feed.id(), "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// ```
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    /// Set the unique URI of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// ```
+    pub fn set_id<V>(&mut self, id: V)
+    where
+        V: Into<String>,
+    {
+        self.id = id.into();
+    }
+
+    /// Set the ID of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// assert_eq!(feed.id(), "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// ```
+    pub fn with_id<V>(mut self, id: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.set_id(id);
+        self
+    }
+
+    /// Return the last time that this feed was modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn updated(&self) -> &FixedDateTime {
+        &self.updated
+    }
+
+    /// Return the last time this feed was modified formatted as in RFC 822, as used by the RSS
+    /// `pubDate` element, e.g. `Sat, 1 May 2021 12:00:00 +0000`.
+    ///
+    /// An error is returned if the year is negative or has more than four digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FixedDateTime};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_updated(FixedDateTime::parse_from_rfc3339("2021-05-01T12:00:00-04:00").unwrap());
+    /// assert_eq!(feed.updated_rfc822().unwrap(), "Sat, 1 May 2021 12:00:00 -0400");
+    /// ```
+    pub fn updated_rfc822(&self) -> Result<String, Error> {
+        rfc822(&self.updated)
+    }
+
+    /// Return the last time this feed was modified with the offset and precision it was
+    /// written with, or `None` if the feed has no `updated` timestamp.
+    ///
+    /// Timestamps are stored with their original UTC offset and fraction of a second, so
+    /// writing the feed reproduces both. Only a fraction of zero, such as `12:00:00.000`, and
+    /// the choice between `Z` and `+00:00` are not kept; such a fraction is reported as absent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FixedDateTime};
+    ///
+    /// let mut feed = Feed::default();
+    /// assert!(feed.updated_parsed().is_none());
+    ///
+    /// let updated = FixedDateTime::parse_from_rfc3339("2021-05-01T12:00:00.25-05:00").unwrap();
+    /// feed.set_updated(updated);
+    ///
+    /// let updated = feed.updated_parsed().unwrap();
+    /// assert_eq!(updated.offset.local_minus_utc(), -5 * 3600);
+    /// assert!(updated.had_fractional_seconds);
+    /// assert_eq!(updated.datetime.to_rfc3339(), "2021-05-01T12:00:00.250-05:00");
+    /// ```
+    pub fn updated_parsed(&self) -> Option<ParsedTimestamp> {
+        if self.updated == default_fixed_datetime() {
+            return None;
+        }
+
+        Some(ParsedTimestamp {
+            datetime: self.updated,
+            offset: *self.updated.offset(),
+            had_fractional_seconds: self.updated.timestamp_subsec_nanos() != 0,
+        })
+    }
+
+    /// Return which of the optional elements of this feed are present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Text};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_subtitle(Text::from("A subtitle"));
+    /// feed.set_logo(" ".to_string());
+    ///
+    /// let present = feed.present_elements();
+    /// assert!(present.subtitle);
+    /// assert!(!present.logo);
+    /// assert!(!present.generator);
+    /// ```
+    pub fn present_elements(&self) -> PresentElements {
+        let text =
+            |text: &Option<Text>| text.as_ref().map_or(false, |text| !text.trim().is_empty());
+        let uri = |uri: &Option<String>| uri.as_deref().map_or(false, |uri| !uri.trim().is_empty());
+
+        PresentElements {
+            authors: !self.authors.is_empty(),
+            categories: !self.categories.is_empty(),
+            contributors: !self.contributors.is_empty(),
+            generator: self.generator.is_some(),
+            icon: uri(&self.icon),
+            links: !self.links.is_empty(),
+            logo: uri(&self.logo),
+            rights: text(&self.rights),
+            subtitle: text(&self.subtitle),
+            entries: !self.entries.is_empty(),
+        }
+    }
+
+    /// Return the last time this feed was modified as a `SystemTime`.
+    ///
+    /// See [`Entry::updated_system_time`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use atom_syndication::{Feed, FixedDateTime};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_updated(FixedDateTime::parse_from_rfc3339("1970-01-01T00:01:00Z").unwrap());
+    /// assert_eq!(feed.updated_system_time().unwrap(), UNIX_EPOCH + Duration::from_secs(60));
+    /// ```
+    pub fn updated_system_time(&self) -> Result<SystemTime, Error> {
+        system_time(&self.updated)
+    }
+
+    /// Set the last time this feed was modified from a `SystemTime`.
+    ///
+    /// See [`Entry::set_updated_time`].
+    ///
+    /// Fails with [`Error::WrongDatetime`] if the time is more than about 200,000 years away
+    /// from the Unix epoch, leaving the current time unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_updated_time(UNIX_EPOCH + Duration::from_secs(60)).unwrap();
+    /// assert_eq!(feed.updated().to_rfc3339(), "1970-01-01T00:01:00+00:00");
+    /// ```
+    pub fn set_updated_time(&mut self, time: SystemTime) -> Result<(), Error> {
+        self.updated = from_system_time(time)?;
+        Ok(())
+    }
+
+    /// Set the last time that this feed was modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// ```
+    pub fn set_updated<V>(&mut self, updated: V)
+    where
+        V: Into<FixedDateTime>,
+    {
+        self.updated = updated.into();
+    }
+
+    /// Set the last modified timestamp of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FixedDateTime};
+    ///
+    /// let feed = Feed::default().with_updated(FixedDateTime::parse_from_rfc3339("2017-06-03T15:15:44-05:00").unwrap());
+    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn with_updated<V>(mut self, updated: V) -> Self
+    where
+        V: Into<FixedDateTime>,
+    {
+        self.set_updated(updated);
+        self
+    }
+
+    /// Return the authors of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_authors(vec![Person::default()]);
+    /// assert_eq!(feed.authors().len(), 1);
+    /// ```
+    pub fn authors(&self) -> &[Person] {
+        self.authors.as_slice()
+    }
+
+    /// Set the authors of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_authors(vec![Person::default()]);
+    /// ```
+    pub fn set_authors<V>(&mut self, authors: V)
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.authors = authors.into();
+    }
+
+    /// Set the authors of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let feed = Feed::default().with_authors(vec![Person::default()]);
+    /// assert_eq!(feed.authors().len(), 1);
+    /// ```
+    pub fn with_authors<V>(mut self, authors: V) -> Self
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.set_authors(authors);
+        self
+    }
+
+    /// Return the names of the authors of this feed, skipping authors without a name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// let jane = Person {
+    ///     name: "Jane Doe".to_string(),
+    ///     ..Person::default()
+    /// };
+    /// feed.set_authors(vec![jane, Person::default()]);
+    /// assert_eq!(feed.author_names(), vec!["Jane Doe"]);
+    /// ```
+    pub fn author_names(&self) -> Vec<&str> {
+        self.authors
+            .iter()
+            .map(Person::name)
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    /// Return the categories this feed belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Category};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_categories(vec![Category::default()]);
+    /// assert_eq!(feed.categories().len(), 1);
+    /// ```
+    pub fn categories(&self) -> &[Category] {
+        self.categories.as_slice()
+    }
+
+    /// Set the categories this feed belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Category};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_categories(vec![Category::default()]);
+    /// ```
+    pub fn set_categories<V>(&mut self, categories: V)
+    where
+        V: Into<Vec<Category>>,
+    {
+        self.categories = categories.into();
+    }
+
+    /// Set the categories of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Feed};
+    ///
+    /// let feed = Feed::default().with_categories(vec![Category::default()]);
+    /// assert_eq!(feed.categories().len(), 1);
+    /// ```
+    pub fn with_categories<V>(mut self, categories: V) -> Self
+    where
+        V: Into<Vec<Category>>,
+    {
+        self.set_categories(categories);
+        self
+    }
+
+    /// Set the categories of this feed to one category per term, with only the term set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_categories_from_terms(vec!["rust", "atom"]);
+    /// assert_eq!(feed.categories()[1].term(), "atom");
+    /// ```
+    pub fn set_categories_from_terms<I, S>(&mut self, terms: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.categories = terms
+            .into_iter()
+            .map(|term| Category {
+                term: term.into(),
+                ..Category::default()
+            })
+            .collect();
+    }
+
+    /// Return whether this feed has a category with the given term.
+    ///
+    /// Terms are compared case-sensitively and an empty term never matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Feed};
+    ///
+    /// let mut category = Category::default();
+    /// category.set_term("rust");
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_categories(vec![category]);
+    /// assert!(feed.has_category("rust"));
+    /// assert!(!feed.has_category("Rust"));
+    /// ```
+    pub fn has_category(&self, term: &str) -> bool {
+        has_term(&self.categories, term, false)
+    }
+
+    /// Return whether this feed has a category with the given term, ignoring case.
+    ///
+    /// An empty term never matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Feed};
+    ///
+    /// let mut category = Category::default();
+    /// category.set_term("rust");
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_categories(vec![category]);
+    /// assert!(feed.has_category_ci("Rust"));
+    /// ```
+    pub fn has_category_ci(&self, term: &str) -> bool {
+        has_term(&self.categories, term, true)
+    }
+
+    /// Return the categories of this feed sorted by term.
+    ///
+    /// Terms are compared case-sensitively by their bytes, so `Rust` sorts before `go`.
+    /// Categories with the same term keep their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_categories_from_terms(vec!["web", "rust", "async"]);
+    ///
+    /// let terms = feed
+    ///     .sorted_categories()
+    ///     .iter()
+    ///     .map(|category| category.term())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(terms, vec!["async", "rust", "web"]);
+    /// ```
+    pub fn sorted_categories(&self) -> Vec<&Category> {
+        let mut categories = self.categories.iter().collect::<Vec<_>>();
+        categories.sort_by(|a, b| a.term.cmp(&b.term));
+        categories
+    }
+
+    /// Return every category term of the entries of this feed with the number of entries that
+    /// have it, the most frequent first.
+    ///
+    /// Terms are grouped case-sensitively, so `Rust` and `rust` are counted separately. An
+    /// entry with the same term twice is counted once, and empty terms are ignored. Terms with
+    /// the same count are sorted like in [`Feed::sorted_categories`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let entry = |terms: Vec<&str>| {
+    ///     let mut entry = Entry::default();
+    ///     entry.set_categories_from_terms(terms);
+    ///     entry
+    /// };
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![entry(vec!["rust", "web"]), entry(vec!["rust", "Rust"])]);
+    /// assert_eq!(
+    ///     feed.category_frequencies(),
+    ///     vec![("rust", 2), ("Rust", 1), ("web", 1)]
+    /// );
+    /// ```
+    pub fn category_frequencies(&self) -> Vec<(&str, usize)> {
+        let mut counts = BTreeMap::new();
+        for entry in &self.entries {
+            let terms = entry
+                .categories
+                .iter()
+                .map(|category| category.term.as_str())
+                .filter(|term| !term.is_empty())
+                .collect::<BTreeSet<_>>();
+            for term in terms {
+                *counts.entry(term).or_insert(0) += 1;
+            }
+        }
+
+        let mut frequencies = counts.into_iter().collect::<Vec<_>>();
+        frequencies.sort_by_key(|&(_, count)| Reverse(count));
+        frequencies
+    }
+
+    /// Return the contributors to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_contributors(vec![Person::default()]);
+    /// assert_eq!(feed.contributors().len(), 1);
+    /// ```
+    pub fn contributors(&self) -> &[Person] {
+        self.contributors.as_slice()
+    }
+
+    /// Set the contributors to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_contributors(vec![Person::default()]);
+    /// ```
+    pub fn set_contributors<V>(&mut self, contributors: V)
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.contributors = contributors.into();
+    }
+
+    /// Set the contributors of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let feed = Feed::default().with_contributors(vec![Person::default()]);
+    /// assert_eq!(feed.contributors().len(), 1);
+    /// ```
+    pub fn with_contributors<V>(mut self, contributors: V) -> Self
+    where
+        V: Into<Vec<Person>>,
+    {
+        self.set_contributors(contributors);
+        self
+    }
+
+    /// Return the name of the software used to generate this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Generator};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_generator(Generator::default());
+    /// assert!(feed.generator().is_some());
+    /// ```
+    pub fn generator(&self) -> Option<&Generator> {
+        self.generator.as_ref()
+    }
+
+    /// Set the name of the software used to generate this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Generator};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_generator(Generator::default());
+    /// ```
+    pub fn set_generator<V>(&mut self, generator: V)
+    where
+        V: Into<Option<Generator>>,
+    {
+        self.generator = generator.into()
+    }
+
+    /// Set the generator of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Generator};
+    ///
+    /// let feed = Feed::default().with_generator(Generator::default());
+    /// assert!(feed.generator().is_some());
+    /// ```
+    pub fn with_generator<V>(mut self, generator: V) -> Self
+    where
+        V: Into<Option<Generator>>,
+    {
+        self.set_generator(generator);
+        self
+    }
+
+    /// Return the icon for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_icon("http://example.com/icon.png".to_string());
+    /// assert_eq!(feed.icon(), Some("http://example.com/icon.png"));
+    /// ```
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    /// Set the icon for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_icon("http://example.com/icon.png".to_string());
+    /// ```
+    pub fn set_icon<V>(&mut self, icon: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.icon = icon.into()
+    }
+
+    /// Set the icon of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_icon("http://example.com/icon.png".to_string());
+    /// assert_eq!(feed.icon(), Some("http://example.com/icon.png"));
+    /// ```
+    pub fn with_icon<V>(mut self, icon: V) -> Self
+    where
+        V: Into<Option<String>>,
+    {
+        self.set_icon(icon);
+        self
+    }
+
+    /// Return the icon of this feed, or a guess at the favicon of its website.
+    ///
+    /// When the feed has no non-empty `<icon>`, the favicon is assumed to live at
+    /// `https://{host}/favicon.ico`, where `host` (with its port, if any) is taken from the
+    /// first `rel="alternate"` link. This is only a convention many websites follow, so the
+    /// returned URL may not exist. `None` is returned when there is neither an icon nor an
+    /// alternate link with an absolute URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![Link::alternate("http://example.com/blog/")]);
+    /// assert_eq!(
+    ///     feed.icon_with_fallback().as_deref(),
+    ///     Some("https://example.com/favicon.ico")
+    /// );
+    ///
+    /// feed.set_icon("http://example.com/icon.png".to_string());
+    /// assert_eq!(feed.icon_with_fallback().as_deref(), Some("http://example.com/icon.png"));
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn icon_with_fallback(&self) -> Option<String> {
+        if let Some(icon) = self.icon.as_deref().filter(|icon| !icon.is_empty()) {
+            return Some(icon.to_string());
+        }
+
+        let url = url::Url::parse(find_link(&self.links, "alternate")?.href.trim()).ok()?;
+        let host = url.host_str()?;
+        Some(match url.port() {
+            Some(port) => format!("https://{}:{}/favicon.ico", host, port),
+            None => format!("https://{}/favicon.ico", host),
+        })
+    }
+
+    /// Return the icon of this feed as a square [`Image`], if it has a non-empty one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ImageAspect};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_icon("http://example.com/icon.png".to_string());
+    ///
+    /// let icon = feed.icon_image().unwrap();
+    /// assert_eq!(icon.url, "http://example.com/icon.png");
+    /// assert_eq!(icon.aspect, ImageAspect::Square);
+    /// ```
+    pub fn icon_image(&self) -> Option<Image> {
+        image(self.icon.as_deref(), ImageAspect::Square)
+    }
+
+    /// Return the logo of this feed as a wide [`Image`], if it has a non-empty one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ImageAspect};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_logo("http://example.com/logo.png".to_string());
+    ///
+    /// let logo = feed.logo_image().unwrap();
+    /// assert_eq!(logo.url, "http://example.com/logo.png");
+    /// assert_eq!(logo.aspect, ImageAspect::Wide);
+    /// ```
+    pub fn logo_image(&self) -> Option<Image> {
+        image(self.logo.as_deref(), ImageAspect::Wide)
+    }
+
+    /// Return the Web pages related to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![Link::default()]);
+    /// assert_eq!(feed.links().len(), 1);
+    /// ```
+    pub fn links(&self) -> &[Link] {
+        self.links.as_slice()
+    }
+
+    /// Set the Web pages related to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![Link::default()]);
+    /// ```
+    pub fn set_links<V>(&mut self, links: V)
+    where
+        V: Into<Vec<Link>>,
+    {
+        self.links = links.into();
+    }
+
+    /// Set the links of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let feed = Feed::default().with_links(vec![Link::default()]);
+    /// assert_eq!(feed.links().len(), 1);
+    /// ```
+    pub fn with_links<V>(mut self, links: V) -> Self
+    where
+        V: Into<Vec<Link>>,
+    {
+        self.set_links(links);
+        self
+    }
+
+    /// Append a link to this feed, keeping the existing ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.add_link(Link::alternate("http://example.com/"));
+    /// feed.add_link(Link::alternate("http://example.com/en/"));
+    /// assert_eq!(feed.links().len(), 2);
+    /// ```
+    pub fn add_link(&mut self, link: Link) {
+        self.links.push(link);
+    }
+
+    /// Set a link of this feed, replacing every existing link with the same relation type.
+    ///
+    /// The link takes the place of the first replaced one, or is appended when there is none.
+    /// Relation types are compared as returned by [`Link::normalized_rel`], so `self` and its
+    /// IANA registry URI are the same relation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![
+    ///     Link::alternate("http://example.com/old"),
+    ///     Link::self_link("http://example.com/feed"),
+    ///     Link::alternate("http://example.com/older"),
+    /// ]);
+    /// feed.set_link(Link::alternate("http://example.com/new"));
+    ///
+    /// let hrefs = feed.links().iter().map(Link::href).collect::<Vec<_>>();
+    /// assert_eq!(hrefs, vec!["http://example.com/new", "http://example.com/feed"]);
+    /// ```
+    pub fn set_link(&mut self, link: Link) {
+        replace_link(&mut self.links, link);
+    }
+
+    /// Return the logo for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_logo("http://example.com/logo.png".to_string());
+    /// assert_eq!(feed.logo(), Some("http://example.com/logo.png"));
+    /// ```
+    pub fn logo(&self) -> Option<&str> {
+        self.logo.as_deref()
+    }
+
+    /// Set the logo for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_logo("http://example.com/logo.png".to_string());
+    /// ```
+    pub fn set_logo<V>(&mut self, logo: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.logo = logo.into()
+    }
+
+    /// Set the logo of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let feed = Feed::default().with_logo("http://example.com/logo.png".to_string());
+    /// assert_eq!(feed.logo(), Some("http://example.com/logo.png"));
+    /// ```
+    pub fn with_logo<V>(mut self, logo: V) -> Self
+    where
+        V: Into<Option<String>>,
+    {
+        self.set_logo(logo);
+        self
+    }
+
+    /// Return the information about the rights held in and over this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Text};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_rights(Text::from("© 2017 John Doe"));
+    /// assert_eq!(feed.rights().map(Text::as_str), Some("© 2017 John Doe"));
+    /// ```
+    pub fn rights(&self) -> Option<&Text> {
+        self.rights.as_ref()
+    }
+
+    /// Set the information about the rights held in and over this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Text};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_rights(Text::from("© 2017 John Doe"));
+    /// ```
+    pub fn set_rights<V>(&mut self, rights: V)
+    where
+        V: Into<Option<Text>>,
+    {
+        self.rights = rights.into()
+    }
+
+    /// Set the rights of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Text};
+    ///
+    /// let feed = Feed::default().with_rights(Text::from("© 2017 John Doe"));
+    /// assert_eq!(feed.rights().map(Text::as_str), Some("© 2017 John Doe"));
+    /// ```
+    pub fn with_rights<V>(mut self, rights: V) -> Self
+    where
+        V: Into<Option<Text>>,
+    {
+        self.set_rights(rights);
+        self
+    }
+
+    /// Return the description or subtitle of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Text};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_subtitle(Text::from("Feed subtitle"));
+    /// assert_eq!(feed.subtitle().map(Text::as_str), Some("Feed subtitle"));
+    /// ```
+    pub fn subtitle(&self) -> Option<&Text> {
+        self.subtitle.as_ref()
+    }
+
+    /// Return the value of the description or subtitle of this feed, regardless of its type.
+    ///
+    /// Use [`Feed::subtitle`] to tell whether the value is plain text, HTML or XHTML.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Text};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_subtitle(Text::html("<em>Feed</em> subtitle"));
+    /// assert_eq!(feed.subtitle_str(), Some("<em>Feed</em> subtitle"));
+    /// ```
+    pub fn subtitle_str(&self) -> Option<&str> {
+        self.subtitle.as_ref().map(Text::as_str)
+    }
+
+    /// Set the description or subtitle of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Text};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_subtitle(Text::from("Feed subtitle"));
+    /// ```
+    pub fn set_subtitle<V>(&mut self, subtitle: V)
+    where
+        V: Into<Option<Text>>,
+    {
+        self.subtitle = subtitle.into()
+    }
+
+    /// Set the subtitle of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Text};
+    ///
+    /// let feed = Feed::default().with_subtitle(Text::from("Feed subtitle"));
+    /// assert_eq!(feed.subtitle().map(Text::as_str), Some("Feed subtitle"));
+    /// ```
+    pub fn with_subtitle<V>(mut self, subtitle: V) -> Self
+    where
+        V: Into<Option<Text>>,
+    {
+        self.set_subtitle(subtitle);
+        self
+    }
+
+    /// Return the entries in this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry::default()]);
+    /// assert_eq!(feed.entries().len(), 1);
+    /// ```
+    pub fn entries(&self) -> &[Entry] {
+        self.entries.as_slice()
+    }
+
+    /// Return the number of entries in this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry::default(); 3]);
+    /// assert_eq!(feed.entry_count(), 3);
+    /// ```
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if this feed has no entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let mut feed = Feed::default();
+    /// assert!(feed.is_empty());
+    ///
+    /// feed.set_entries(vec![Entry::default()]);
+    /// assert!(!feed.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Set the entries in this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry::default()]);
+    /// ```
+    pub fn set_entries<V>(&mut self, entries: V)
+    where
+        V: Into<Vec<Entry>>,
+    {
+        self.entries = entries.into();
+    }
+
+    /// Set the entries of this feed and return the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let feed = Feed::default().with_entries(vec![Entry::default()]);
+    /// assert_eq!(feed.entries().len(), 1);
+    /// ```
+    pub fn with_entries<V>(mut self, entries: V) -> Self
+    where
+        V: Into<Vec<Entry>>,
+    {
+        self.set_entries(entries);
+        self
+    }
+
+    /// Consume this feed and return an iterator over its entries, discarding the metadata.
+    ///
+    /// The entries are moved out in order without being cloned. Use [`Feed::into_parts`] to
+    /// keep the metadata as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let feed = Feed::default().with_entries(vec![
+    ///     Entry::default().with_id("urn:1"),
+    ///     Entry::default().with_id("urn:2"),
+    /// ]);
+    ///
+    /// let ids = feed.into_entries().map(|entry| entry.id).collect::<Vec<_>>();
+    /// assert_eq!(ids, vec!["urn:1", "urn:2"]);
+    /// ```
+    pub fn into_entries(self) -> impl Iterator<Item = Entry> {
+        self.entries.into_iter()
+    }
+
+    /// Return the extensions for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::extension::{ExtensionMap, Extension};
+    ///
+    /// let extension = Extension::default();
+    ///
+    /// let mut item_map = BTreeMap::<String, Vec<Extension>>::new();
+    /// item_map.insert("ext:name".to_string(), vec![extension]);
+    ///
+    /// let mut extension_map = ExtensionMap::default();
+    /// extension_map.insert("ext".to_string(), item_map);
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_extensions(extension_map);
+    /// assert_eq!(feed.extensions()
+    ///                .get("ext")
+    ///                .and_then(|m| m.get("ext:name"))
+    ///                .map(|v| v.len()),
+    ///            Some(1));
+    /// ```
+    pub fn extensions(&self) -> &ExtensionMap {
+        &self.extensions
+    }
+
+    /// Set the extensions for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::extension::ExtensionMap;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_extensions(ExtensionMap::default());
+    /// ```
+    pub fn set_extensions<V>(&mut self, extensions: V)
+    where
+        V: Into<ExtensionMap>,
+    {
+        self.extensions = extensions.into()
+    }
+
+    /// Return the namespaces for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut namespaces = BTreeMap::new();
+    /// namespaces.insert("ext".to_string(), "http://example.com".to_string());
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_namespaces(namespaces);
+    /// assert_eq!(feed.namespaces().get("ext").map(|s| s.as_str()), Some("http://example.com"));
+    /// ```
+    pub fn namespaces(&self) -> &BTreeMap<String, String> {
+        &self.namespaces
+    }
+
+    /// Set the namespaces for this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_namespaces(BTreeMap::new());
+    /// ```
+    pub fn set_namespaces<V>(&mut self, namespaces: V)
+    where
+        V: Into<BTreeMap<String, String>>,
+    {
+        self.namespaces = namespaces.into()
+    }
+
+    /// Return base URL of the feed.
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_deref()
+    }
+
+    /// Set base URL of the feed.
+    pub fn set_base<V>(&mut self, base: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.base = base.into();
+    }
+
+    /// Return natural language of the feed.
+    pub fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    /// Set the base URL of the feed.
+    pub fn set_lang<V>(&mut self, lang: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.lang = lang.into();
+    }
+
+    /// Return the contents of the `xml-stylesheet` processing instructions of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_stylesheets(vec![r#"type="text/xsl" href="/feed.xsl""#.to_string()]);
+    /// assert_eq!(feed.stylesheets().len(), 1);
+    /// assert!(feed.to_string().contains(r#"<?xml-stylesheet type="text/xsl" href="/feed.xsl"?>"#));
+    /// ```
+    pub fn stylesheets(&self) -> &[String] {
+        &self.stylesheets
+    }
+
+    /// Set the contents of the `xml-stylesheet` processing instructions of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_stylesheets(vec![r#"type="text/xsl" href="/feed.xsl""#.to_string()]);
+    /// ```
+    pub fn set_stylesheets<V>(&mut self, stylesheets: V)
+    where
+        V: Into<Vec<String>>,
+    {
+        self.stylesheets = stylesheets.into();
+    }
+
+    /// Return the `version` of the XML declaration of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_xml_version("1.1".to_string());
+    /// assert_eq!(feed.xml_version(), Some("1.1"));
+    /// ```
+    pub fn xml_version(&self) -> Option<&str> {
+        self.xml_version.as_deref()
+    }
+
+    /// Set the `version` of the XML declaration of this feed, which is written as `1.0` when
+    /// unset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_xml_version("1.1".to_string());
+    /// assert!(feed.to_string().starts_with(r#"<?xml version="1.1"?>"#));
+    /// ```
+    pub fn set_xml_version<V>(&mut self, xml_version: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.xml_version = xml_version.into();
+    }
+
+    /// Return the `standalone` pseudo-attribute of the XML declaration of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_standalone(true);
+    /// assert_eq!(feed.standalone(), Some(true));
+    /// ```
+    pub fn standalone(&self) -> Option<bool> {
+        self.standalone
+    }
+
+    /// Set the `standalone` pseudo-attribute of the XML declaration of this feed, which is
+    /// omitted when unset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_standalone(true);
+    /// assert!(feed.to_string().starts_with(r#"<?xml version="1.0" standalone="yes"?>"#));
+    /// ```
+    pub fn set_standalone<V>(&mut self, standalone: V)
+    where
+        V: Into<Option<bool>>,
+    {
+        self.standalone = standalone.into();
+    }
+
+    /// Return the attributes of this feed that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_unknown_attrs(vec![("v:channel".to_string(), "news".to_string())]);
+    /// assert_eq!(feed.unknown_attrs(), &[("v:channel".to_string(), "news".to_string())]);
+    /// ```
+    pub fn unknown_attrs(&self) -> &[(String, String)] {
+        &self.unknown_attrs
+    }
+
+    /// Set the attributes of this feed that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_unknown_attrs(vec![("v:channel".to_string(), "news".to_string())]);
+    /// ```
+    pub fn set_unknown_attrs<V>(&mut self, unknown_attrs: V)
+    where
+        V: Into<Vec<(String, String)>>,
+    {
+        self.unknown_attrs = unknown_attrs.into();
+    }
+
+    /// Remove the given tracking query parameters from every link of this feed, its entries and
+    /// their sources.
+    ///
+    /// See [`Link::cleaned_href_with`] for how parameters are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_href("http://example.com/?utm_medium=rss");
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![link]);
+    /// feed.strip_tracking_params(Link::TRACKING_PARAMS);
+    /// assert_eq!(feed.links()[0].href(), "http://example.com/");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn strip_tracking_params(&mut self, params: &[&str]) {
+        let clean = |links: &mut Vec<Link>| {
+            for link in links {
+                link.href = link.cleaned_href_with(params);
+            }
+        };
+
+        clean(&mut self.links);
+
+        for entry in &mut self.entries {
+            clean(&mut entry.links);
+
+            if let Some(ref mut source) = entry.source {
+                clean(&mut source.links);
+            }
+        }
+    }
+
+    /// Remove potentially dangerous markup from the HTML content and summaries of the entries
+    /// of this feed, so they can be rendered in a browser.
+    ///
+    /// Contents with `type="html"` or `type="text/html"` and summaries with [`TextType::Html`]
+    /// are cleaned with the default allowlist of [`ammonia`], which keeps common formatting elements such as `p`,
+    /// `a`, `img`, `ul` and `table` with a small set of attributes, and removes everything else:
+    /// `<script>` and `<style>` elements with their contents, event handler attributes such as
+    /// `onclick`, and URLs with schemes other than `http`, `https`, `mailto` and a few others,
+    /// including `javascript:`. Use [`Feed::sanitize_html_with`] to customize the allowlist.
+    ///
+    /// XHTML contents and summaries are cleaned the same way, without the wrapping `div`, and
+    /// changed to HTML, since the output of the sanitizer is not necessarily well-formed XML.
+    /// Plain text and other content types are left unchanged, as are the text constructs of the
+    /// feed itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content_html(r#"<p onclick="steal()">Hi<script>steal()</script></p>"#);
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![entry]);
+    /// feed.sanitize_html();
+    /// assert_eq!(
+    ///     feed.entries()[0].content().and_then(|content| content.value()),
+    ///     Some("<p>Hi</p>")
+    /// );
+    /// ```
+    ///
+    /// [`TextType::Html`]: crate::TextType::Html
+    #[cfg(feature = "ammonia")]
+    pub fn sanitize_html(&mut self) {
+        self.sanitize_html_with(&ammonia::Builder::default());
+    }
+
+    /// Remove potentially dangerous markup from the HTML content and summaries of the entries
+    /// of this feed with a customized sanitizer.
+    ///
+    /// See [`Feed::sanitize_html`] for which values are sanitized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_content_html(r#"<p>A <img src="http://example.com/a.png"> picture</p>"#);
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![entry]);
+    ///
+    /// let mut sanitizer = ammonia::Builder::default();
+    /// sanitizer.rm_tags(&["img"]);
+    /// feed.sanitize_html_with(&sanitizer);
+    /// assert_eq!(
+    ///     feed.entries()[0].content().and_then(|content| content.value()),
+    ///     Some("<p>A  picture</p>")
+    /// );
+    /// ```
+    #[cfg(feature = "ammonia")]
+    pub fn sanitize_html_with(&mut self, sanitizer: &ammonia::Builder<'_>) {
+        for entry in &mut self.entries {
+            if let Some(ref mut content) = entry.content {
+                match content.content_type.as_deref() {
+                    Some("html") | Some("text/html") => {
+                        if let Some(ref mut value) = content.value {
+                            *value = sanitizer.clean(value).to_string();
+                        }
+                    }
+                    Some("xhtml") => {
+                        if let Some(ref mut value) = content.value {
+                            *value = sanitizer.clean(unwrap_xhtml_div(value)).to_string();
+                        }
+                        content.content_type = Some("html".to_string());
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(ref mut summary) = entry.summary {
+                match summary.r#type {
+                    TextType::Html => {
+                        summary.value = sanitizer.clean(&summary.value).to_string();
+                    }
+                    TextType::Xhtml => {
+                        summary.value = sanitizer
+                            .clean(unwrap_xhtml_div(&summary.value))
+                            .to_string();
+                        summary.r#type = TextType::Html;
+                    }
+                    TextType::Text => {}
+                }
+            }
+        }
+    }
+
+    /// Return the hosts referenced by the links of this feed, its entries and their sources.
+    ///
+    /// Hrefs that are not absolute URLs, or have no host, are skipped. Hosts are returned as
+    /// normalized by the URL parser, so domain names are lowercase and IPv6 addresses are
+    /// enclosed in brackets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![Link::alternate("http://10.0.0.1/admin")]);
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![Link::alternate("https://Example.com/")]);
+    /// feed.set_entries(vec![entry]);
+    ///
+    /// let hosts = feed.href_hosts();
+    /// assert!(hosts.contains("example.com"));
+    /// assert!(hosts.contains("10.0.0.1"));
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn href_hosts(&self) -> HashSet<String> {
+        let entry_links = self.entries.iter().flat_map(|entry| {
+            let source_links = entry.source.iter().flat_map(|source| &source.links);
+            entry.links.iter().chain(source_links)
+        });
+
+        self.links
+            .iter()
+            .chain(entry_links)
+            .filter_map(|link| {
+                let url = url::Url::parse(link.href.trim()).ok()?;
+                url.host_str().map(str::to_string)
+            })
+            .collect()
+    }
+
+    /// Keep only the entries for which `pred` returns `true`, in place.
+    ///
+    /// Unlike [`Feed::filter_entries`] the `updated` date is left unchanged; call
+    /// [`Feed::update_from_entries`] afterwards to recompute it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut read = Entry::default();
+    /// read.set_id("urn:read");
+    /// let mut unread = Entry::default();
+    /// unread.set_id("urn:unread");
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![read, unread]);
+    ///
+    /// feed.retain_entries(|entry| entry.id() != "urn:read");
+    /// assert_eq!(feed.entries().len(), 1);
+    /// assert_eq!(feed.entries()[0].id(), "urn:unread");
+    /// ```
+    pub fn retain_entries<F>(&mut self, pred: F)
+    where
+        F: FnMut(&Entry) -> bool,
+    {
+        self.entries.retain(pred);
     }
 
-    /// Return the title of this feed.
+    /// Consume this feed and return it with every entry replaced by the result of `f`.
+    ///
+    /// Entries are transformed in order. The metadata of the feed is left unchanged, including
+    /// its `updated` date; see [`Feed::update_from_entries`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Feed;
+    /// use atom_syndication::{Entry, Feed};
     ///
     /// let mut feed = Feed::default();
     /// feed.set_title("Feed Title");
+    /// feed.set_entries(vec![Entry::default(); 2]);
+    ///
+    /// let feed = feed.map_entries(|mut entry| {
+    ///     entry.set_title("Rewritten");
+    ///     entry
+    /// });
     /// assert_eq!(feed.title(), "Feed Title");
+    /// assert!(feed.entries().iter().all(|entry| entry.title() == "Rewritten"));
     /// ```
-    pub fn title(&self) -> &Text {
-        &self.title
+    pub fn map_entries<F>(mut self, f: F) -> Feed
+    where
+        F: FnMut(Entry) -> Entry,
+    {
+        self.entries = self.entries.into_iter().map(f).collect();
+        self
     }
 
-    /// Set the title of this feed.
+    /// Set the `updated` date of this feed to the most recent `updated` date of its entries.
+    ///
+    /// If the feed has no entries the date is left unchanged.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Feed;
+    /// use atom_syndication::{Entry, Feed, FixedDateTime};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_updated(FixedDateTime::parse_from_rfc3339("2021-05-01T12:00:00Z").unwrap());
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_title("Feed Title");
+    /// feed.set_entries(vec![entry]);
+    /// feed.update_from_entries();
+    /// assert_eq!(feed.updated().to_rfc3339(), "2021-05-01T12:00:00+00:00");
     /// ```
-    pub fn set_title<V>(&mut self, title: V)
+    pub fn update_from_entries(&mut self) {
+        if let Some(updated) = self.entries.iter().map(|entry| entry.updated).max() {
+            self.updated = updated;
+        }
+    }
+
+    /// Return a copy of this feed containing only the entries for which `pred` returns `true`.
+    ///
+    /// The `updated` date of the new feed is the most recent `updated` date of the retained
+    /// entries. If no entries are retained the original date is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut first = Entry::default();
+    /// first.set_title("First");
+    /// let mut second = Entry::default();
+    /// second.set_title("Second");
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![first, second]);
+    ///
+    /// let filtered = feed.filter_entries(|entry| entry.title().as_str() == "Second");
+    /// assert_eq!(filtered.entries().len(), 1);
+    /// assert_eq!(filtered.entries()[0].title(), "Second");
+    /// ```
+    pub fn filter_entries<F>(&self, pred: F) -> Feed
     where
-        V: Into<Text>,
+        F: Fn(&Entry) -> bool,
     {
-        self.title = title.into();
+        let entries = self
+            .entries
+            .iter()
+            .filter(|entry| pred(entry))
+            .cloned()
+            .collect::<Vec<_>>();
+        let updated = entries
+            .iter()
+            .map(|entry| entry.updated)
+            .max()
+            .unwrap_or(self.updated);
+
+        Feed {
+            title: self.title.clone(),
+            id: self.id.clone(),
+            updated,
+            authors: self.authors.clone(),
+            categories: self.categories.clone(),
+            contributors: self.contributors.clone(),
+            generator: self.generator.clone(),
+            icon: self.icon.clone(),
+            links: self.links.clone(),
+            logo: self.logo.clone(),
+            rights: self.rights.clone(),
+            subtitle: self.subtitle.clone(),
+            entries,
+            extensions: self.extensions.clone(),
+            namespaces: self.namespaces.clone(),
+            base: self.base.clone(),
+            lang: self.lang.clone(),
+            unknown_attrs: self.unknown_attrs.clone(),
+            stylesheets: self.stylesheets.clone(),
+            xml_version: self.xml_version.clone(),
+            standalone: self.standalone,
+        }
     }
 
-    /// Return the unique URI of this feed.
+    /// Return a copy of this feed containing only the entries added or updated since `previous`.
+    ///
+    /// Entries are matched by id. An entry is added if `previous` has no entry with its id, and
+    /// updated if its `updated` date is later than that of the entry in `previous`. The entries
+    /// are sorted by their `updated` date, most recent first, and the metadata is copied as in
+    /// [`Feed::filter_entries`]. Entries removed since `previous` are not represented.
     ///
     /// # Examples
     ///
     /// ```
     /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
-    /// assert_eq!(feed.id(), "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// let previous = r#"
+    ///     <feed>
+    ///         <entry><id>1</id><updated>2021-05-01T00:00:00Z</updated></entry>
+    ///         <entry><id>2</id><updated>2021-05-01T00:00:00Z</updated></entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    /// let current = r#"
+    ///     <feed>
+    ///         <entry><id>1</id><updated>2021-05-01T00:00:00Z</updated></entry>
+    ///         <entry><id>2</id><updated>2021-05-02T00:00:00Z</updated></entry>
+    ///         <entry><id>3</id><updated>2021-05-03T00:00:00Z</updated></entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let changes = current.changes_since(&previous);
+    /// let ids = changes.entries().iter().map(|entry| entry.id()).collect::<Vec<_>>();
+    /// assert_eq!(ids, vec!["3", "2"]);
     /// ```
-    pub fn id(&self) -> &str {
-        self.id.as_str()
+    pub fn changes_since(&self, previous: &Feed) -> Feed {
+        let previous = previous
+            .entries
+            .iter()
+            .map(|entry| (entry.id.as_str(), entry.updated))
+            .collect::<HashMap<_, _>>();
+        let mut changes = self.filter_entries(|entry| match previous.get(entry.id.as_str()) {
+            Some(updated) => entry.updated > *updated,
+            None => true,
+        });
+        changes.sort_entries_by_updated();
+        changes
     }
 
-    /// Set the unique URI of this feed.
+    /// Return the URI of the next page of this feed from its `rel="next"` link.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Feed;
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("next");
+    /// link.set_href("http://example.com/feed?page=3");
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// feed.set_links(vec![link]);
+    /// assert_eq!(feed.next_page(), Some("http://example.com/feed?page=3"));
+    /// assert_eq!(feed.prev_page(), None);
     /// ```
-    pub fn set_id<V>(&mut self, id: V)
-    where
-        V: Into<String>,
-    {
-        self.id = id.into();
+    pub fn next_page(&self) -> Option<&str> {
+        find_link(&self.links, "next").map(Link::href)
     }
 
-    /// Return the last time that this feed was modified.
+    /// Return the URI of the previous page of this feed from its `rel="previous"` link.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Feed;
-    /// use atom_syndication::FixedDateTime;
-    /// use std::str::FromStr;
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("previous");
+    /// link.set_href("http://example.com/feed?page=1");
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
-    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// feed.set_links(vec![link]);
+    /// assert_eq!(feed.prev_page(), Some("http://example.com/feed?page=1"));
     /// ```
-    pub fn updated(&self) -> &FixedDateTime {
-        &self.updated
+    pub fn prev_page(&self) -> Option<&str> {
+        find_link(&self.links, "previous").map(Link::href)
     }
 
-    /// Set the last time that this feed was modified.
+    /// Return the URI of the first page of this feed from its `rel="first"` link.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Feed;
-    /// use atom_syndication::FixedDateTime;
-    /// use std::str::FromStr;
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("first");
+    /// link.set_href("http://example.com/feed");
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// feed.set_links(vec![link]);
+    /// assert_eq!(feed.first_page(), Some("http://example.com/feed"));
     /// ```
-    pub fn set_updated<V>(&mut self, updated: V)
-    where
-        V: Into<FixedDateTime>,
-    {
-        self.updated = updated.into();
+    pub fn first_page(&self) -> Option<&str> {
+        find_link(&self.links, "first").map(Link::href)
     }
 
-    /// Return the authors of this feed.
+    /// Return the URI of the last page of this feed from its `rel="last"` link.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Person};
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("last");
+    /// link.set_href("http://example.com/feed?page=9");
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_authors(vec![Person::default()]);
-    /// assert_eq!(feed.authors().len(), 1);
+    /// feed.set_links(vec![link]);
+    /// assert_eq!(feed.last_page(), Some("http://example.com/feed?page=9"));
     /// ```
-    pub fn authors(&self) -> &[Person] {
-        self.authors.as_slice()
+    pub fn last_page(&self) -> Option<&str> {
+        find_link(&self.links, "last").map(Link::href)
     }
 
-    /// Set the authors of this feed.
+    /// Return `true` if this feed is marked as an archive document with `<fh:archive/>`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Person};
+    /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_authors(vec![Person::default()]);
+    /// let feed = r#"
+    ///     <feed xmlns="http://www.w3.org/2005/Atom" xmlns:fh="http://purl.org/syndication/history/1.0">
+    ///         <fh:archive/>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    /// assert!(feed.is_archive());
+    /// assert!(!feed.is_complete());
     /// ```
-    pub fn set_authors<V>(&mut self, authors: V)
-    where
-        V: Into<Vec<Person>>,
-    {
-        self.authors = authors.into();
+    pub fn is_archive(&self) -> bool {
+        !self.feed_history("archive").is_empty()
     }
 
-    /// Return the categories this feed belongs to.
+    /// Return `true` if this feed is marked as a complete feed with `<fh:complete/>`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Category};
+    /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_categories(vec![Category::default()]);
-    /// assert_eq!(feed.categories().len(), 1);
+    /// let feed = r#"
+    ///     <feed xmlns="http://www.w3.org/2005/Atom" xmlns:fh="http://purl.org/syndication/history/1.0">
+    ///         <fh:complete/>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    /// assert!(feed.is_complete());
     /// ```
-    pub fn categories(&self) -> &[Category] {
-        self.categories.as_slice()
+    pub fn is_complete(&self) -> bool {
+        !self.feed_history("complete").is_empty()
     }
 
-    /// Set the categories this feed belongs to.
+    /// Sort the entries of this feed by their `updated` date, most recent first.
+    ///
+    /// The sort is stable, so entries with the same date keep their order.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Category};
+    /// use atom_syndication::{Entry, Feed, FixedDateTime};
+    ///
+    /// let mut old = Entry::default();
+    /// old.set_updated(FixedDateTime::parse_from_rfc3339("2017-01-01T00:00:00Z").unwrap());
+    /// let mut new = Entry::default();
+    /// new.set_updated(FixedDateTime::parse_from_rfc3339("2018-01-01T00:00:00Z").unwrap());
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_categories(vec![Category::default()]);
+    /// feed.set_entries(vec![old.clone(), new.clone()]);
+    /// feed.sort_entries_by_updated();
+    /// assert_eq!(feed.entries(), &[new, old][..]);
     /// ```
-    pub fn set_categories<V>(&mut self, categories: V)
-    where
-        V: Into<Vec<Category>>,
-    {
-        self.categories = categories.into();
+    pub fn sort_entries_by_updated(&mut self) {
+        self.entries
+            .sort_by_key(|entry| std::cmp::Reverse(entry.updated));
     }
 
-    /// Return the contributors to this feed.
+    /// Keep only the `n` most recently updated entries of this feed.
+    ///
+    /// The remaining entries are sorted by their `updated` date, most recent first.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Person};
+    /// use atom_syndication::{Entry, Feed};
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_contributors(vec![Person::default()]);
-    /// assert_eq!(feed.contributors().len(), 1);
+    /// feed.set_entries(vec![Entry::default(); 20]);
+    /// feed.truncate_entries(10);
+    /// assert_eq!(feed.entries().len(), 10);
     /// ```
-    pub fn contributors(&self) -> &[Person] {
-        self.contributors.as_slice()
+    pub fn truncate_entries(&mut self, n: usize) {
+        self.sort_entries_by_updated();
+        self.entries.truncate(n);
     }
 
-    /// Set the contributors to this feed.
+    /// Merge the entries of feeds whose entries are each sorted newest first into one feed,
+    /// newest first.
+    ///
+    /// The inputs must already be sorted by their `updated` dates, most recent first, as
+    /// [`Feed::sort_entries_by_updated`] leaves them; the inputs are merged with a heap in
+    /// `O(n log k)` time for `n` entries from `k` feeds instead of being sorted again. An
+    /// unsorted input still has all of its entries included, but the result is then not
+    /// sorted either. Entries with the same date keep the order of the feeds they come from.
+    ///
+    /// Only the first, and so most recently updated, entry with a given id is kept; entries
+    /// with an empty id are all kept. The metadata of the inputs is discarded: the result is a
+    /// default feed whose `updated` date is that of its newest entry.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Person};
+    /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_contributors(vec![Person::default()]);
+    /// let first = r#"<feed>
+    ///     <entry><id>a</id><updated>2021-05-03T00:00:00Z</updated></entry>
+    ///     <entry><id>b</id><updated>2021-05-01T00:00:00Z</updated></entry>
+    /// </feed>"#.parse::<Feed>().unwrap();
+    /// let second = r#"<feed>
+    ///     <entry><id>c</id><updated>2021-05-02T00:00:00Z</updated></entry>
+    ///     <entry><id>a</id><updated>2021-04-01T00:00:00Z</updated></entry>
+    /// </feed>"#.parse::<Feed>().unwrap();
+    ///
+    /// let merged = Feed::merge_sorted(vec![first, second]);
+    /// let ids = merged.entries().iter().map(|entry| entry.id()).collect::<Vec<_>>();
+    /// assert_eq!(ids, vec!["a", "c", "b"]);
+    /// assert_eq!(merged.updated().to_rfc3339(), "2021-05-03T00:00:00+00:00");
     /// ```
-    pub fn set_contributors<V>(&mut self, contributors: V)
+    pub fn merge_sorted<I>(feeds: I) -> Feed
     where
-        V: Into<Vec<Person>>,
+        I: IntoIterator<Item = Feed>,
     {
-        self.contributors = contributors.into();
+        let mut sources = feeds
+            .into_iter()
+            .map(|feed| feed.entries.into_iter())
+            .collect::<Vec<_>>();
+        let mut heads = sources
+            .iter_mut()
+            .map(|source| source.next())
+            .collect::<Vec<_>>();
+        let mut heap = heads
+            .iter()
+            .enumerate()
+            .filter_map(|(index, head)| Some((head.as_ref()?.updated, Reverse(index))))
+            .collect::<BinaryHeap<_>>();
+
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        while let Some((_, Reverse(index))) = heap.pop() {
+            let entry = match heads[index].take() {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            if let Some(next) = sources[index].next() {
+                heap.push((next.updated, Reverse(index)));
+                heads[index] = Some(next);
+            }
+
+            if entry.id.is_empty() || seen.insert(entry.id.clone()) {
+                entries.push(entry);
+            }
+        }
+
+        let mut feed = Feed {
+            entries,
+            ..Feed::default()
+        };
+        feed.update_from_entries();
+        feed
     }
 
-    /// Return the name of the software used to generate this feed.
+    /// Return the entries on the zero-based page `page` when showing `per_page` entries per page.
+    ///
+    /// Entries are paged from the most recently updated one, as sorted by
+    /// [`Feed::sort_entries_by_updated`], without changing the order of the entries in this
+    /// feed; entries updated at the same instant keep their current order. The last page may
+    /// hold fewer than `per_page` entries, and pages past the end, as well as every page when
+    /// `per_page` is zero, are empty.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Generator};
+    /// use atom_syndication::{Entry, Feed, FixedDateTime};
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_generator(Generator::default());
-    /// assert!(feed.generator().is_some());
+    /// feed.set_entries(vec![Entry::default(); 25]);
+    /// let updated = FixedDateTime::parse_from_rfc3339("2021-05-01T12:00:00Z").unwrap();
+    /// feed.entries[24].set_updated(updated);
+    /// feed.entries[24].set_id("latest");
+    ///
+    /// assert_eq!(feed.entries_page(0, 10)[0].id(), "latest");
+    /// assert_eq!(feed.entries_page(2, 10).len(), 5);
+    /// assert!(feed.entries_page(3, 10).is_empty());
     /// ```
-    pub fn generator(&self) -> Option<&Generator> {
-        self.generator.as_ref()
+    pub fn entries_page(&self, page: usize, per_page: usize) -> Vec<&Entry> {
+        let len = self.entries.len();
+        let start = page.checked_mul(per_page).unwrap_or(len).min(len);
+        let end = start.saturating_add(per_page).min(len);
+
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.updated));
+        entries.truncate(end);
+        entries.drain(..start);
+        entries
     }
 
-    /// Set the name of the software used to generate this feed.
+    /// Split this feed into [RFC 5005](https://tools.ietf.org/html/rfc5005#section-3) paged
+    /// feeds of `per_page` entries each, linked to each other relative to `base_url`.
+    ///
+    /// Entries are paged in their current order, so call [`Feed::sort_entries_by_updated`]
+    /// first to put the most recent entries on the first page. The first page is served at
+    /// `base_url` itself and page `n` at `base_url?page=n`, or `base_url&page=n` when `base_url`
+    /// already has a query. Every page keeps the metadata of this feed, and its `self`, `first`,
+    /// `last`, `next` and `previous` links are replaced by links to the other pages. The last
+    /// page holds the oldest entries and is marked by having no `next` link.
+    ///
+    /// A feed without entries produces a single empty page, and a `per_page` of zero puts every
+    /// entry on one page.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Generator};
+    /// use atom_syndication::{Entry, Feed};
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_generator(Generator::default());
+    /// feed.set_entries(vec![Entry::default(); 25]);
+    ///
+    /// let pages = feed.into_pages(10, "http://example.com/feed");
+    /// assert_eq!(pages.len(), 3);
+    /// assert_eq!(pages[0].next_page(), Some("http://example.com/feed?page=2"));
+    /// assert_eq!(pages[2].prev_page(), Some("http://example.com/feed?page=2"));
+    /// assert_eq!(pages[2].next_page(), None);
+    /// assert_eq!(pages[2].entries().len(), 5);
     /// ```
-    pub fn set_generator<V>(&mut self, generator: V)
-    where
-        V: Into<Option<Generator>>,
-    {
-        self.generator = generator.into()
+    pub fn into_pages(mut self, per_page: usize, base_url: &str) -> Vec<Feed> {
+        const PAGING_RELS: &[&str] = &["self", "first", "last", "next", "previous"];
+
+        let mut entries = std::mem::take(&mut self.entries);
+        self.links
+            .retain(|link| !PAGING_RELS.contains(&link.normalized_rel()));
+
+        let per_page = if per_page == 0 {
+            entries.len().max(1)
+        } else {
+            per_page
+        };
+        // Move the entries into their pages, splitting the last page off first.
+        let mut chunks = Vec::with_capacity(entries.len() / per_page + 1);
+        while !entries.is_empty() {
+            let last = (entries.len() - 1) / per_page * per_page;
+            chunks.push(entries.split_off(last));
+        }
+        chunks.reverse();
+        if chunks.is_empty() {
+            chunks.push(Vec::new());
+        }
+
+        let separator = if base_url.contains('?') { '&' } else { '?' };
+        let page_url = |page: usize| {
+            if page == 1 {
+                base_url.to_string()
+            } else {
+                format!("{}{}page={}", base_url, separator, page)
+            }
+        };
+        let page_link = |rel: &str, page: usize| Link {
+            href: page_url(page),
+            rel: rel.to_string(),
+            ..Link::default()
+        };
+
+        let count = chunks.len();
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, entries)| {
+                let page = index + 1;
+                let mut feed = self.clone();
+                feed.links.push(page_link("self", page));
+                feed.links.push(page_link("first", 1));
+                feed.links.push(page_link("last", count));
+                if page > 1 {
+                    feed.links.push(page_link("previous", page - 1));
+                }
+                if page < count {
+                    feed.links.push(page_link("next", page + 1));
+                }
+                feed.entries = entries;
+                feed
+            })
+            .collect()
     }
 
-    /// Return the icon for this feed.
+    /// Return the entries of this feed that were last modified strictly after `time`.
+    ///
+    /// Entries are returned in their current order. See [`Entry::is_updated_after`].
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
     /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_icon("http://example.com/icon.png".to_string());
-    /// assert_eq!(feed.icon(), Some("http://example.com/icon.png"));
+    /// let feed = r#"
+    ///     <feed>
+    ///         <entry><id>old</id><updated>1970-01-01T00:00:30Z</updated></entry>
+    ///         <entry><id>new</id><updated>1970-01-01T00:01:30Z</updated></entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let entries = feed.entries_updated_after(UNIX_EPOCH + Duration::from_secs(60));
+    /// assert_eq!(entries.len(), 1);
+    /// assert_eq!(entries[0].id(), "new");
     /// ```
-    pub fn icon(&self) -> Option<&str> {
-        self.icon.as_deref()
+    pub fn entries_updated_after(&self, time: SystemTime) -> Vec<&Entry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.is_updated_after(time))
+            .collect()
     }
 
-    /// Set the icon for this feed.
+    /// Return the entries of this feed dated from `start`, inclusive, up to `end`, exclusive.
+    ///
+    /// Entries are dated by [`Entry::published_or_updated`] and returned in their current
+    /// order. Entries whose date cannot be represented as a `SystemTime` are left out.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
     /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_icon("http://example.com/icon.png".to_string());
+    /// let feed = r#"
+    ///     <feed>
+    ///         <entry><id>1</id><updated>1970-01-01T00:00:30Z</updated></entry>
+    ///         <entry><id>2</id><updated>1970-01-01T00:01:00Z</updated></entry>
+    ///         <entry><id>3</id><updated>1970-01-01T00:02:00Z</updated></entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let start = UNIX_EPOCH + Duration::from_secs(60);
+    /// let entries = feed.entries_between(start, start + Duration::from_secs(60));
+    /// assert_eq!(entries.len(), 1);
+    /// assert_eq!(entries[0].id(), "2");
     /// ```
-    pub fn set_icon<V>(&mut self, icon: V)
-    where
-        V: Into<Option<String>>,
-    {
-        self.icon = icon.into()
+    pub fn entries_between(&self, start: SystemTime, end: SystemTime) -> Vec<&Entry> {
+        self.entries
+            .iter()
+            .filter(|entry| {
+                system_time(entry.published_or_updated())
+                    .map(|date| start <= date && date < end)
+                    .unwrap_or(false)
+            })
+            .collect()
     }
 
-    /// Return the Web pages related to this feed.
+    /// Remove the entries of this feed dated before `now - window`, keeping entries without a
+    /// usable date.
+    ///
+    /// Entries are dated by [`Entry::published_or_updated`], and an entry dated exactly
+    /// `now - window` is kept. An entry has no usable date when it has neither a `published`
+    /// nor an `updated` timestamp, or when its date cannot be represented as a `SystemTime`;
+    /// use [`Feed::retain_recent_with`] to remove such entries as well. The `updated` date of
+    /// the feed is left unchanged.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Link};
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_links(vec![Link::default()]);
-    /// assert_eq!(feed.links().len(), 1);
+    /// let mut feed = r#"
+    ///     <feed>
+    ///         <entry><id>1</id><updated>1970-01-02T00:00:00Z</updated></entry>
+    ///         <entry><id>2</id><updated>1970-01-09T00:00:00Z</updated></entry>
+    ///         <entry><id>3</id></entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let day = Duration::from_secs(24 * 60 * 60);
+    /// feed.retain_recent(7 * day, UNIX_EPOCH + 10 * day);
+    /// let ids = feed.entries().iter().map(|entry| entry.id()).collect::<Vec<_>>();
+    /// assert_eq!(ids, vec!["2", "3"]);
     /// ```
-    pub fn links(&self) -> &[Link] {
-        self.links.as_slice()
+    pub fn retain_recent(&mut self, window: Duration, now: SystemTime) {
+        self.retain_recent_with(window, now, true);
     }
 
-    /// Set the Web pages related to this feed.
+    /// Remove the entries of this feed dated before `now - window`, choosing whether to keep
+    /// entries without a usable date.
+    ///
+    /// See [`Feed::retain_recent`], which keeps them.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Link};
+    /// use std::time::{Duration, SystemTime};
+    /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_links(vec![Link::default()]);
+    /// let mut feed = "<feed><entry><id>1</id></entry></feed>".parse::<Feed>().unwrap();
+    /// feed.retain_recent_with(Duration::from_secs(60), SystemTime::now(), false);
+    /// assert!(feed.entries().is_empty());
     /// ```
-    pub fn set_links<V>(&mut self, links: V)
-    where
-        V: Into<Vec<Link>>,
-    {
-        self.links = links.into();
+    pub fn retain_recent_with(&mut self, window: Duration, now: SystemTime, keep_undated: bool) {
+        let cutoff = now.checked_sub(window);
+        let missing = default_fixed_datetime();
+        self.entries.retain(|entry| {
+            let date = entry.published_or_updated();
+            match system_time(date) {
+                Ok(_) if entry.published.is_none() && *date == missing => keep_undated,
+                Ok(date) => cutoff.map_or(true, |cutoff| date >= cutoff),
+                Err(_) => keep_undated,
+            }
+        });
     }
 
-    /// Return the logo for this feed.
+    /// Return the entries of this feed whose [`Entry::effective_lang`] is `lang` or a more
+    /// specific variant of it.
+    ///
+    /// Languages are compared case-insensitively and by whole subtags, so `en` matches `en` and
+    /// `en-US` but not `eo`. Entries without a language never match.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Feed;
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut german = Entry::default();
+    /// german.set_lang("de".to_string());
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_logo("http://example.com/logo.png".to_string());
-    /// assert_eq!(feed.logo(), Some("http://example.com/logo.png"));
+    /// feed.set_lang("en-US".to_string());
+    /// feed.set_entries(vec![Entry::default(), german]);
+    ///
+    /// assert_eq!(feed.entries_in_language("en").len(), 1);
+    /// assert_eq!(feed.entries_in_language("de").len(), 1);
+    /// assert!(feed.entries_in_language("fr").is_empty());
     /// ```
-    pub fn logo(&self) -> Option<&str> {
-        self.logo.as_deref()
+    pub fn entries_in_language(&self, lang: &str) -> Vec<&Entry> {
+        let matches = |entry_lang: &str| {
+            entry_lang.len() >= lang.len()
+                && entry_lang.is_char_boundary(lang.len())
+                && entry_lang[..lang.len()].eq_ignore_ascii_case(lang)
+                && entry_lang[lang.len()..]
+                    .chars()
+                    .next()
+                    .map_or(true, |c| c == '-' || c == '_')
+        };
+
+        self.entries
+            .iter()
+            .filter(|entry| entry.effective_lang(self).map_or(false, matches))
+            .collect()
     }
 
-    /// Set the logo for this feed.
+    /// Remove every entry whose [`Entry::content_hash`] matches the one of an earlier entry.
+    ///
+    /// This catches duplicates published under different ids. The first occurrence of each
+    /// entry is kept and the order of the remaining entries is preserved.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::Feed;
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut original = Entry::default();
+    /// original.set_id("urn:original");
+    /// original.set_title("Cross-posted");
+    ///
+    /// let mut copy = original.clone();
+    /// copy.set_id("urn:copy");
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_logo("http://example.com/logo.png".to_string());
+    /// feed.set_entries(vec![original, copy]);
+    /// feed.dedup_by_content();
+    /// assert_eq!(feed.entries().len(), 1);
+    /// assert_eq!(feed.entries()[0].id(), "urn:original");
     /// ```
-    pub fn set_logo<V>(&mut self, logo: V)
-    where
-        V: Into<Option<String>>,
-    {
-        self.logo = logo.into()
+    pub fn dedup_by_content(&mut self) {
+        let mut seen = HashSet::new();
+        self.entries
+            .retain(|entry| seen.insert(entry.content_hash()));
     }
 
-    /// Return the information about the rights held in and over this feed.
+    /// Remove the links of this feed and of each of its entries that repeat an earlier link of
+    /// the same feed or entry, keeping the first one.
+    ///
+    /// See [`Entry::dedup_links`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Text};
+    /// use atom_syndication::{Feed, Link};
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_rights(Text::from("© 2017 John Doe"));
-    /// assert_eq!(feed.rights().map(Text::as_str), Some("© 2017 John Doe"));
+    /// feed.set_links(vec![
+    ///     Link::self_link("http://example.com/feed"),
+    ///     Link::self_link("http://example.com/feed"),
+    /// ]);
+    /// feed.dedup_links();
+    /// assert_eq!(feed.links().len(), 1);
     /// ```
-    pub fn rights(&self) -> Option<&Text> {
-        self.rights.as_ref()
+    pub fn dedup_links(&mut self) {
+        dedup_links(&mut self.links);
+        for entry in &mut self.entries {
+            entry.dedup_links();
+        }
     }
 
-    /// Set the information about the rights held in and over this feed.
+    /// Return a copy of this feed with only its `n` most recently updated entries.
+    ///
+    /// See [`Feed::truncate_entries`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Text};
+    /// use atom_syndication::{Entry, Feed};
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_rights(Text::from("© 2017 John Doe"));
+    /// feed.set_entries(vec![Entry::default(); 20]);
+    /// assert_eq!(feed.with_latest(10).entries().len(), 10);
+    /// assert_eq!(feed.entries().len(), 20);
     /// ```
-    pub fn set_rights<V>(&mut self, rights: V)
-    where
-        V: Into<Option<Text>>,
-    {
-        self.rights = rights.into()
+    pub fn with_latest(&self, n: usize) -> Feed {
+        let mut feed = self.clone();
+        feed.truncate_entries(n);
+        feed
     }
 
-    /// Return the description or subtitle of this feed.
+    /// Return every distinct person named as an author or contributor of this feed or its
+    /// entries.
+    ///
+    /// People are returned in the order they first appear: the authors and contributors of the
+    /// feed, then the authors and contributors of each entry. Two people are considered the same
+    /// as by [`Person::same_as`], so emails differing only in case match, and the first
+    /// occurrence is kept.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Text};
+    /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_subtitle(Text::from("Feed subtitle"));
-    /// assert_eq!(feed.subtitle().map(Text::as_str), Some("Feed subtitle"));
+    /// let feed = r#"
+    ///     <feed>
+    ///         <author><name>Jane Doe</name><email>jane@example.com</email></author>
+    ///         <entry>
+    ///             <author><name>Jane Doe</name><email>jane@example.com</email></author>
+    ///             <contributor><name>John Doe</name></contributor>
+    ///         </entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let names = feed.all_people().iter().map(|p| p.name()).collect::<Vec<_>>();
+    /// assert_eq!(names, vec!["Jane Doe", "John Doe"]);
     /// ```
-    pub fn subtitle(&self) -> Option<&Text> {
-        self.subtitle.as_ref()
+    pub fn all_people(&self) -> Vec<&Person> {
+        let mut seen = HashSet::new();
+        let entries = self
+            .entries
+            .iter()
+            .flat_map(|entry| entry.authors.iter().chain(&entry.contributors));
+
+        self.authors
+            .iter()
+            .chain(&self.contributors)
+            .chain(entries)
+            .filter(|person| {
+                let email = person
+                    .email
+                    .as_ref()
+                    .map(|email| email.to_ascii_lowercase());
+                seen.insert((person.name.as_str(), email))
+            })
+            .collect()
     }
 
-    /// Set the description or subtitle of this feed.
+    /// Return every distinct link relation used by the links of this feed and its entries.
+    ///
+    /// Links with an empty `rel` are counted as `alternate`, and relations written as IANA
+    /// registry URIs are counted by their bare token, see [`Link::normalized_rel`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Text};
+    /// use atom_syndication::Feed;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_subtitle(Text::from("Feed subtitle"));
+    /// let feed = r#"
+    ///     <feed>
+    ///         <link rel="self" href="http://example.com/feed"/>
+    ///         <entry>
+    ///             <link href="http://example.com/1"/>
+    ///             <link rel="related" href="http://example.com/2"/>
+    ///         </entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let rels = feed.link_rels();
+    /// assert_eq!(rels.len(), 3);
+    /// assert!(rels.contains("self") && rels.contains("alternate") && rels.contains("related"));
     /// ```
-    pub fn set_subtitle<V>(&mut self, subtitle: V)
-    where
-        V: Into<Option<Text>>,
-    {
-        self.subtitle = subtitle.into()
+    pub fn link_rels(&self) -> HashSet<String> {
+        self.links
+            .iter()
+            .chain(self.entries.iter().flat_map(|entry| &entry.links))
+            .map(|link| link.relation().to_string())
+            .collect()
     }
 
-    /// Return the entries in this feed.
+    /// Return an estimate of the number of bytes this feed takes up when written.
+    ///
+    /// The estimate is computed from the lengths of the values in the feed plus the overhead of
+    /// their tags and attributes, without writing the feed. Text is assumed to expand every
+    /// character that needs escaping to a six byte entity, so the estimate is usually somewhat
+    /// larger than the written output but is not guaranteed to be an upper bound.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Entry};
+    /// use atom_syndication::Feed;
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_entries(vec![Entry::default()]);
-    /// assert_eq!(feed.entries().len(), 1);
+    /// feed.set_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    /// feed.set_title("Feed Title");
+    ///
+    /// let written = feed.to_string().len();
+    /// let estimate = feed.byte_size_estimate();
+    /// assert!(estimate > written / 2 && estimate < written * 2);
     /// ```
-    pub fn entries(&self) -> &[Entry] {
-        self.entries.as_slice()
+    pub fn byte_size_estimate(&self) -> usize {
+        self.size_estimate()
     }
 
-    /// Set the entries in this feed.
+    /// The maximum number of pages followed by [`Feed::read_all_pages`], including the initial
+    /// page.
+    pub const MAX_PAGES: usize = 1000;
+
+    /// Collect the entries of a paged feed by following its `rel="next"` links.
+    ///
+    /// Starting with the entries of `initial`, every next page is retrieved with `fetch`, which
+    /// is given the URI of the page. Walking stops when a page has no next link or after
+    /// [`Feed::MAX_PAGES`] pages. A next link pointing to a page that has already been visited,
+    /// including `initial` through its `rel="self"` link, fails with [`Error::PagingCycle`]
+    /// naming that URI. Any error returned by `fetch` is returned immediately.
+    ///
+    /// With the `url` feature, relative next links are resolved against the `xml:base` of the
+    /// link and the feed, and against the URI the page was fetched from, or for `initial` its
+    /// `rel="self"` link, before they are fetched or compared. Without it, or when there is no
+    /// absolute base, `fetch` is given the `href` as it appears in the page.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Entry};
+    /// use atom_syndication::{Error, Feed};
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_entries(vec![Entry::default()]);
+    /// let first = r#"
+    ///     <feed xmlns="http://www.w3.org/2005/Atom">
+    ///         <link rel="next" href="http://example.com/feed?page=2"/>
+    ///         <entry><id>urn:1</id></entry>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    ///
+    /// let entries = Feed::read_all_pages(first, |url| {
+    ///     assert_eq!(url, "http://example.com/feed?page=2");
+    ///     "<feed><entry><id>urn:2</id></entry></feed>".parse::<Feed>()
+    /// })
+    /// .unwrap();
+    /// assert_eq!(entries.len(), 2);
     /// ```
-    pub fn set_entries<V>(&mut self, entries: V)
+    pub fn read_all_pages<F>(initial: Feed, mut fetch: F) -> Result<Vec<Entry>, Error>
     where
-        V: Into<Vec<Entry>>,
+        F: FnMut(&str) -> Result<Feed, Error>,
     {
-        self.entries = entries.into();
+        let mut visited = HashSet::new();
+        let location = initial.page_link("self", None);
+        let mut next = initial.page_link("next", location.as_deref());
+        if let Some(location) = location {
+            visited.insert(location);
+        }
+
+        let mut entries = initial.entries;
+        let mut pages = 1;
+
+        while let Some(url) = next.take() {
+            if visited.contains(&url) {
+                return Err(Error::PagingCycle(url));
+            }
+            if pages >= Feed::MAX_PAGES {
+                break;
+            }
+
+            let page = fetch(&url)?;
+            pages += 1;
+
+            next = page.page_link("next", Some(&url));
+            visited.insert(url);
+            entries.extend(page.entries);
+        }
+
+        Ok(entries)
     }
 
-    /// Return the extensions for this feed.
+    /// Return the link to the license of this feed (`rel="license"`, RFC 4946).
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::BTreeMap;
-    /// use atom_syndication::Feed;
-    /// use atom_syndication::extension::{ExtensionMap, Extension};
-    ///
-    /// let extension = Extension::default();
-    ///
-    /// let mut item_map = BTreeMap::<String, Vec<Extension>>::new();
-    /// item_map.insert("ext:name".to_string(), vec![extension]);
+    /// use atom_syndication::{Feed, Link};
     ///
-    /// let mut extension_map = ExtensionMap::default();
-    /// extension_map.insert("ext".to_string(), item_map);
+    /// let mut link = Link::default();
+    /// link.set_rel("license");
+    /// link.set_href("https://creativecommons.org/licenses/by/4.0/");
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_extensions(extension_map);
-    /// assert_eq!(feed.extensions()
-    ///                .get("ext")
-    ///                .and_then(|m| m.get("ext:name"))
-    ///                .map(|v| v.len()),
-    ///            Some(1));
+    /// feed.set_links(vec![link]);
+    /// assert_eq!(
+    ///     feed.license_link().map(Link::href),
+    ///     Some("https://creativecommons.org/licenses/by/4.0/")
+    /// );
     /// ```
-    pub fn extensions(&self) -> &ExtensionMap {
-        &self.extensions
+    pub fn license_link(&self) -> Option<&Link> {
+        find_link(&self.links, "license")
     }
 
-    /// Set the extensions for this feed.
+    /// Return the URI of the license of this feed from its `rel="license"` link.
     ///
     /// # Examples
     ///
     /// ```
     /// use atom_syndication::Feed;
-    /// use atom_syndication::extension::ExtensionMap;
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_extensions(ExtensionMap::default());
+    /// let feed = r#"
+    ///     <feed>
+    ///         <link rel="license" href="https://creativecommons.org/licenses/by/4.0/"/>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.license_url(), Some("https://creativecommons.org/licenses/by/4.0/"));
     /// ```
-    pub fn set_extensions<V>(&mut self, extensions: V)
-    where
-        V: Into<ExtensionMap>,
-    {
-        self.extensions = extensions.into()
+    pub fn license_url(&self) -> Option<&str> {
+        self.license_link().map(Link::href)
     }
 
-    /// Return the namespaces for this feed.
+    /// Return the licensing information of this feed: the URI of its `rel="license"` link, or
+    /// the text of its `rights` element if it has no such link.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::BTreeMap;
-    /// use atom_syndication::Feed;
-    ///
-    /// let mut namespaces = BTreeMap::new();
-    /// namespaces.insert("ext".to_string(), "http://example.com".to_string());
+    /// use atom_syndication::{Feed, Text};
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_namespaces(namespaces);
-    /// assert_eq!(feed.namespaces().get("ext").map(|s| s.as_str()), Some("http://example.com"));
+    /// assert_eq!(feed.license_info(), None);
+    ///
+    /// feed.set_rights(Text::plain("© 2021 John Doe, CC BY 4.0"));
+    /// assert_eq!(feed.license_info(), Some("© 2021 John Doe, CC BY 4.0"));
     /// ```
-    pub fn namespaces(&self) -> &BTreeMap<String, String> {
-        &self.namespaces
+    pub fn license_info(&self) -> Option<&str> {
+        self.license_url().or_else(|| {
+            self.rights
+                .as_ref()
+                .map(Text::as_str)
+                .filter(|rights| !rights.is_empty())
+        })
     }
 
-    /// Set the namespaces for this feed.
+    /// Return how this feed relates to the other documents of its history.
+    ///
+    /// `<fh:complete/>` takes precedence over `<fh:archive/>`, which takes precedence over the
+    /// `next`, `previous`, `first`, `last`, `next-archive` and `prev-archive` links that mark a
+    /// paged feed.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::BTreeMap;
-    /// use atom_syndication::Feed;
+    /// use atom_syndication::{Feed, PagingState};
     ///
-    /// let mut feed = Feed::default();
-    /// feed.set_namespaces(BTreeMap::new());
+    /// let feed = r#"
+    ///     <feed xmlns="http://www.w3.org/2005/Atom">
+    ///         <link rel="next" href="http://example.com/feed?page=2"/>
+    ///     </feed>
+    /// "#.parse::<Feed>().unwrap();
+    /// assert_eq!(feed.paging_state(), PagingState::Paged);
+    /// assert_eq!(Feed::default().paging_state(), PagingState::Unknown);
     /// ```
-    pub fn set_namespaces<V>(&mut self, namespaces: V)
-    where
-        V: Into<BTreeMap<String, String>>,
-    {
-        self.namespaces = namespaces.into()
-    }
+    pub fn paging_state(&self) -> PagingState {
+        const PAGING_RELS: &[&str] = &[
+            "next",
+            "previous",
+            "first",
+            "last",
+            "next-archive",
+            "prev-archive",
+        ];
 
-    /// Return base URL of the feed.
-    pub fn base(&self) -> Option<&str> {
-        self.base.as_deref()
+        if self.is_complete() {
+            PagingState::Complete
+        } else if self.is_archive() {
+            PagingState::Archive
+        } else if PAGING_RELS
+            .iter()
+            .any(|rel| find_link(&self.links, rel).is_some())
+        {
+            PagingState::Paged
+        } else {
+            PagingState::Unknown
+        }
     }
 
-    /// Set base URL of the feed.
-    pub fn set_base<V>(&mut self, base: V)
-    where
-        V: Into<Option<String>>,
-    {
-        self.base = base.into();
+    fn feed_history(&self, name: &str) -> Vec<&Extension> {
+        find_extensions(
+            &self.extensions,
+            &self.namespaces,
+            FEED_HISTORY_NAMESPACE,
+            "fh",
+            name,
+        )
     }
+}
 
-    /// Return natural language of the feed.
-    pub fn lang(&self) -> Option<&str> {
-        self.lang.as_deref()
-    }
+#[cfg(feature = "test-util")]
+impl Feed {
+    /// Write this feed, read it back and check that the result equals this feed.
+    ///
+    /// Comments and stylesheets are preserved when reading the feed back. The XML declaration
+    /// is not compared, since an unset version is written as `1.0`. On a mismatch the error
+    /// names every field that differs, with its value before and after the round trip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    /// feed.assert_round_trips().unwrap();
+    /// ```
+    pub fn assert_round_trips(&self) -> Result<(), String> {
+        let xml = self
+            .write_unchecked(Vec::new())
+            .map_err(|err| format!("failed to write the feed: {}", err))?;
 
-    /// Set the base URL of the feed.
-    pub fn set_lang<V>(&mut self, lang: V)
-    where
-        V: Into<Option<String>>,
-    {
-        self.lang = lang.into();
+        let options = ReadOptions {
+            preserve_comments: true,
+            preserve_stylesheets: true,
+            ..ReadOptions::default()
+        };
+        let read = Feed::read_with(&xml[..], options).map_err(|err| {
+            format!(
+                "failed to read the written feed: {}\n{}",
+                err,
+                String::from_utf8_lossy(&xml)
+            )
+        })?;
+
+        let mut differences = Vec::new();
+        macro_rules! compare {
+            ($($field:ident),*) => {
+                $(
+                    if self.$field != read.$field {
+                        differences.push(format!(
+                            "`{}` differs:\n  written: {:?}\n  read:    {:?}",
+                            stringify!($field),
+                            self.$field,
+                            read.$field
+                        ));
+                    }
+                )*
+            };
+        }
+        compare!(
+            title,
+            id,
+            updated,
+            authors,
+            categories,
+            contributors,
+            generator,
+            icon,
+            links,
+            logo,
+            rights,
+            subtitle,
+            entries,
+            extensions,
+            namespaces,
+            base,
+            lang,
+            unknown_attrs,
+            stylesheets
+        );
+
+        if differences.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "feed does not round trip\n{}",
+                differences.join("\n")
+            ))
+        }
     }
 }
 
 impl FromXml for Feed {
     fn from_xml<B: BufRead>(
-        reader: &mut Reader<B>,
-        mut atts: Attributes<'_>,
+        reader: &mut XmlReader<B>,
+        atts: Attributes<'_>,
     ) -> Result<Self, Error> {
         let mut feed = Feed::default();
+        feed.read_xml(reader, atts)?;
+        Ok(feed)
+    }
+}
+
+impl Feed {
+    /// Read the contents of a `feed` element into this feed, keeping what was read if an error
+    /// occurs.
+    fn read_xml<B: BufRead>(
+        &mut self,
+        reader: &mut XmlReader<B>,
+        mut atts: Attributes<'_>,
+    ) -> Result<(), Error> {
+        let feed = self;
         let mut buf = Vec::new();
 
         for attr in atts.with_checks(false).flatten() {
             match attr.key {
-                b"xml:base" => feed.base = Some(attr.unescape_and_decode_value(reader)?),
-                b"xml:lang" => feed.lang = Some(attr.unescape_and_decode_value(reader)?),
-                b"xmlns:dc" => {}
+                b"xml:base" => feed.base = Some(reader.unescape_attr(&attr)?),
+                b"xml:lang" => feed.lang = Some(reader.unescape_attr(&attr)?),
                 attr_key if attr_key.starts_with(b"xmlns:") => {
                     let ns = str::from_utf8(&attr_key[6..])?.to_string();
-                    let ns_url = attr.unescape_and_decode_value(reader)?;
-                    feed.namespaces.insert(ns, ns_url);
+                    let ns_url = reader.unescape_attr(&attr)?;
+                    // A prefix for the Atom elements themselves is not an extension namespace.
+                    if ns_url != ATOM_NAMESPACE {
+                        feed.namespaces.insert(ns, ns_url);
+                    }
                 }
-                _ => {}
+                b"xmlns" => {}
+                _ => feed.unknown_attrs.push(unknown_attr(reader, &attr)?),
             }
         }
 
+        let mut comments = Vec::new();
+
         loop {
             match reader.read_event(&mut buf)? {
-                Event::Start(element) => match element.name() {
-                    b"title" => feed.title = Text::from_xml(reader, element.attributes())?,
-                    b"id" => feed.id = atom_text(reader)?.unwrap_or_default(),
-                    b"updated" => {
-                        feed.updated = atom_datetime(reader)?.unwrap_or_else(default_fixed_datetime)
-                    }
-                    b"author" => feed
-                        .authors
-                        .push(Person::from_xml(reader, element.attributes())?),
-                    b"category" => feed
-                        .categories
-                        .push(Category::from_xml(reader, element.attributes())?),
-                    b"contributor" => feed
-                        .contributors
-                        .push(Person::from_xml(reader, element.attributes())?),
-                    b"generator" => {
-                        feed.generator = Some(Generator::from_xml(reader, element.attributes())?)
-                    }
-                    b"icon" => feed.icon = atom_text(reader)?,
-                    b"link" => feed
-                        .links
-                        .push(Link::from_xml(reader, element.attributes())?),
-                    b"logo" => feed.logo = atom_text(reader)?,
-                    b"rights" => feed.rights = Some(Text::from_xml(reader, element.attributes())?),
-                    b"subtitle" => {
-                        feed.subtitle = Some(Text::from_xml(reader, element.attributes())?)
+                Event::Start(element) => {
+                    let element_name = reader.element_name(element.name());
+                    if *element_name != *b"entry" {
+                        comments.clear();
                     }
-                    b"entry" => feed
-                        .entries
-                        .push(Entry::from_xml(reader, element.attributes())?),
-                    n => {
-                        if let Some((ns, name)) = extension_name(element.name()) {
-                            parse_extension(
-                                reader,
-                                element.attributes(),
-                                ns,
-                                name,
-                                &mut feed.extensions,
-                            )?;
-                        } else {
-                            reader.read_to_end(n, &mut Vec::new())?;
+
+                    match &*element_name {
+                        b"title" => feed.title = Text::from_xml(reader, element.attributes())?,
+                        b"id" => feed.id = atom_text(reader)?.unwrap_or_default(),
+                        b"updated" => {
+                            feed.updated =
+                                atom_datetime(reader)?.unwrap_or_else(default_fixed_datetime)
+                        }
+                        b"author" => feed
+                            .authors
+                            .push(Person::from_xml(reader, element.attributes())?),
+                        b"category" => feed
+                            .categories
+                            .push(Category::from_xml(reader, element.attributes())?),
+                        b"contributor" => feed
+                            .contributors
+                            .push(Person::from_xml(reader, element.attributes())?),
+                        b"generator" => {
+                            feed.generator =
+                                Some(Generator::from_xml(reader, element.attributes())?)
+                        }
+                        b"icon" => feed.icon = atom_text(reader)?,
+                        b"link" => feed
+                            .links
+                            .extend(Link::read_xml(reader, element.attributes())?),
+                        b"logo" => feed.logo = atom_text(reader)?,
+                        b"rights" => {
+                            feed.rights = Some(Text::from_xml(reader, element.attributes())?)
+                        }
+                        b"subtitle" => {
+                            feed.subtitle = Some(Text::from_xml(reader, element.attributes())?)
+                        }
+                        b"entry" if reader.stop_at_entry() => {
+                            reader.set_stopped_at(element.to_owned());
+                            return Ok(());
+                        }
+                        b"entry" if reader.skip_entries() => {
+                            comments.clear();
+                            reader.read_to_end(element.name(), &mut Vec::new())?;
+                        }
+                        b"entry" if reader.options().skip_malformed_entries => {
+                            let mut element = element.to_owned();
+                            let depth = reader.depth();
+                            loop {
+                                let position = reader.buffer_position();
+                                let start = reader.last_start();
+                                match Entry::from_xml(reader, element.attributes()) {
+                                    Ok(mut entry) => {
+                                        entry.leading_comments = std::mem::take(&mut comments);
+                                        feed.entries.push(entry);
+                                        reader.push_entry_start(start);
+                                        break;
+                                    }
+                                    Err(err) if err.kind() == ErrorKind::Structure => {
+                                        reader.warn(Warning::MalformedEntry {
+                                            position,
+                                            error: err.to_string(),
+                                        });
+                                        comments.clear();
+                                        match reader.recover(depth, b"entry")? {
+                                            Some(next) => element = next,
+                                            None => return Ok(()),
+                                        }
+                                    }
+                                    Err(err) => return Err(err),
+                                }
+                            }
+                        }
+                        b"entry" => {
+                            let start = reader.last_start();
+                            let mut entry = Entry::from_xml(reader, element.attributes())?;
+                            entry.leading_comments = std::mem::take(&mut comments);
+                            feed.entries.push(entry);
+                            reader.push_entry_start(start);
+                        }
+                        _ => {
+                            if let Some((ns, name)) = extension_name(element.name()) {
+                                parse_extension(
+                                    reader,
+                                    element.attributes(),
+                                    ns,
+                                    name,
+                                    &mut feed.extensions,
+                                )?;
+                            } else {
+                                skip_unknown(reader, element.name(), "feed")?;
+                            }
                         }
                     }
-                },
+                }
+                Event::Comment(text) if reader.options().preserve_comments => {
+                    comments.push(reader.decode(&text).into_owned());
+                }
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
                 _ => {}
@@ -744,18 +3915,186 @@ impl FromXml for Feed {
             buf.clear();
         }
 
-        Ok(feed)
+        Ok(())
+    }
+
+    /// Rewrite the `href` of every link and the `src` of every content to absolute form, see
+    /// [`ReadOptions::resolve_links`].
+    #[cfg(feature = "url")]
+    fn resolve_links(&mut self, base: &url::Url) {
+        let base = rebase(base, self.base.as_deref());
+        resolve_hrefs(&mut self.links, &base);
+
+        for entry in &mut self.entries {
+            let base = rebase(&base, entry.base.as_deref());
+            resolve_hrefs(&mut entry.links, &base);
+
+            if let Some(ref mut source) = entry.source {
+                let base = rebase(&base, xml_base(&source.unknown_attrs));
+                resolve_hrefs(&mut source.links, &base);
+            }
+
+            if let Some(ref mut content) = entry.content {
+                let base = rebase(&base, content.base.as_deref());
+                if let Some(ref mut src) = content.src {
+                    resolve_href(src, &base);
+                }
+            }
+        }
     }
 }
 
-impl ToXml for Feed {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+/// Return the `xml:base` among the attributes of an element that are not otherwise recognized.
+#[cfg(feature = "url")]
+fn xml_base(attrs: &[(String, String)]) -> Option<&str> {
+    attrs
+        .iter()
+        .find(|(name, _)| name == "xml:base")
+        .map(|(_, value)| value.as_str())
+}
+
+/// Return the base URL of an element with the given `xml:base`, inside an element with `base`.
+#[cfg(feature = "url")]
+fn rebase(base: &url::Url, xml_base: Option<&str>) -> url::Url {
+    xml_base
+        .and_then(|xml_base| base.join(xml_base.trim()).ok())
+        .unwrap_or_else(|| base.clone())
+}
+
+/// Resolve the `href` of every link against `base`, honoring the `xml:base` of each link.
+#[cfg(feature = "url")]
+fn resolve_hrefs(links: &mut [Link], base: &url::Url) {
+    for link in links {
+        let base = rebase(base, xml_base(&link.unknown_attrs));
+        resolve_href(&mut link.href, &base);
+    }
+}
+
+impl Feed {
+    /// Return the `href` of the first link of this page with the given relation, resolved
+    /// against the `xml:base` of the link and the feed and against `location`, the URI the page
+    /// was retrieved from. References that cannot be resolved are returned as they are.
+    fn page_link(&self, rel: &str, location: Option<&str>) -> Option<String> {
+        let link = find_link(&self.links, rel)?;
+
+        #[cfg(feature = "url")]
+        {
+            let location = location.and_then(|location| url::Url::parse(location).ok());
+            let base = match (location, self.base.as_deref()) {
+                (Some(location), base) => Some(rebase(&location, base)),
+                (None, base) => base.and_then(|base| url::Url::parse(base.trim()).ok()),
+            };
+            let link_base = xml_base(&link.unknown_attrs);
+            let base = match base {
+                Some(base) => Some(rebase(&base, link_base)),
+                None => link_base.and_then(|base| url::Url::parse(base.trim()).ok()),
+            };
+            if let Some(base) = base {
+                let mut href = link.href.clone();
+                resolve_href(&mut href, &base);
+                return Some(href);
+            }
+        }
+        #[cfg(not(feature = "url"))]
+        let _ = location;
+
+        Some(link.href.clone())
+    }
+}
+
+/// Return the image at `url` with the given aspect, if the URI is not empty.
+fn image(url: Option<&str>, aspect: ImageAspect) -> Option<Image> {
+    let url = url.map(str::trim).filter(|url| !url.is_empty())?;
+    Some(Image {
+        url: url.to_string(),
+        aspect,
+    })
+}
+
+/// Whether the internal subset of a document type declaration declares an entity.
+fn declares_entities(doctype: &str) -> bool {
+    doctype
+        .as_bytes()
+        .windows(8)
+        .any(|window| window.eq_ignore_ascii_case(b"<!ENTITY"))
+}
+
+/// Resolve a URI reference against `base`, leaving empty and unparseable references unchanged.
+#[cfg(feature = "url")]
+fn resolve_href(href: &mut String, base: &url::Url) {
+    if href.trim().is_empty() {
+        return;
+    }
+    if let Ok(url) = base.join(href.trim()) {
+        *href = url.into();
+    }
+}
+
+impl Feed {
+    /// Write the XML declaration, naming `encoding` if given, and the stylesheet processing
+    /// instructions.
+    pub(crate) fn write_prolog<W: Write>(
+        &self,
+        writer: &mut XmlWriter<'_, W>,
+        encoding: Option<&[u8]>,
+    ) -> Result<(), XmlError> {
+        let newline = || Event::Text(BytesText::from_escaped("\n".as_bytes()));
+        let version = self.xml_version.as_deref().unwrap_or("1.0");
+        let standalone = self
+            .standalone
+            .map(|standalone| if standalone { &b"yes"[..] } else { &b"no"[..] });
+        writer.write_event(Event::Decl(BytesDecl::new(
+            version.as_bytes(),
+            encoding,
+            standalone,
+        )))?;
+        writer.write_event(newline())?;
+
+        for stylesheet in &self.stylesheets {
+            let pi = format!("xml-stylesheet {}", stylesheet);
+            writer.write_event(Event::PI(BytesText::from_escaped(pi.as_bytes())))?;
+            writer.write_event(newline())?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the opening `feed` tag and the metadata that precedes the entries.
+    pub(crate) fn write_head<W: Write>(
+        &self,
+        writer: &mut XmlWriter<'_, W>,
+    ) -> Result<(), XmlError> {
         let name = b"feed";
         let mut element = BytesStart::borrowed(name, name.len());
-        element.push_attribute(("xmlns", "http://www.w3.org/2005/Atom"));
+        match writer.options().namespace_prefix {
+            Some(prefix) => {
+                element.push_attribute((
+                    format!("xmlns:{}", prefix).as_bytes(),
+                    ATOM_NAMESPACE.as_bytes(),
+                ));
+                writer.declare_namespace(prefix, ATOM_NAMESPACE);
+            }
+            None => element.push_attribute(("xmlns", ATOM_NAMESPACE)),
+        }
 
         for (ns, uri) in &self.namespaces {
             element.push_attribute((format!("xmlns:{}", ns).as_bytes(), uri.as_bytes()));
+            writer.declare_namespace(ns, uri);
+        }
+
+        // Declare the well-known prefixes of extensions whose namespace was not given.
+        let mut prefixes = extension_prefixes(&self.extensions);
+        for entry in &self.entries {
+            prefixes.extend(extension_prefixes(&entry.extensions));
+        }
+        for prefix in prefixes {
+            if writer.is_prefix_declared(prefix) {
+                continue;
+            }
+            if let Some(uri) = well_known_namespace(prefix) {
+                element.push_attribute((format!("xmlns:{}", prefix).as_bytes(), uri.as_bytes()));
+                writer.declare_namespace(prefix, uri);
+            }
         }
 
         if let Some(ref base) = self.base {
@@ -766,9 +4105,15 @@ impl ToXml for Feed {
             element.push_attribute(("xml:lang", lang.as_str()));
         }
 
+        element.extend_attributes(
+            self.unknown_attrs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
         writer.write_event(Event::Start(element))?;
-        writer.write_object_named(&self.title, b"title")?;
         writer.write_text_element(b"id", &*self.id)?;
+        writer.write_object_named(&self.title, b"title")?;
         writer.write_text_element(b"updated", &*self.updated.to_rfc3339())?;
         writer.write_objects_named(&self.authors, "author")?;
         writer.write_objects(&self.categories)?;
@@ -778,38 +4123,60 @@ impl ToXml for Feed {
             writer.write_object(generator)?;
         }
 
-        if let Some(ref icon) = self.icon {
-            writer.write_text_element(b"icon", &**icon)?;
+        if let Some(icon) = self.icon.as_deref().filter(|icon| !icon.is_empty()) {
+            writer.write_text_element(b"icon", icon)?;
         }
 
         writer.write_objects(&self.links)?;
 
-        if let Some(ref logo) = self.logo {
-            writer.write_text_element(b"logo", &**logo)?;
+        if let Some(logo) = self.logo.as_deref().filter(|logo| !logo.is_empty()) {
+            writer.write_text_element(b"logo", logo)?;
         }
 
-        if let Some(ref rights) = self.rights {
+        if let Some(rights) = self
+            .rights
+            .as_ref()
+            .filter(|rights| !rights.value.is_empty())
+        {
             writer.write_object_named(rights, b"rights")?;
         }
 
-        if let Some(ref subtitle) = self.subtitle {
+        if let Some(subtitle) = self
+            .subtitle
+            .as_ref()
+            .filter(|subtitle| !subtitle.value.is_empty())
+        {
             writer.write_object_named(subtitle, b"subtitle")?;
         }
 
-        writer.write_objects(&self.entries)?;
+        Ok(())
+    }
 
-        for map in self.extensions.values() {
+    /// Write the extensions that follow the entries and the closing `feed` tag.
+    pub(crate) fn write_tail<W: Write>(
+        extensions: &ExtensionMap,
+        writer: &mut XmlWriter<'_, W>,
+    ) -> Result<(), XmlError> {
+        for map in extensions.values() {
             for extensions in map.values() {
                 writer.write_objects(extensions)?;
             }
         }
 
-        writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
+        writer.write_event(Event::End(BytesEnd::borrowed(b"feed")))?;
 
         Ok(())
     }
 }
 
+impl ToXml for Feed {
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError> {
+        self.write_head(writer)?;
+        writer.write_objects(&self.entries)?;
+        Feed::write_tail(&self.extensions, writer)
+    }
+}
+
 impl FromStr for Feed {
     type Err = Error;
 
@@ -818,11 +4185,28 @@ impl FromStr for Feed {
     }
 }
 
-impl ToString for Feed {
-    fn to_string(&self) -> String {
-        let buf = self.write_to(Vec::new()).unwrap_or_default();
-        // this unwrap should be safe since the bytes written from the Feed are all valid utf8
-        String::from_utf8(buf).unwrap()
+impl TryFrom<&str> for Feed {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<&[u8]> for Feed {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        Feed::read_from(bytes)
+    }
+}
+
+impl fmt::Display for Feed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let buf = self
+            .write_xml(Vec::new(), WriteOptions::default())
+            .map_err(|_| fmt::Error)?;
+        f.write_str(str::from_utf8(&buf).map_err(|_| fmt::Error)?)
     }
 }
 
@@ -846,6 +4230,10 @@ impl Default for Feed {
             namespaces: BTreeMap::default(),
             base: None,
             lang: None,
+            unknown_attrs: Vec::new(),
+            stylesheets: Vec::new(),
+            xml_version: None,
+            standalone: None,
         }
     }
 }
@@ -856,6 +4244,78 @@ impl FeedBuilder {
     pub fn build(&self) -> Feed {
         self.build_impl().unwrap()
     }
+
+    /// Set whether the feed is stamped with [`Generator::default_for_crate`] when no generator
+    /// is set.
+    ///
+    /// Enabling this has no effect if a generator, or explicitly no generator, was already set,
+    /// and a generator set afterwards replaces the default one. Disabling it removes the
+    /// default generator again. By default no generator is added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{FeedBuilder, Generator};
+    ///
+    /// let feed = FeedBuilder::default().with_default_generator(true).build();
+    /// assert_eq!(feed.generator(), Some(&Generator::default_for_crate()));
+    ///
+    /// let feed = FeedBuilder::default().build();
+    /// assert_eq!(feed.generator(), None);
+    /// ```
+    pub fn with_default_generator(&mut self, enabled: bool) -> &mut Self {
+        let default = Generator::default_for_crate();
+        if enabled && self.generator.is_none() {
+            self.generator = Some(Some(default));
+        } else if !enabled && self.generator.as_ref() == Some(&Some(default)) {
+            self.generator = None;
+        }
+        self
+    }
+
+    /// Set the entries of the feed from any iterator of entries.
+    pub fn entries<I>(&mut self, entries: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Entry>,
+    {
+        self.entries = Some(entries.into_iter().collect());
+        self
+    }
+
+    /// Add an entry to the feed.
+    pub fn entry<V>(&mut self, entry: V) -> &mut Self
+    where
+        V: Into<Entry>,
+    {
+        self.entries.get_or_insert_with(Vec::new).push(entry.into());
+        self
+    }
+
+    /// Set the entries of the feed to the entries built by the given builders.
+    ///
+    /// Building an entry cannot fail, so every builder produces an entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{EntryBuilder, FeedBuilder};
+    ///
+    /// let titles = vec!["First", "Second"];
+    /// let feed = FeedBuilder::default()
+    ///     .entries_from_builders(titles.into_iter().map(|title| {
+    ///         let mut builder = EntryBuilder::default();
+    ///         builder.title(title);
+    ///         builder
+    ///     }))
+    ///     .build();
+    /// assert_eq!(feed.entries()[1].title(), "Second");
+    /// ```
+    pub fn entries_from_builders<I>(&mut self, builders: I) -> &mut Self
+    where
+        I: IntoIterator<Item = EntryBuilder>,
+    {
+        self.entries(builders.into_iter().map(|builder| builder.build()))
+    }
 }
 
 #[cfg(test)]
@@ -866,7 +4326,7 @@ mod test {
     fn test_default() {
         let feed = Feed::default();
         let xml_fragment = r#"<?xml version="1.0"?>
-<feed xmlns="http://www.w3.org/2005/Atom"><title></title><id></id><updated>1970-01-01T00:00:00+00:00</updated></feed>"#;
+<feed xmlns="http://www.w3.org/2005/Atom"><id></id><title></title><updated>1970-01-01T00:00:00+00:00</updated></feed>"#;
         assert_eq!(feed.to_string(), xml_fragment);
         let loaded_feed = Feed::read_from(xml_fragment.as_bytes()).unwrap();
         assert_eq!(loaded_feed, feed);
@@ -880,7 +4340,7 @@ mod test {
         feed.set_base(Some("http://example.com/blog/".into()));
         feed.set_lang(Some("fr_FR".into()));
         let xml_fragment = r#"<?xml version="1.0"?>
-<feed xmlns="http://www.w3.org/2005/Atom" xml:base="http://example.com/blog/" xml:lang="fr_FR"><title></title><id></id><updated>1970-01-01T00:00:00+00:00</updated></feed>"#;
+<feed xmlns="http://www.w3.org/2005/Atom" xml:base="http://example.com/blog/" xml:lang="fr_FR"><id></id><title></title><updated>1970-01-01T00:00:00+00:00</updated></feed>"#;
         assert_eq!(feed.to_string(), xml_fragment);
         let loaded_feed = Feed::read_from(xml_fragment.as_bytes()).unwrap();
         assert_eq!(loaded_feed, feed);
@@ -1,10 +1,441 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::io::BufRead;
+use std::ops::{Deref, DerefMut};
+use std::time::Instant;
 
-use quick_xml::events::attributes::Attributes;
-use quick_xml::Reader;
+use quick_xml::events::attributes::{Attribute, Attributes};
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::{Error as XmlError, Reader};
 
+use crate::entities::html_entities;
 use crate::error::Error;
+use crate::options::ReadOptions;
+use crate::warning::Warning;
 
 pub trait FromXml: Sized {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes<'_>) -> Result<Self, Error>;
+    fn from_xml<R: BufRead>(reader: &mut XmlReader<R>, atts: Attributes<'_>)
+        -> Result<Self, Error>;
+}
+
+/// The namespace of the Atom elements.
+pub(crate) const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+
+/// The number of events read between checks of the `max_duration` deadline.
+const TIMEOUT_CHECK_INTERVAL: u64 = 1024;
+
+/// A `Reader` that carries the `ReadOptions` the elements read through it are parsed with.
+pub struct XmlReader<B: BufRead> {
+    reader: Reader<B>,
+    options: ReadOptions,
+    depth: usize,
+    warnings: Vec<Warning>,
+    skipped_elements: usize,
+    events: u64,
+    deadline: Option<Instant>,
+    entities: HashMap<Vec<u8>, Vec<u8>>,
+    /// The namespace declarations in scope, with the depth of the element declaring them.
+    namespaces: Vec<(usize, Vec<u8>, Vec<u8>)>,
+    /// Whether entries are skipped without being parsed.
+    skip_entries: bool,
+    /// The number of text chunks that could be used as they appear in the input.
+    borrowed_texts: usize,
+    /// The number of text chunks that had to be unescaped.
+    unescaped_texts: usize,
+    /// The encoding named in the XML declaration, if any.
+    declared_encoding: Option<String>,
+    /// Whether reading the feed stops at the start tag of its first entry.
+    stop_at_entry: bool,
+    /// The start tag of the entry reading stopped at.
+    stopped_at: Option<BytesStart<'static>>,
+    /// The byte offset of the `<` of the last start tag read.
+    last_start: usize,
+    /// The byte offsets of the start tags of the entries read, if they are recorded.
+    entry_starts: Option<Vec<usize>>,
+}
+
+impl<B: BufRead> XmlReader<B> {
+    pub fn options(&self) -> &ReadOptions {
+        &self.options
+    }
+
+    /// Consume the reader and return the underlying reader.
+    pub fn into_inner(self) -> B {
+        self.reader.into_underlying_reader()
+    }
+
+    /// Return whether entries are skipped without being parsed.
+    pub fn skip_entries(&self) -> bool {
+        self.skip_entries
+    }
+
+    /// Set whether entries are skipped without being parsed.
+    pub fn set_skip_entries(&mut self, skip_entries: bool) {
+        self.skip_entries = skip_entries;
+    }
+
+    /// Return the encoding named in the XML declaration, if one was read.
+    pub fn declared_encoding(&self) -> Option<&str> {
+        self.declared_encoding.as_deref()
+    }
+
+    /// Set the encoding named in the XML declaration.
+    pub fn set_declared_encoding(&mut self, encoding: Option<String>) {
+        self.declared_encoding = encoding;
+    }
+
+    /// Return whether reading the feed stops at the start tag of its first entry.
+    pub fn stop_at_entry(&self) -> bool {
+        self.stop_at_entry
+    }
+
+    /// Set whether reading the feed stops at the start tag of its first entry.
+    pub fn set_stop_at_entry(&mut self, stop_at_entry: bool) {
+        self.stop_at_entry = stop_at_entry;
+    }
+
+    /// Record the start tag of the entry reading stopped at.
+    pub fn set_stopped_at(&mut self, element: BytesStart<'static>) {
+        self.stopped_at = Some(element);
+    }
+
+    /// Take the start tag of the entry reading stopped at, if it stopped at one.
+    pub fn take_stopped_at(&mut self) -> Option<BytesStart<'static>> {
+        self.stopped_at.take()
+    }
+
+    /// Return the byte offset of the `<` of the last start tag read.
+    pub fn last_start(&self) -> usize {
+        self.last_start
+    }
+
+    /// Start recording the byte offsets of the start tags of the entries read.
+    pub fn record_entry_starts(&mut self) {
+        self.entry_starts = Some(Vec::new());
+    }
+
+    /// Record the byte offset of the start tag of an entry that was read, if offsets are
+    /// recorded.
+    pub fn push_entry_start(&mut self, start: usize) {
+        if let Some(ref mut entry_starts) = self.entry_starts {
+            entry_starts.push(start);
+        }
+    }
+
+    /// Take the byte offsets of the start tags of the entries read.
+    pub fn take_entry_starts(&mut self) -> Vec<usize> {
+        self.entry_starts.take().unwrap_or_default()
+    }
+
+    /// Skip an element that is not recognized, whose start was the last event read.
+    pub fn skip_element(&mut self, name: &[u8]) -> Result<(), Error> {
+        self.skipped_elements += 1;
+        if let Some(ref handler) = self.options.unknown_element_handler {
+            let (prefix, local_name) = split_name(name);
+            let namespace = self.resolve_prefix(prefix).unwrap_or(b"");
+            handler.call(
+                &self.reader.decode(namespace),
+                &self.reader.decode(local_name),
+            );
+        }
+        self.read_to_end(name, &mut Vec::new())
+    }
+
+    /// Return the nesting depth of the current element.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Recover from an error inside an element at `depth` by skipping ahead to the start of the
+    /// next sibling named `sibling`, which is returned, or to the end of the parent element, in
+    /// which case `None` is returned.
+    pub fn recover(
+        &mut self,
+        depth: usize,
+        sibling: &[u8],
+    ) -> Result<Option<BytesStart<'static>>, Error> {
+        self.reader.expand_empty_elements(true);
+        let mut buf = Vec::new();
+
+        loop {
+            buf.clear();
+            let position = self.reader.buffer_position();
+            match self.next_event(&mut buf)? {
+                Event::Start(element) => {
+                    self.depth += 1;
+                    if self.depth == depth && *self.element_name(element.name()) == *sibling {
+                        self.last_start = position;
+                        self.depth -= 1;
+                        self.drop_namespaces();
+                        self.depth += 1;
+                        self.declare_namespaces(&element)?;
+                        return Ok(Some(element.into_owned()));
+                    }
+                }
+                Event::End(_) => {
+                    self.end_element();
+                    if self.depth < depth - 1 {
+                        return Ok(None);
+                    }
+                }
+                Event::Eof => return Err(Error::Eof),
+                _ => {}
+            }
+        }
+    }
+
+    /// Return the number of elements skipped because they were not recognized.
+    pub fn skipped_elements(&self) -> usize {
+        self.skipped_elements
+    }
+
+    /// Unescape and decode the value of an attribute.
+    ///
+    /// Values without entity references, such as most URLs, are only decoded, which skips the
+    /// unescaping pass and the second UTF-8 check done by quick-xml.
+    pub fn unescape_attr(&self, attr: &Attribute<'_>) -> Result<String, Error> {
+        if !attr.value.contains(&b'&') {
+            return Ok(self.reader.decode(&attr.value).into_owned());
+        }
+        Ok(attr.unescape_and_decode_value_with_custom_entities(&self.reader, &self.entities)?)
+    }
+
+    /// Unescape and decode a text event, or only decode it when reading raw text.
+    ///
+    /// Text without references is borrowed from the event when it is valid UTF-8.
+    pub fn unescape_text<'t>(&mut self, text: &'t BytesText<'_>) -> Result<Cow<'t, str>, Error> {
+        if self.options.raw_text || !text.escaped().contains(&b'&') {
+            self.borrowed_texts += 1;
+            return Ok(self.reader.decode(text.escaped()));
+        }
+        self.unescaped_texts += 1;
+        let text = text.unescape_and_decode_with_custom_entities(&self.reader, &self.entities)?;
+        Ok(Cow::Owned(text))
+    }
+
+    /// Return the number of text chunks read that needed no unescaping, so they could have
+    /// been borrowed from the input, and the number that had to be unescaped.
+    pub fn text_counts(&self) -> (usize, usize) {
+        (self.borrowed_texts, self.unescaped_texts)
+    }
+
+    /// Unescape and decode the name and attributes of a start tag, or only decode them when
+    /// reading raw text.
+    pub fn unescape_start(&self, start: &BytesStart<'_>) -> Result<String, Error> {
+        if self.options.raw_text {
+            return Ok(self.reader.decode(start).into_owned());
+        }
+        Ok(start.unescape_and_decode_with_custom_entities(&self.reader, &self.entities)?)
+    }
+
+    /// Record a problem in the input that was recovered from.
+    pub fn warn(&mut self, warning: Warning) {
+        self.warnings.push(warning);
+    }
+
+    /// Return the warnings recorded while reading.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Consume the reader and return the warnings recorded while reading.
+    pub fn into_warnings(self) -> Vec<Warning> {
+        self.warnings
+    }
+
+    /// Return the namespace bound to a prefix, or the default namespace for an empty prefix,
+    /// in the element that was last started.
+    pub fn resolve_prefix(&self, prefix: &[u8]) -> Option<&[u8]> {
+        self.namespaces
+            .iter()
+            .rev()
+            .find(|(_, declared, _)| declared.as_slice() == prefix)
+            .map(|(_, _, namespace)| namespace.as_slice())
+    }
+
+    /// Return the name an element is matched by, lowercased if elements are matched
+    /// case-insensitively and the name has no prefix.
+    ///
+    /// The prefix of an element in the Atom namespace is removed, so `<a:title>` is matched as
+    /// `title` when `a` is bound to the Atom namespace.
+    pub fn element_name<'a>(&self, name: &'a [u8]) -> Cow<'a, [u8]> {
+        let name = match split_name(name) {
+            (b"", _) => name,
+            (prefix, local_name)
+                if self.resolve_prefix(prefix) == Some(ATOM_NAMESPACE.as_bytes()) =>
+            {
+                local_name
+            }
+            _ => return Cow::Borrowed(name),
+        };
+
+        if self.options.case_insensitive_elements {
+            Cow::Owned(name.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(name)
+        }
+    }
+
+    /// Read the next event from the underlying reader, failing once `max_duration` is exceeded.
+    fn next_event<'b>(&mut self, buf: &'b mut Vec<u8>) -> Result<Event<'b>, Error> {
+        self.events += 1;
+        if let Some(deadline) = self.deadline {
+            if self.events % TIMEOUT_CHECK_INTERVAL == 0 && Instant::now() > deadline {
+                return Err(Error::Timeout);
+            }
+        }
+
+        Ok(self.reader.read_event(buf)?)
+    }
+
+    /// Read the next event, failing once elements are nested deeper than `max_depth`.
+    pub fn read_event<'b>(&mut self, buf: &'b mut Vec<u8>) -> Result<Event<'b>, Error> {
+        let position = self.reader.buffer_position();
+        let event = self.next_event(buf)?;
+        match event {
+            Event::Start(ref element) => {
+                self.last_start = position;
+                self.depth += 1;
+                if self.depth > self.options.max_depth {
+                    return Err(Error::MaxDepthExceeded);
+                }
+                self.declare_namespaces(element)?;
+            }
+            Event::End(_) => self.end_element(),
+            _ => {}
+        }
+
+        Ok(event)
+    }
+
+    /// Skip to the end of the element whose start was the last event read.
+    ///
+    /// The end is found by nesting depth, so it is found whatever the prefix of the element.
+    /// `end` is only used to describe the missing end tag.
+    pub fn read_to_end<K: AsRef<[u8]>>(&mut self, end: K, buf: &mut Vec<u8>) -> Result<(), Error> {
+        let end = end.as_ref();
+        let mut depth = 0;
+        loop {
+            match self.next_event(buf)? {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                }
+                Event::Eof => {
+                    let end = String::from_utf8_lossy(end);
+                    return Err(XmlError::UnexpectedEof(format!("</{:?}>", end)).into());
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        self.end_element();
+        Ok(())
+    }
+
+    /// Record the namespaces declared by an element that was just started.
+    fn declare_namespaces(&mut self, element: &BytesStart<'_>) -> Result<(), Error> {
+        let xmlns = b"xmlns";
+        if !element
+            .attributes_raw()
+            .windows(xmlns.len())
+            .any(|w| w == xmlns)
+        {
+            return Ok(());
+        }
+
+        for attr in element.attributes().with_checks(false) {
+            let attr = attr?;
+            let prefix = if attr.key == b"xmlns" {
+                &b""[..]
+            } else if attr.key.starts_with(b"xmlns:") {
+                &attr.key[b"xmlns:".len()..]
+            } else {
+                continue;
+            };
+            let namespace = attr.unescaped_value()?.into_owned();
+            self.namespaces
+                .push((self.depth, prefix.to_vec(), namespace));
+        }
+        Ok(())
+    }
+
+    /// Leave the current element, dropping the namespaces it declared.
+    fn end_element(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+        self.drop_namespaces();
+    }
+
+    /// Drop the namespaces declared by elements deeper than the current depth.
+    fn drop_namespaces(&mut self) {
+        while self
+            .namespaces
+            .last()
+            .map_or(false, |(depth, _, _)| *depth > self.depth)
+        {
+            self.namespaces.pop();
+        }
+    }
+}
+
+impl<B: BufRead> Deref for XmlReader<B> {
+    type Target = Reader<B>;
+
+    fn deref(&self) -> &Reader<B> {
+        &self.reader
+    }
+}
+
+impl<B: BufRead> DerefMut for XmlReader<B> {
+    fn deref_mut(&mut self) -> &mut Reader<B> {
+        &mut self.reader
+    }
+}
+
+/// Split a qualified name into its prefix, which is empty if it has none, and its local name.
+pub(crate) fn split_name(name: &[u8]) -> (&[u8], &[u8]) {
+    match name.iter().position(|&b| b == b':') {
+        Some(index) => (&name[..index], &name[index + 1..]),
+        None => (&b""[..], name),
+    }
+}
+
+/// Create the XML reader used by every parsing entry point.
+///
+/// Empty elements are always expanded into a start and an end event, so `<content src="x"/>`
+/// and `<content src="x"></content>` are parsed identically.
+pub fn xml_reader<B: BufRead>(reader: B, options: ReadOptions) -> XmlReader<B> {
+    let mut reader = Reader::from_reader(reader);
+    reader.expand_empty_elements(true);
+    let deadline = options
+        .max_duration
+        .and_then(|duration| Instant::now().checked_add(duration));
+    let entities = if options.html_entities {
+        html_entities()
+    } else {
+        HashMap::new()
+    };
+    XmlReader {
+        reader,
+        options,
+        depth: 0,
+        warnings: Vec::new(),
+        skipped_elements: 0,
+        events: 0,
+        deadline,
+        entities,
+        namespaces: Vec::new(),
+        skip_entries: false,
+        borrowed_texts: 0,
+        unescaped_texts: 0,
+        declared_encoding: None,
+        stop_at_entry: false,
+        stopped_at: None,
+        last_start: 0,
+        entry_starts: None,
+    }
 }
@@ -3,17 +3,15 @@ use std::io::{BufRead, Write};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Reader;
-use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
-use crate::util::atom_text;
+use crate::fromxml::{FromXml, XmlReader};
+use crate::toxml::{ToXml, XmlWriter};
+use crate::util::{atom_text, unknown_attr};
 
 /// Represents the generator of an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -30,9 +28,32 @@ pub struct Generator {
     pub uri: Option<String>,
     /// The generator version.
     pub version: Option<String>,
+    /// Attributes that are not otherwise recognized, in the order they appeared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown_attrs: Vec<(String, String)>,
 }
 
 impl Generator {
+    /// Return a generator identifying this crate, with its repository URI and version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let generator = Generator::default_for_crate();
+    /// assert_eq!(generator.value(), "atom_syndication");
+    /// assert_eq!(generator.version(), Some(env!("CARGO_PKG_VERSION")));
+    /// ```
+    pub fn default_for_crate() -> Self {
+        Generator {
+            value: env!("CARGO_PKG_NAME").into(),
+            uri: Some(env!("CARGO_PKG_REPOSITORY").into()),
+            version: Some(env!("CARGO_PKG_VERSION").into()),
+            unknown_attrs: Vec::new(),
+        }
+    }
+
     /// Return the name of the generator.
     ///
     /// # Examples
@@ -129,20 +150,53 @@ impl Generator {
     {
         self.version = version.into()
     }
+
+    /// Return the attributes of this generator that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut generator = Generator::default();
+    /// generator.set_unknown_attrs(vec![("v:build".to_string(), "42".to_string())]);
+    /// assert_eq!(generator.unknown_attrs(), &[("v:build".to_string(), "42".to_string())]);
+    /// ```
+    pub fn unknown_attrs(&self) -> &[(String, String)] {
+        &self.unknown_attrs
+    }
+
+    /// Set the attributes of this generator that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut generator = Generator::default();
+    /// generator.set_unknown_attrs(vec![("v:build".to_string(), "42".to_string())]);
+    /// ```
+    pub fn set_unknown_attrs<V>(&mut self, unknown_attrs: V)
+    where
+        V: Into<Vec<(String, String)>>,
+    {
+        self.unknown_attrs = unknown_attrs.into();
+    }
 }
 
 impl FromXml for Generator {
     fn from_xml<B: BufRead>(
-        reader: &mut Reader<B>,
+        reader: &mut XmlReader<B>,
         mut atts: Attributes<'_>,
     ) -> Result<Self, Error> {
         let mut generator = Generator::default();
 
         for att in atts.with_checks(false).flatten() {
             match att.key {
-                b"uri" => generator.uri = Some(att.unescape_and_decode_value(reader)?),
-                b"version" => generator.version = Some(att.unescape_and_decode_value(reader)?),
-                _ => {}
+                b"uri" => generator.uri = Some(reader.unescape_attr(&att)?),
+                b"version" => generator.version = Some(reader.unescape_attr(&att)?),
+                b"xmlns" => {}
+                _ => generator.unknown_attrs.push(unknown_attr(reader, &att)?),
             }
         }
 
@@ -153,7 +207,7 @@ impl FromXml for Generator {
 }
 
 impl ToXml for Generator {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError> {
         let name = b"generator";
         let mut element = BytesStart::borrowed(name, name.len());
 
@@ -165,6 +219,12 @@ impl ToXml for Generator {
             element.push_attribute(("version", &**version));
         }
 
+        element.extend_attributes(
+            self.unknown_attrs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
         writer.write_event(Event::Start(element))?;
         writer.write_event(Event::Text(BytesText::from_escaped(self.value.as_bytes())))?;
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
@@ -0,0 +1,507 @@
+//! Lightweight helpers for scanning HTML found in and around feeds.
+//!
+//! These are deliberately forgiving scanners rather than a full HTML parser: they only look
+//! for start tags and their attributes.
+
+/// A start tag found in an HTML document.
+pub(crate) struct Tag<'a> {
+    attrs: Vec<(&'a str, String)>,
+}
+
+impl<'a> Tag<'a> {
+    /// Return the decoded value of the attribute, matching its name case-insensitively.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Decode the character references that commonly appear in HTML attribute values.
+pub(crate) fn decode_entities(value: &str) -> String {
+    if !value.contains('&') {
+        return value.to_string();
+    }
+
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = match rest.find(';') {
+            Some(end) if end <= 10 => end,
+            _ => {
+                result.push('&');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+
+        let decoded = match &rest[1..end] {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            reference if reference.starts_with("#x") || reference.starts_with("#X") => {
+                u32::from_str_radix(&reference[2..], 16)
+                    .ok()
+                    .and_then(std::char::from_u32)
+            }
+            reference if reference.starts_with('#') => reference[1..]
+                .parse::<u32>()
+                .ok()
+                .and_then(std::char::from_u32),
+            _ => None,
+        };
+
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == ':' || c == '_'
+}
+
+/// Parse the attributes of a start tag, returning them and the length of input consumed up to
+/// and including the closing `>`.
+fn parse_attrs(input: &str) -> (Vec<(&str, String)>, usize) {
+    let mut attrs = Vec::new();
+    let mut pos = 0;
+    let bytes = input.as_bytes();
+
+    loop {
+        while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b'/') {
+            pos += 1;
+        }
+
+        if pos >= bytes.len() {
+            return (attrs, pos);
+        }
+
+        if bytes[pos] == b'>' {
+            return (attrs, pos + 1);
+        }
+
+        let name_start = pos;
+        while pos < bytes.len()
+            && !bytes[pos].is_ascii_whitespace()
+            && !b"=>/".contains(&bytes[pos])
+        {
+            pos += 1;
+        }
+        let name = &input[name_start..pos];
+
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+
+        if pos >= bytes.len() || bytes[pos] != b'=' {
+            if !name.is_empty() {
+                attrs.push((name, String::new()));
+            } else {
+                pos += 1;
+            }
+            continue;
+        }
+
+        pos += 1;
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+
+        let value = match bytes.get(pos) {
+            Some(&quote) if quote == b'"' || quote == b'\'' => {
+                let value_start = pos + 1;
+                let value_end = input[value_start..]
+                    .find(quote as char)
+                    .map_or(input.len(), |end| value_start + end);
+                pos = (value_end + 1).min(input.len());
+                &input[value_start..value_end]
+            }
+            _ => {
+                let value_start = pos;
+                while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() && bytes[pos] != b'>' {
+                    pos += 1;
+                }
+                &input[value_start..pos]
+            }
+        };
+
+        attrs.push((name, decode_entities(value)));
+    }
+}
+
+/// Elements that are rendered inline, so removing them must not separate adjacent words.
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "cite", "code", "data", "dfn", "em", "i", "kbd", "mark", "q",
+    "s", "samp", "small", "span", "strong", "sub", "sup", "time", "u", "var",
+];
+
+/// Remove the markup from an HTML fragment, returning its decoded text.
+///
+/// Block level elements are replaced with a space so the words on either side stay apart, and
+/// the contents of `<script>` and `<style>` elements are dropped entirely.
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        if rest.starts_with("!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+
+        let name = rest.trim_start_matches('/');
+        let name = &name[..name.find(|c| !is_name_char(c)).unwrap_or(name.len())];
+        rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+
+        if name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style") {
+            let closing = format!("</{}", name.to_ascii_lowercase());
+            let end = rest.to_ascii_lowercase().find(&closing);
+            rest = end.map_or("", |end| {
+                let rest = &rest[end..];
+                rest.find('>').map_or("", |end| &rest[end + 1..])
+            });
+        }
+
+        if !INLINE_ELEMENTS
+            .iter()
+            .any(|inline| name.eq_ignore_ascii_case(inline))
+        {
+            text.push(' ');
+        }
+    }
+
+    text.push_str(rest);
+    decode_entities(&text)
+}
+
+/// Return the markup inside the `div` element that wraps XHTML content, or the markup unchanged
+/// if it is not a single `div` element.
+///
+/// The `div` is matched by its local name, so both `<div xmlns="...">` and a prefixed
+/// `<xhtml:div>` are unwrapped. Only the outermost element is removed.
+pub(crate) fn unwrap_xhtml_div(xhtml: &str) -> &str {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let trimmed = xhtml.trim();
+    let mut reader = Reader::from_str(trimmed);
+    reader.check_end_names(false);
+    let mut buf = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    loop {
+        let position = reader.buffer_position();
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(element)) => {
+                if depth == 0 {
+                    if position != 0 || element.local_name() != b"div" {
+                        return xhtml;
+                    }
+                    start = reader.buffer_position();
+                }
+                depth += 1;
+            }
+            Ok(Event::End(_)) => {
+                depth -= 1;
+                if depth == 0 {
+                    if reader.buffer_position() != trimmed.len() {
+                        return xhtml;
+                    }
+                    return &trimmed[start..position];
+                }
+            }
+            Ok(Event::Eof) | Err(_) => return xhtml,
+            Ok(Event::Text(text)) if depth == 0 && text.is_empty() => {}
+            Ok(_) if depth == 0 => return xhtml,
+            Ok(_) => {}
+        }
+        buf.clear();
+    }
+}
+
+/// Render plain text as an HTML fragment, escaping the characters that are special in HTML and
+/// turning each line break into a `<br>`.
+pub(crate) fn text_to_html(text: &str) -> String {
+    let mut html = String::with_capacity(text.len());
+    let mut lines = text.split('\n').peekable();
+
+    while let Some(line) = lines.next() {
+        let line = if line.ends_with('\r') {
+            &line[..line.len() - 1]
+        } else {
+            line
+        };
+        for c in line.chars() {
+            match c {
+                '&' => html.push_str("&amp;"),
+                '<' => html.push_str("&lt;"),
+                '>' => html.push_str("&gt;"),
+                '"' => html.push_str("&quot;"),
+                c => html.push(c),
+            }
+        }
+        if lines.peek().is_some() {
+            html.push_str("<br>");
+        }
+    }
+
+    html
+}
+
+/// Collapse the whitespace of plain text and shorten it to at most `max_chars` characters,
+/// ending on a word boundary with an ellipsis when anything was cut.
+///
+/// The ellipsis counts towards the limit. A first word too long to fit is cut mid-word.
+pub(crate) fn truncate_words(text: &str, max_chars: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= max_chars {
+        return text;
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+
+    let end = text
+        .char_indices()
+        .nth(max_chars - 1)
+        .map_or(text.len(), |(index, _)| index);
+    let mut cut = &text[..end];
+    if !text[end..].starts_with(' ') {
+        if let Some(space) = cut.rfind(' ') {
+            cut = &cut[..space];
+        }
+    }
+
+    format!("{}\u{2026}", cut.trim_end())
+}
+
+/// Collapse every run of whitespace in an HTML fragment to a single space.
+///
+/// Tags, comments and the contents of `<pre>` elements are copied unchanged.
+#[cfg(feature = "html")]
+pub(crate) fn collapse_whitespace(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    let mut pre_depth = 0usize;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '<' {
+            let end = if rest.starts_with("<!--") {
+                rest.find("-->").map_or(rest.len(), |end| end + 3)
+            } else {
+                rest.find('>').map_or(rest.len(), |end| end + 1)
+            };
+            let tag = &rest[..end];
+            let name = tag[1..].trim_start_matches('/');
+            let name = &name[..name.find(|c| !is_name_char(c)).unwrap_or(name.len())];
+
+            if name.eq_ignore_ascii_case("pre") {
+                if tag.starts_with("</") {
+                    pre_depth = pre_depth.saturating_sub(1);
+                } else if !tag.ends_with("/>") {
+                    pre_depth += 1;
+                }
+            }
+
+            result.push_str(tag);
+            rest = &rest[end..];
+        } else if c.is_ascii_whitespace() && pre_depth == 0 {
+            result.push(' ');
+            rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        } else {
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    result
+}
+
+/// Return every start tag with the given name, matching it case-insensitively.
+pub(crate) fn find_tags<'a>(html: &'a str, name: &'a str) -> Vec<Tag<'a>> {
+    let mut tags = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+
+        if rest.starts_with("!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+
+        let name_len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+        let tag_name = &rest[..name_len];
+        rest = &rest[name_len..];
+
+        if tag_name.eq_ignore_ascii_case(name) {
+            let (attrs, consumed) = parse_attrs(rest);
+            rest = &rest[consumed..];
+            tags.push(Tag { attrs });
+        }
+    }
+
+    tags
+}
+
+/// Find the feeds advertised by an HTML page.
+///
+/// Every `<link rel="alternate">` whose `type` is `application/atom+xml` or
+/// `application/rss+xml` is returned, in document order and without duplicates, with its
+/// `href` resolved against `base_url`. Links that cannot be resolved are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::discover_feeds;
+///
+/// let html = r#"<html><head>
+///     <link rel="alternate" type="application/atom+xml" href="/feed.atom">
+///     <link rel="stylesheet" href="/style.css">
+/// </head></html>"#;
+/// assert_eq!(
+///     discover_feeds(html, "https://example.com/blog/"),
+///     vec!["https://example.com/feed.atom".to_string()]
+/// );
+/// ```
+#[cfg(feature = "url")]
+pub fn discover_feeds(html: &str, base_url: &str) -> Vec<String> {
+    const FEED_TYPES: &[&str] = &["application/atom+xml", "application/rss+xml"];
+
+    let base = url::Url::parse(base_url).ok();
+    let mut feeds = Vec::new();
+
+    for tag in find_tags(html, "link") {
+        let is_alternate = tag
+            .attr("rel")
+            .into_iter()
+            .flat_map(str::split_whitespace)
+            .any(|token| token.eq_ignore_ascii_case("alternate"));
+        let is_feed = tag.attr("type").into_iter().any(|mime_type| {
+            FEED_TYPES
+                .iter()
+                .any(|feed_type| mime_type.trim().eq_ignore_ascii_case(feed_type))
+        });
+        let href = match tag.attr("href") {
+            Some(href) if is_alternate && is_feed && !href.trim().is_empty() => href.trim(),
+            _ => continue,
+        };
+
+        let resolved = match base {
+            Some(ref base) => base.join(href),
+            None => url::Url::parse(href),
+        };
+
+        if let Ok(url) = resolved {
+            let url = String::from(url);
+            if !feeds.contains(&url) {
+                feeds.push(url);
+            }
+        }
+    }
+
+    feeds
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find_tags() {
+        let html = r#"<p>text <A HREF='/one' title="a &amp; b">one</A><!-- <a href="/no"> -->
+            <a href=/two class=x>two</a><abbr title="x">y</abbr></p>"#;
+        let tags = find_tags(html, "a");
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].attr("href"), Some("/one"));
+        assert_eq!(tags[0].attr("title"), Some("a & b"));
+        assert_eq!(tags[1].attr("href"), Some("/two"));
+        assert_eq!(tags[1].attr("class"), Some("x"));
+    }
+
+    #[test]
+    fn test_strip_tags() {
+        let html = r#"<p>Hello <b>wor</b>ld</p><p>again&amp;again</p>
+            <script>var x = "<p>";</script><STYLE>p { }</STYLE><!-- note -->done"#;
+        assert_eq!(
+            strip_tags(html).split_whitespace().collect::<Vec<_>>(),
+            vec!["Hello", "world", "again&again", "done"]
+        );
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn test_collapse_whitespace() {
+        let html = "<p>\n  Some   <b  class=\"x\">text</b>\t\n</p>\n<pre>  keep\n   this </pre> <!--  a   b  --> end  ";
+        assert_eq!(
+            collapse_whitespace(html),
+            "<p> Some <b  class=\"x\">text</b> </p> <pre>  keep\n   this </pre> <!--  a   b  --> end "
+        );
+    }
+
+    #[test]
+    fn test_unwrap_xhtml_div() {
+        let xhtml = r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>A</p></div>"#;
+        assert_eq!(unwrap_xhtml_div(xhtml), "<p>A</p>");
+
+        let xhtml = r#" <xhtml:div xmlns:xhtml="http://www.w3.org/1999/xhtml">
+            <xhtml:div>Nested</xhtml:div> &amp; more
+        </xhtml:div> "#;
+        assert_eq!(
+            unwrap_xhtml_div(xhtml).trim(),
+            "<xhtml:div>Nested</xhtml:div> &amp; more"
+        );
+
+        assert_eq!(unwrap_xhtml_div("<div/>"), "<div/>");
+        assert_eq!(unwrap_xhtml_div("<div></div>"), "");
+        assert_eq!(
+            unwrap_xhtml_div("<div>A</div><div>B</div>"),
+            "<div>A</div><div>B</div>"
+        );
+        assert_eq!(unwrap_xhtml_div("Text <div>A</div>"), "Text <div>A</div>");
+        assert_eq!(unwrap_xhtml_div("<p>A</p>"), "<p>A</p>");
+        assert_eq!(unwrap_xhtml_div("<div>A"), "<div>A");
+    }
+
+    #[test]
+    fn test_truncate_words() {
+        assert_eq!(truncate_words(" Short\n text ", 10), "Short text");
+        assert_eq!(truncate_words("One two three", 9), "One two\u{2026}");
+        assert_eq!(truncate_words("One two three", 8), "One two\u{2026}");
+        assert_eq!(truncate_words("One two three", 7), "One\u{2026}");
+        assert_eq!(truncate_words("Überraschung ist schön", 5), "Über\u{2026}");
+        assert_eq!(truncate_words("日本語のテキスト です", 4), "日本語\u{2026}");
+        assert_eq!(truncate_words("abc", 0), "");
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &amp; b &#60; &#x3E; &bogus; &"),
+            "a & b < > &bogus; &"
+        );
+    }
+}
@@ -20,6 +20,15 @@
 //! let feed = string.parse::<Feed>().unwrap();
 //! ```
 //!
+//! Reading never panics on malformed or malicious input: every problem is reported as an
+//! [`Error`]. This is checked by the truncated and corrupted inputs in the test suite and by
+//! the `read_feed` target in the `fuzz` directory, which can be run with
+//! `cargo fuzz run read_feed`.
+//!
+//! Entities declared in a document type declaration are never expanded, so reading cannot be
+//! used to disclose local files or make requests through external entities. Such declarations
+//! are rejected outright unless [`ReadOptions::allow_entity_declarations`] is set.
+//!
 //! # Writing
 //!
 //! A feed can be written to any object that implements the `Write` trait or converted to an XML
@@ -27,6 +36,20 @@
 //!
 //! **Note**: Writing a feed does not perform any escaping of XML entities.
 //!
+//! Child elements are always written in the same order, independent of how the feed was built
+//! or parsed, so writing the same feed twice produces identical bytes. Feeds and sources write
+//! `id`, `title`, `updated`, `author`, `category`, `contributor`, `generator`, `icon`, `link`,
+//! `logo`, `rights` and `subtitle`, followed by the entries of a feed. Entries write their
+//! preserved comments, then `id`, `title`, `updated`, `published`, `author`, `category`,
+//! `contributor`, `link`, `rights`, `source`, `summary` and `content`. Extension elements are
+//! written last, sorted by prefix and name. Repeated elements, such as several `link` elements
+//! with the same `rel`, keep the order they were read or set in.
+//!
+//! Optional elements whose value is empty are treated like unset ones: an `icon`, `logo`,
+//! `rights`, `subtitle` or `summary` set to an empty string is not written, so clearing a field
+//! with `Some("")` instead of `None` never produces an empty element such as
+//! `<subtitle></subtitle>`. Likewise, an empty `scheme` or `label` of a category is not written.
+//!
 //! ## Example
 //!
 //! ```no_run
@@ -53,17 +76,28 @@ extern crate serde;
 extern crate derive_builder;
 
 mod category;
+mod chunks;
 mod content;
+mod cursor;
+mod entities;
 mod entry;
 mod feed;
 mod generator;
 mod link;
+mod opml;
+mod options;
 mod person;
+mod size;
 mod source;
+mod tag;
 mod text;
+mod validate;
+mod warning;
+mod writer;
 
 mod error;
 mod fromxml;
+mod html;
 mod toxml;
 mod util;
 
@@ -73,29 +107,44 @@ pub mod extension;
 pub use crate::category::Category;
 #[cfg(feature = "builders")]
 pub use crate::category::CategoryBuilder;
+pub use crate::chunks::ChunkReader;
 pub use crate::content::Content;
 #[cfg(feature = "builders")]
 pub use crate::content::ContentBuilder;
+pub use crate::cursor::EntryCursor;
 pub use crate::entry::Entry;
 #[cfg(feature = "builders")]
 pub use crate::entry::EntryBuilder;
-pub use crate::error::Error;
-pub use crate::feed::Feed;
+pub use crate::error::{Error, ErrorKind};
 #[cfg(feature = "builders")]
 pub use crate::feed::FeedBuilder;
+pub use crate::feed::{
+    Feed, FeedParts, Image, ImageAspect, PagingState, ParseStats, ParsedTimestamp, PresentElements,
+    SourcePosition,
+};
 pub use crate::generator::Generator;
 #[cfg(feature = "builders")]
 pub use crate::generator::GeneratorBuilder;
-pub use crate::link::Link;
+#[cfg(feature = "url")]
+pub use crate::html::discover_feeds;
 #[cfg(feature = "builders")]
 pub use crate::link::LinkBuilder;
+pub use crate::link::{Link, RepliesLink};
+pub use crate::opml::{feeds_to_opml, OpmlOutline};
+pub use crate::options::{
+    Conformance, ReadOptions, TrimMode, UnknownElementHandler, UnnamedPersons, WriteOptions,
+};
 pub use crate::person::Person;
 #[cfg(feature = "builders")]
 pub use crate::person::PersonBuilder;
 pub use crate::source::Source;
 #[cfg(feature = "builders")]
 pub use crate::source::SourceBuilder;
+pub use crate::tag::TagUri;
 #[cfg(feature = "builders")]
 pub use crate::text::TextBuilder;
 pub use crate::text::{Text, TextType};
 pub use crate::util::FixedDateTime;
+pub use crate::validate::{Fix, TimestampIssue, ValidationError};
+pub use crate::warning::Warning;
+pub use crate::writer::FeedWriter;
@@ -1,18 +1,25 @@
+use std::collections::HashSet;
 use std::io::{BufRead, Write};
 
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Reader;
-use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
+use crate::fromxml::{split_name, XmlReader};
+use crate::toxml::{ToXml, XmlWriter};
+use crate::util::unknown_attr;
+use crate::warning::Warning;
+
+/// The namespace of the Atom threading extension.
+pub(crate) const THREADING_NAMESPACE: &str = "http://purl.org/syndication/thread/1.0";
+
+/// The prefix of link relations written as URIs in the IANA registry.
+const IANA_RELATION_PREFIX: &str = "http://www.iana.org/assignments/relation/";
 
 /// Represents a link in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -35,6 +42,14 @@ pub struct Link {
     pub title: Option<String>,
     /// The length of the resource, in bytes.
     pub length: Option<String>,
+    /// The total number of replies, from the threading extension's `thr:count` attribute.
+    pub thr_count: Option<String>,
+    /// The date the replies were last updated, from the threading extension's `thr:updated`
+    /// attribute.
+    pub thr_updated: Option<String>,
+    /// Attributes that are not otherwise recognized, in the order they appeared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown_attrs: Vec<(String, String)>,
 }
 
 impl Default for Link {
@@ -46,11 +61,106 @@ impl Default for Link {
             mime_type: Default::default(),
             title: Default::default(),
             length: Default::default(),
+            thr_count: Default::default(),
+            thr_updated: Default::default(),
+            unknown_attrs: Default::default(),
         }
     }
 }
 
 impl Link {
+    /// Create a link with the given relation type.
+    fn with_rel<S>(href: S, rel: &str) -> Self
+    where
+        S: Into<String>,
+    {
+        Link {
+            href: href.into(),
+            rel: rel.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Create an `alternate` link to the given URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::alternate("http://example.com/article");
+    /// assert_eq!(link.rel(), "alternate");
+    /// assert_eq!(link.href(), "http://example.com/article");
+    /// ```
+    pub fn alternate<S>(href: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Link::with_rel(href, "alternate")
+    }
+
+    /// Create a `self` link to the given URI of an Atom document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::self_link("http://example.com/feed.atom");
+    /// assert_eq!(link.rel(), "self");
+    /// assert_eq!(link.mime_type(), Some("application/atom+xml"));
+    /// ```
+    pub fn self_link<S>(href: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Link {
+            mime_type: Some("application/atom+xml".into()),
+            ..Link::with_rel(href, "self")
+        }
+    }
+
+    /// Create an `enclosure` link to a resource of the given MIME type and length in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::enclosure("http://example.com/episode.mp3", "audio/mpeg", 1337);
+    /// assert_eq!(link.rel(), "enclosure");
+    /// assert_eq!(link.mime_type(), Some("audio/mpeg"));
+    /// assert_eq!(link.length(), Some("1337"));
+    /// ```
+    pub fn enclosure<S, T>(href: S, mime_type: T, length: u64) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        Link {
+            mime_type: Some(mime_type.into()),
+            length: Some(length.to_string()),
+            ..Link::with_rel(href, "enclosure")
+        }
+    }
+
+    /// Create a `related` link to the given URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let link = Link::related("http://example.com/other");
+    /// assert_eq!(link.rel(), "related");
+    /// ```
+    pub fn related<S>(href: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Link::with_rel(href, "related")
+    }
+
     /// Return the URI the referenced resource.
     ///
     /// # Examples
@@ -115,6 +225,76 @@ impl Link {
         self.rel = rel.into()
     }
 
+    /// Return the link relationship type without the IANA registry prefix.
+    ///
+    /// RFC 4287 treats a registered relation such as `self` and the same relation written as
+    /// a URI under `http://www.iana.org/assignments/relation/` as equivalent, so both are
+    /// returned as the bare token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("http://www.iana.org/assignments/relation/self");
+    /// assert_eq!(link.normalized_rel(), "self");
+    ///
+    /// link.set_rel("http://example.com/custom");
+    /// assert_eq!(link.normalized_rel(), "http://example.com/custom");
+    /// ```
+    pub fn normalized_rel(&self) -> &str {
+        if self.rel.starts_with(IANA_RELATION_PREFIX) {
+            &self.rel[IANA_RELATION_PREFIX.len()..]
+        } else {
+            &self.rel
+        }
+    }
+
+    /// Return whether this link points to an alternate version of the resource.
+    ///
+    /// A link without a `rel` attribute, or with an empty one, is an alternate link, as
+    /// RFC 4287 specifies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_rel("");
+    /// assert!(link.is_alternate());
+    ///
+    /// assert!(!Link::self_link("http://example.com/feed").is_alternate());
+    /// ```
+    pub fn is_alternate(&self) -> bool {
+        self.relation() == "alternate"
+    }
+
+    /// Return the relation type links are matched by: the [normalized](Link::normalized_rel)
+    /// `rel`, or `alternate` when it is empty.
+    pub(crate) fn relation(&self) -> &str {
+        match self.normalized_rel() {
+            "" => "alternate",
+            rel => rel,
+        }
+    }
+
+    /// Return whether this link points to a related resource that may be large, such as the
+    /// audio file of a podcast episode (`rel="enclosure"`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// assert!(Link::enclosure("http://example.com/1.mp3", "audio/mpeg", 1024).is_enclosure());
+    /// assert!(!Link::alternate("http://example.com/1").is_enclosure());
+    /// ```
+    pub fn is_enclosure(&self) -> bool {
+        self.normalized_rel() == "enclosure"
+    }
+
     /// Return the language of the referenced resource.
     ///
     /// # Examples
@@ -242,35 +422,308 @@ impl Link {
     {
         self.length = length.into()
     }
+
+    /// Return the number of replies from the threading extension's `thr:count` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_thr_count("5".to_string());
+    /// assert_eq!(link.thr_count(), Some("5"));
+    /// ```
+    pub fn thr_count(&self) -> Option<&str> {
+        self.thr_count.as_deref()
+    }
+
+    /// Set the number of replies for the threading extension's `thr:count` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_thr_count("5".to_string());
+    /// ```
+    pub fn set_thr_count<V>(&mut self, thr_count: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.thr_count = thr_count.into()
+    }
+
+    /// Return the date the replies were last updated from the threading extension's
+    /// `thr:updated` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_thr_updated("2017-06-03T15:15:44-05:00".to_string());
+    /// assert_eq!(link.thr_updated(), Some("2017-06-03T15:15:44-05:00"));
+    /// ```
+    pub fn thr_updated(&self) -> Option<&str> {
+        self.thr_updated.as_deref()
+    }
+
+    /// Set the date the replies were last updated for the threading extension's `thr:updated`
+    /// attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_thr_updated("2017-06-03T15:15:44-05:00".to_string());
+    /// ```
+    pub fn set_thr_updated<V>(&mut self, thr_updated: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.thr_updated = thr_updated.into()
+    }
+
+    /// Return the attributes of this link that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_unknown_attrs(vec![("v:rank".to_string(), "2".to_string())]);
+    /// assert_eq!(link.unknown_attrs(), &[("v:rank".to_string(), "2".to_string())]);
+    /// ```
+    pub fn unknown_attrs(&self) -> &[(String, String)] {
+        &self.unknown_attrs
+    }
+
+    /// Set the attributes of this link that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_unknown_attrs(vec![("v:rank".to_string(), "2".to_string())]);
+    /// ```
+    pub fn set_unknown_attrs<V>(&mut self, unknown_attrs: V)
+    where
+        V: Into<Vec<(String, String)>>,
+    {
+        self.unknown_attrs = unknown_attrs.into();
+    }
+}
+
+/// A `rel="replies"` link from the Atom threading extension (RFC 4685).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepliesLink {
+    /// The URI of the replies resource.
+    pub href: String,
+    /// The total number of replies, if known.
+    pub count: Option<u32>,
+    /// The date the replies were last updated, if known.
+    pub updated: Option<String>,
+}
+
+impl<'a> From<&'a Link> for RepliesLink {
+    fn from(link: &'a Link) -> Self {
+        RepliesLink {
+            href: link.href.clone(),
+            count: link
+                .thr_count
+                .as_ref()
+                .and_then(|count| count.trim().parse().ok()),
+            updated: link.thr_updated.clone(),
+        }
+    }
+}
+
+/// Return the first link with the given relation type, ignoring the IANA registry prefix and
+/// taking an empty relation as `alternate`.
+pub(crate) fn find_link<'a>(links: &'a [Link], rel: &str) -> Option<&'a Link> {
+    links.iter().find(|link| link.relation() == rel)
+}
+
+/// Replace the links with the relation type of `link` by `link`, at the position of the first
+/// one, or append `link` when there is none.
+pub(crate) fn replace_link(links: &mut Vec<Link>, link: Link) {
+    let rel = link.relation().to_string();
+    match links.iter().position(|other| other.relation() == rel) {
+        Some(index) => {
+            links[index] = link;
+            let rest = links.split_off(index + 1);
+            links.extend(rest.into_iter().filter(|other| other.relation() != rel));
+        }
+        None => links.push(link),
+    }
+}
+
+/// Remove the links that repeat the `href`, relation type and media type of an earlier link.
+pub(crate) fn dedup_links(links: &mut Vec<Link>) {
+    let mut seen = HashSet::new();
+    links.retain(|link| {
+        seen.insert((
+            link.href.clone(),
+            link.relation().to_string(),
+            link.mime_type.clone(),
+        ))
+    });
+}
+
+#[cfg(feature = "url")]
+impl Link {
+    /// Query parameters commonly appended to links for tracking purposes.
+    ///
+    /// A trailing `*` matches every parameter starting with the given prefix.
+    pub const TRACKING_PARAMS: &'static [&'static str] = &[
+        "utm_*", "fbclid", "gclid", "dclid", "msclkid", "mc_cid", "mc_eid", "igshid", "yclid",
+        "_hsenc", "_hsmi",
+    ];
+
+    /// Return the URI of the referenced resource without the query parameters listed in
+    /// [`Link::TRACKING_PARAMS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_href("http://example.com/article?id=1&utm_source=feed&fbclid=abc");
+    /// assert_eq!(link.cleaned_href(), "http://example.com/article?id=1");
+    /// ```
+    pub fn cleaned_href(&self) -> String {
+        self.cleaned_href_with(Link::TRACKING_PARAMS)
+    }
+
+    /// Return the URI of the referenced resource without the given query parameters.
+    ///
+    /// A trailing `*` in a parameter name matches every parameter starting with that prefix.
+    /// Relative or otherwise unparseable URIs are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_href("http://example.com/article?id=1&ref=home");
+    /// assert_eq!(link.cleaned_href_with(&["ref"]), "http://example.com/article?id=1");
+    /// ```
+    pub fn cleaned_href_with(&self, params: &[&str]) -> String {
+        let mut url = match url::Url::parse(&self.href) {
+            Ok(url) => url,
+            Err(_) => return self.href.clone(),
+        };
+
+        let is_tracking = |key: &str| {
+            params.iter().any(|param| {
+                if param.ends_with('*') {
+                    key.starts_with(&param[..param.len() - 1])
+                } else {
+                    key == *param
+                }
+            })
+        };
+
+        let pairs = url
+            .query_pairs()
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect::<Vec<_>>();
+        if !pairs.iter().any(|(key, _)| is_tracking(key)) {
+            return self.href.clone();
+        }
+
+        let kept = pairs
+            .into_iter()
+            .filter(|(key, _)| !is_tracking(key))
+            .collect::<Vec<_>>();
+        if kept.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(kept);
+        }
+
+        url.into()
+    }
 }
 
-impl FromXml for Link {
-    fn from_xml<B: BufRead>(
-        reader: &mut Reader<B>,
+impl Link {
+    /// Read a link, or `None` when its required `href` attribute is missing.
+    ///
+    /// A link without `href` is an error in strict mode. Otherwise it is skipped with a warning,
+    /// so it never ends up in the feed as a link with an empty `href`.
+    pub(crate) fn read_xml<B: BufRead>(
+        reader: &mut XmlReader<B>,
         mut atts: Attributes<'_>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Option<Self>, Error> {
         let mut link = Link::default();
+        let mut has_href = false;
 
         for att in atts.with_checks(false).flatten() {
             match att.key {
-                b"href" => link.href = att.unescape_and_decode_value(reader)?,
-                b"rel" => link.rel = att.unescape_and_decode_value(reader)?,
-                b"hreflang" => link.hreflang = Some(att.unescape_and_decode_value(reader)?),
-                b"type" => link.mime_type = Some(att.unescape_and_decode_value(reader)?),
-                b"title" => link.title = Some(att.unescape_and_decode_value(reader)?),
-                b"length" => link.length = Some(att.unescape_and_decode_value(reader)?),
-                _ => {}
+                b"href" => {
+                    link.href = reader.unescape_attr(&att)?;
+                    has_href = true;
+                }
+                b"rel" => link.rel = reader.unescape_attr(&att)?,
+                b"hreflang" => link.hreflang = Some(reader.unescape_attr(&att)?),
+                b"type" => link.mime_type = Some(reader.unescape_attr(&att)?),
+                b"title" => link.title = Some(reader.unescape_attr(&att)?),
+                b"length" => link.length = Some(reader.unescape_attr(&att)?),
+                b"xmlns" => {}
+                key if is_threading_attr(reader, key, b"count") => {
+                    link.thr_count = Some(reader.unescape_attr(&att)?)
+                }
+                key if is_threading_attr(reader, key, b"updated") => {
+                    link.thr_updated = Some(reader.unescape_attr(&att)?)
+                }
+                key if key.starts_with(b"xmlns:")
+                    && reader.resolve_prefix(&key[b"xmlns:".len()..])
+                        == Some(THREADING_NAMESPACE.as_bytes()) => {}
+                _ => link.unknown_attrs.push(unknown_attr(reader, &att)?),
             }
         }
 
+        let position = reader.buffer_position();
         reader.read_to_end(b"link", &mut Vec::new())?;
 
-        Ok(link)
+        if has_href {
+            return Ok(Some(link));
+        }
+        if reader.options().strict {
+            return Err(Error::MissingLinkHref);
+        }
+        reader.warn(Warning::LinkWithoutHref {
+            rel: link.rel,
+            position,
+        });
+        Ok(None)
     }
 }
 
+/// Return `true` if `key` is the threading extension attribute `local_name`, matching the
+/// `thr` prefix when it is not declared.
+fn is_threading_attr<B: BufRead>(reader: &XmlReader<B>, key: &[u8], local_name: &[u8]) -> bool {
+    let (prefix, name) = split_name(key);
+    name == local_name
+        && !prefix.is_empty()
+        && match reader.resolve_prefix(prefix) {
+            Some(namespace) => namespace == THREADING_NAMESPACE.as_bytes(),
+            None => prefix == b"thr",
+        }
+}
+
 impl ToXml for Link {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError> {
         let name = b"link";
         let mut element = BytesStart::borrowed(name, name.len());
         element.push_attribute(("href", &*self.href));
@@ -292,6 +745,24 @@ impl ToXml for Link {
             element.push_attribute(("length", &**length));
         }
 
+        if self.thr_count.is_some() || self.thr_updated.is_some() {
+            element.push_attribute(("xmlns:thr", THREADING_NAMESPACE));
+        }
+
+        if let Some(ref thr_count) = self.thr_count {
+            element.push_attribute(("thr:count", &**thr_count));
+        }
+
+        if let Some(ref thr_updated) = self.thr_updated {
+            element.push_attribute(("thr:updated", &**thr_updated));
+        }
+
+        element.extend_attributes(
+            self.unknown_attrs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
         writer.write_event(Event::Empty(element))?;
 
         Ok(())
@@ -0,0 +1,131 @@
+use std::io::Write;
+
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::Error as XmlError;
+use quick_xml::Writer;
+
+use crate::feed::Feed;
+use crate::link::find_link;
+
+/// An `outline` element of an OPML subscription list describing one feed.
+///
+/// Returned by [`Feed::to_opml_outline`] and written by [`feeds_to_opml`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct OpmlOutline {
+    /// The text shown for the subscription, taken from the title of the feed.
+    pub text: String,
+    /// The title of the subscription, taken from the title of the feed.
+    pub title: String,
+    /// The URL the feed is fetched from, taken from its `rel="self"` link.
+    pub xml_url: Option<String>,
+    /// The URL of the website of the feed, taken from its `rel="alternate"` link.
+    pub html_url: Option<String>,
+}
+
+impl OpmlOutline {
+    fn write<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        let mut element = BytesStart::borrowed_name(b"outline");
+        element.push_attribute(("type", "rss"));
+        element.push_attribute(("text", self.text.as_str()));
+        element.push_attribute(("title", self.title.as_str()));
+
+        if let Some(ref xml_url) = self.xml_url {
+            element.push_attribute(("xmlUrl", xml_url.as_str()));
+        }
+
+        if let Some(ref html_url) = self.html_url {
+            element.push_attribute(("htmlUrl", html_url.as_str()));
+        }
+
+        writer.write_event(Event::Empty(element))
+    }
+}
+
+impl Feed {
+    /// Describe this feed as an outline of an OPML subscription list.
+    ///
+    /// The `text` and `title` are the trimmed title of the feed, the `xml_url` is the `href` of
+    /// its first `rel="self"` link and the `html_url` the one of its first `rel="alternate"`
+    /// link. A missing or empty link leaves the URL unset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Example Blog");
+    /// feed.set_links(vec![
+    ///     Link::self_link("http://example.com/feed.xml"),
+    ///     Link::alternate("http://example.com/"),
+    /// ]);
+    ///
+    /// let outline = feed.to_opml_outline();
+    /// assert_eq!(outline.text, "Example Blog");
+    /// assert_eq!(outline.xml_url.as_deref(), Some("http://example.com/feed.xml"));
+    /// assert_eq!(outline.html_url.as_deref(), Some("http://example.com/"));
+    /// ```
+    pub fn to_opml_outline(&self) -> OpmlOutline {
+        let title = self.title.as_str().trim().to_string();
+        let url = |rel| {
+            find_link(&self.links, rel)
+                .map(|link| link.href.trim())
+                .filter(|href| !href.is_empty())
+                .map(str::to_string)
+        };
+
+        OpmlOutline {
+            text: title.clone(),
+            title,
+            xml_url: url("self"),
+            html_url: url("alternate"),
+        }
+    }
+}
+
+/// Write an OPML 2.0 subscription list with one outline per feed.
+///
+/// Each feed is described by [`Feed::to_opml_outline`]. The outlines use `type="rss"`, which
+/// feed readers expect for Atom feeds as well.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{feeds_to_opml, Feed, Link};
+///
+/// let mut feed = Feed::default();
+/// feed.set_title("Fish & Chips");
+/// feed.set_links(vec![Link::self_link("http://example.com/feed.xml")]);
+///
+/// let opml = feeds_to_opml(&[feed]);
+/// assert!(opml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?><opml version="2.0">"#));
+/// assert!(opml.contains(r#"text="Fish &amp; Chips""#));
+/// assert!(opml.contains(r#"xmlUrl="http://example.com/feed.xml"/>"#));
+/// ```
+pub fn feeds_to_opml(feeds: &[Feed]) -> String {
+    let mut writer = Writer::new(Vec::new());
+    write_opml(&mut writer, feeds).expect("writing to a Vec cannot fail");
+    String::from_utf8(writer.into_inner()).expect("the written OPML is valid UTF-8")
+}
+
+fn write_opml(writer: &mut Writer<Vec<u8>>, feeds: &[Feed]) -> Result<(), XmlError> {
+    writer.write_event(Event::Decl(BytesDecl::new(b"1.0", Some(b"UTF-8"), None)))?;
+
+    let mut opml = BytesStart::borrowed_name(b"opml");
+    opml.push_attribute(("version", "2.0"));
+    writer.write_event(Event::Start(opml))?;
+
+    writer.write_event(Event::Start(BytesStart::borrowed_name(b"head")))?;
+    writer.write_event(Event::Start(BytesStart::borrowed_name(b"title")))?;
+    writer.write_event(Event::Text(BytesText::from_plain_str("Subscriptions")))?;
+    writer.write_event(Event::End(BytesEnd::borrowed(b"title")))?;
+    writer.write_event(Event::End(BytesEnd::borrowed(b"head")))?;
+
+    writer.write_event(Event::Start(BytesStart::borrowed_name(b"body")))?;
+    for feed in feeds {
+        feed.to_opml_outline().write(writer)?;
+    }
+    writer.write_event(Event::End(BytesEnd::borrowed(b"body")))?;
+
+    writer.write_event(Event::End(BytesEnd::borrowed(b"opml")))
+}
@@ -0,0 +1,536 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// Options that control how a feed is written.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{Feed, WriteOptions};
+///
+/// let mut options = WriteOptions::default();
+/// options.namespace_prefix = Some("a");
+///
+/// let xml = Feed::default().write_with(Vec::new(), options).unwrap();
+/// assert!(String::from_utf8(xml).unwrap().contains("<a:feed xmlns:a="));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct WriteOptions<'a> {
+    /// The namespace prefix to use for every Atom element, e.g. `a` to write `<a:feed>`.
+    ///
+    /// When unset the Atom namespace is declared as the default namespace and elements are
+    /// written without a prefix. Feeds written with a prefix are read back like unprefixed
+    /// ones, since elements are matched by namespace.
+    pub namespace_prefix: Option<&'a str>,
+    /// Whether to collapse runs of whitespace in HTML content and text constructs to a single
+    /// space. Whitespace inside `<pre>` elements, tags and comments is kept as is.
+    ///
+    /// Defaults to `false`.
+    #[cfg(feature = "html")]
+    pub collapse_html_whitespace: bool,
+    /// Whether to write a generated `urn:uuid:` id for each entry whose id is empty or only
+    /// whitespace, so the output is a valid Atom document.
+    ///
+    /// The id holds a version 5 UUID in the URL namespace of a seed made of the `href` of the
+    /// first `rel="alternate"` link of the entry, its title, its `updated` and `published`
+    /// timestamps and its index in the feed, counting from 0. It stays the same across runs as
+    /// long as those do, and entries that share a link and title still get different ids. An
+    /// entry inserted before it, as when a new entry is added at the top of the feed, changes
+    /// its id. The entry itself is not changed.
+    ///
+    /// This requires the `uuid` feature. Without it, writing an entry without an id fails with
+    /// [`Error::MissingRequiredField`](crate::Error::MissingRequiredField) naming the entries,
+    /// such as `entry[1] id`, before anything is written, and so does
+    /// [`FeedWriter::write_entry`](crate::FeedWriter::write_entry) for the entry it is given.
+    ///
+    /// Defaults to `false`.
+    pub generate_missing_ids: bool,
+    /// What to do with an author or contributor whose name is empty or only whitespace, which
+    /// is invalid because Atom requires every person to have a name.
+    ///
+    /// An absent `email` or `uri` is never written, whatever this is set to.
+    ///
+    /// Defaults to [`UnnamedPersons::Write`].
+    pub unnamed_persons: UnnamedPersons,
+    /// Whether to fail writing with
+    /// [`Error::MissingRequiredField`](crate::Error::MissingRequiredField) when the `id` or
+    /// `title` of the feed or of one of its entries is empty or only whitespace, before
+    /// anything is written.
+    ///
+    /// `updated` is not checked: it always holds a timestamp, and one set to the Unix epoch is
+    /// written as it is. An entry id generated by
+    /// [`generate_missing_ids`](WriteOptions::generate_missing_ids) counts as present.
+    ///
+    /// Defaults to `false`. [`Feed::write_to`](crate::Feed::write_to) and the other `write_to`
+    /// methods always check.
+    pub require_fields: bool,
+}
+
+impl WriteOptions<'_> {
+    /// Return the default options with `require_fields` set, as used by the `write_to`
+    /// methods of [`Feed`](crate::Feed).
+    pub(crate) fn checked() -> Self {
+        WriteOptions {
+            require_fields: true,
+            ..WriteOptions::default()
+        }
+    }
+}
+
+/// How a person without a name is written, see [`WriteOptions::unnamed_persons`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum UnnamedPersons {
+    /// Write the person with an empty `name` element, as [`WriteOptions::default`] does.
+    Write,
+    /// Leave the person out of the output.
+    Skip,
+    /// Fail writing with [`Error::MissingRequiredField`](crate::Error::MissingRequiredField)
+    /// naming the persons, such as `entry[0] author[1] name`, before anything is written.
+    ///
+    /// When writing with a [`FeedWriter`](crate::FeedWriter), the metadata is checked when it
+    /// is started and each entry when it is written, so a failing entry is left out while
+    /// the ones before it have been written already.
+    Reject,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for UnnamedPersons {
+    fn default() -> Self {
+        UnnamedPersons::Write
+    }
+}
+
+/// Options that control how a feed is read.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{Feed, ReadOptions};
+///
+/// let mut options = ReadOptions::default();
+/// options.strict = true;
+///
+/// let xml = "<feed><author><name>Jane</name><uri>not a uri</uri></author></feed>";
+/// assert!(Feed::read_with(xml.as_bytes(), options).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ReadOptions {
+    /// Whether to reject values that violate the Atom specification instead of keeping them
+    /// as they appear in the input.
+    ///
+    /// In strict mode the `uri` of a person must be a valid URI reference, and a `link` without
+    /// an `href` attribute fails with [`Error::MissingLinkHref`](crate::Error::MissingLinkHref).
+    /// Otherwise such a link is skipped with a warning instead of being read with an empty
+    /// `href`. A timestamp without a UTC offset fails with
+    /// [`Error::WrongDatetime`](crate::Error::WrongDatetime) unless
+    /// [`assume_utc_timestamps`](ReadOptions::assume_utc_timestamps) is enabled.
+    ///
+    /// Defaults to `false`.
+    pub strict: bool,
+    /// The maximum nesting depth of elements, counting the `feed` element as depth 1.
+    ///
+    /// Reading fails with [`Error::MaxDepthExceeded`](crate::Error::MaxDepthExceeded) when an
+    /// element is nested deeper, which bounds the work done on untrusted input.
+    ///
+    /// Defaults to `128`.
+    pub max_depth: usize,
+    /// Whether to keep the XML comments inside an entry in
+    /// [`Entry::comments`](crate::Entry::comments) and those directly before it in
+    /// [`Entry::leading_comments`](crate::Entry::leading_comments), so they are written back
+    /// with the entry.
+    ///
+    /// Defaults to `false`.
+    pub preserve_comments: bool,
+    /// Whether to keep the `xml-stylesheet` processing instructions before the `feed` element
+    /// in [`Feed::stylesheets`](crate::Feed::stylesheets), so they are written back.
+    ///
+    /// Defaults to `false`.
+    pub preserve_stylesheets: bool,
+    /// Whether to keep the `version` and `standalone` pseudo-attributes of the XML declaration
+    /// in [`Feed::xml_version`](crate::Feed::xml_version) and
+    /// [`Feed::standalone`](crate::Feed::standalone), so they are written back.
+    ///
+    /// Defaults to `false`.
+    pub preserve_declaration: bool,
+    /// Whether to match the names of Atom elements case-insensitively, so `<Title>` or
+    /// `<ENTRY>` produced by non-conformant generators are read like their lowercase forms.
+    ///
+    /// Only unprefixed names and names whose prefix is bound to the Atom namespace are
+    /// affected; extension elements keep their case.
+    ///
+    /// Defaults to `false`.
+    pub case_insensitive_elements: bool,
+    /// Whether to accept timestamps without a UTC offset, such as `2021-05-01T12:00:00`, by
+    /// assuming they are in UTC.
+    ///
+    /// Such timestamps are not valid RFC 3339 but are common in feeds. A warning is recorded
+    /// each time the assumption is made, see
+    /// [`Feed::read_with_warnings`](crate::Feed::read_with_warnings). When disabled, they are
+    /// left to the lenient date parser used for other formats, which also reads them as times
+    /// in UTC but does not accept every variant, such as a space instead of the `T`; reading
+    /// fails with [`Error::WrongDatetime`](crate::Error::WrongDatetime) for the other variants,
+    /// and for all of them in [`strict`](ReadOptions::strict) mode.
+    ///
+    /// Defaults to `true`.
+    pub assume_utc_timestamps: bool,
+    /// Whether to resolve the named character references of HTML, such as `&nbsp;` and
+    /// `&copy;`, in text and attribute values.
+    ///
+    /// XML only predefines `&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;`, so reading fails
+    /// with an error on any other named reference unless this is enabled.
+    ///
+    /// Defaults to `false`.
+    pub html_entities: bool,
+    /// The maximum wall-clock time reading may take.
+    ///
+    /// Reading fails with [`Error::Timeout`](crate::Error::Timeout) once the time is exceeded.
+    /// The clock is checked every 1024 XML events rather than continuously, so reading can
+    /// overrun the limit by the time it takes to parse that many events, and time spent
+    /// waiting on the underlying reader counts as well.
+    ///
+    /// Defaults to `None`, meaning no limit.
+    pub max_duration: Option<Duration>,
+    /// Whether to keep the text content of elements exactly as it appears in the input, without
+    /// resolving character and entity references, for example to verify a signature computed
+    /// over the original bytes.
+    ///
+    /// With this enabled `Fish &amp; Chips` is read as `Fish &amp; Chips` rather than
+    /// `Fish & Chips`, and CDATA sections keep their `<![CDATA[` and `]]>` markers. Attribute
+    /// values are still decoded. Values read this way are escaped again when written, so a
+    /// feed read with raw text should not be written back.
+    ///
+    /// Defaults to `false`.
+    pub raw_text: bool,
+    /// Whether to split an `email` that also holds a display name, such as
+    /// `Jane Doe jane@example.com` or `Jane Doe <jane@example.com>`, into the address and the
+    /// name.
+    ///
+    /// [`Person::email`](crate::Person::email) is set to the address alone, and the name is
+    /// used as [`Person::name`](crate::Person::name) if the person has no name of its own.
+    /// Any word containing an `@` may be taken as the address, so this is off by default.
+    ///
+    /// Defaults to `false`.
+    pub split_email_names: bool,
+    /// A callback invoked with the namespace and the local name of every element that is
+    /// skipped because it is not recognized.
+    ///
+    /// Elements with a prefix not bound to the Atom namespace are read as extensions rather
+    /// than skipped, so this sees the unknown unprefixed or Atom elements, whose namespace is
+    /// usually the Atom namespace, and the unknown children of authors, contributors and
+    /// sources. The namespace is empty if none is declared.
+    ///
+    /// Defaults to `None`.
+    pub unknown_element_handler: Option<UnknownElementHandler>,
+    /// Whether to skip an entry that cannot be read and continue with the next one, instead of
+    /// failing to read the whole feed.
+    ///
+    /// A warning naming the byte position of the entry and the error is recorded for each
+    /// skipped entry, see [`Feed::read_with_warnings`](crate::Feed::read_with_warnings).
+    /// After an error the reader resynchronizes by scanning forward, counting start and end
+    /// tags, to the next `<entry>` start tag directly inside the feed, or to the end of the feed;
+    /// everything in between is discarded.
+    ///
+    /// Only entries whose content is invalid, such as an unparseable timestamp or an unknown
+    /// text type, are skipped. Input that is not well-formed XML still fails reading, because
+    /// the XML parser cannot continue past it, and so do errors outside of entries, exceeded
+    /// limits and I/O errors.
+    ///
+    /// Defaults to `false`.
+    pub skip_malformed_entries: bool,
+    /// The name of a nonstandard element of an entry, such as `published_timestamp`, that holds
+    /// its publication time as the number of seconds since the Unix epoch.
+    ///
+    /// The name is matched exactly as it appears in the input, including any prefix. The time
+    /// is stored in [`Entry::published`](crate::Entry::published) in UTC, so it is written as
+    /// a regular RFC 3339 `published` element, and a value that is not an integer fails
+    /// reading with [`Error::WrongDatetime`](crate::Error::WrongDatetime). When an entry also
+    /// has a `published` element, the one that appears last wins.
+    ///
+    /// Defaults to `None`.
+    pub epoch_published_element: Option<String>,
+    /// The name of a nonstandard element of an entry that holds the last time it was modified
+    /// as the number of seconds since the Unix epoch, stored in
+    /// [`Entry::updated`](crate::Entry::updated).
+    ///
+    /// See [`ReadOptions::epoch_published_element`].
+    ///
+    /// Defaults to `None`.
+    pub epoch_updated_element: Option<String>,
+    /// How the whitespace of text constructs with `type="text"`, such as most titles, is
+    /// trimmed.
+    ///
+    /// Text constructs are the `title`, `subtitle`, `summary` and `rights` of feeds, entries and
+    /// sources.
+    ///
+    /// Defaults to [`TrimMode::None`], keeping the text as it appears in the input.
+    pub trim_text: TrimMode,
+    /// How the whitespace of text constructs with `type="html"` is trimmed.
+    ///
+    /// [`TrimMode::Collapse`] keeps a single space where the input has one or more, which
+    /// matters between inline elements such as `<b>bold</b> <i>italic</i>`. XHTML text
+    /// constructs are never trimmed.
+    ///
+    /// Defaults to [`TrimMode::None`], keeping the markup as it appears in the input.
+    pub trim_html: TrimMode,
+    /// Whether to read the dates of RSS elements mixed into an entry as its Atom dates.
+    ///
+    /// | Element    | Read as                                      |
+    /// |------------|----------------------------------------------|
+    /// | `pubDate`  | [`Entry::published`](crate::Entry::published) |
+    /// | `dc:date`  | [`Entry::updated`](crate::Entry::updated)     |
+    ///
+    /// `pubDate` is matched by its unprefixed name and `dc:date` by the Dublin Core namespace,
+    /// whatever prefix is bound to it, or by the `dc` prefix when that is not declared. A mapped
+    /// date is only used when the entry has no Atom element for the same date, and a date that
+    /// cannot be parsed is ignored with a warning instead of failing reading. `pubDate` is
+    /// skipped otherwise, while `dc:date` is kept as an extension either way.
+    ///
+    /// Other RSS elements, such as `guid` or `description`, are skipped either way, and a
+    /// warning naming each one is recorded, see
+    /// [`Feed::read_with_warnings`](crate::Feed::read_with_warnings).
+    ///
+    /// Defaults to `false`.
+    pub map_rss_dates: bool,
+    /// The maximum length in bytes of a single text value, such as the content of an entry or
+    /// its title, which bounds the memory a single pathological element can use.
+    ///
+    /// What happens to a longer value depends on
+    /// [`ReadOptions::truncate_long_text`]. The markup of HTML and XHTML values counts towards
+    /// the length, and values are checked as they are read, so no more than the limit and one
+    /// chunk of text is held at a time. Text constructs are trimmed as set by
+    /// [`ReadOptions::trim_text`] and [`ReadOptions::trim_html`] before their length is
+    /// checked.
+    ///
+    /// Defaults to `None`, meaning no limit.
+    pub max_text_len: Option<usize>,
+    /// Whether to truncate a text value longer than [`ReadOptions::max_text_len`] instead of
+    /// failing.
+    ///
+    /// When disabled, reading fails with [`Error::TextTooLong`](crate::Error::TextTooLong).
+    /// When enabled, the value is cut at the last character boundary within the limit, the rest
+    /// of the element is skipped, and a warning is recorded, see
+    /// [`Feed::read_with_warnings`](crate::Feed::read_with_warnings). A truncated HTML or XHTML
+    /// value may end in the middle of its markup.
+    ///
+    /// Defaults to `false`.
+    pub truncate_long_text: bool,
+    /// The URL the feed was retrieved from, to rewrite every link `href` and content `src`
+    /// to absolute form against while reading.
+    ///
+    /// References are resolved against the nearest `xml:base` in scope, on the element itself,
+    /// the content, the source, the entry or the feed, so an absolute `xml:base` takes
+    /// precedence over this URL. A relative `xml:base` is itself resolved against the
+    /// enclosing base, which is this URL at the top. Empty references and references that
+    /// cannot be resolved are left unchanged.
+    ///
+    /// Defaults to `None`, meaning references are kept as they appear in the input.
+    #[cfg(feature = "url")]
+    pub resolve_links: Option<url::Url>,
+    /// Whether to fail reading with
+    /// [`Error::MissingRequiredField`](crate::Error::MissingRequiredField) when the feed or one
+    /// of its entries has an empty `id`, `title` or `updated`.
+    ///
+    /// The check is made after the whole feed has been read, so every missing field is named.
+    ///
+    /// Defaults to `false`.
+    pub require_fields: bool,
+    /// Whether to accept a document type declaration that declares entities, such as
+    /// `<!DOCTYPE feed [<!ENTITY xxe SYSTEM "file:///etc/passwd">]>`.
+    ///
+    /// Declared entities are never expanded, whether internal or external, so no file or URL
+    /// is ever read through them and a reference to one fails reading like any other unknown
+    /// entity. Because such declarations are the vehicle of XML external entity (XXE) attacks,
+    /// reading fails with [`Error::ExternalEntity`](crate::Error::ExternalEntity) as soon as
+    /// one is seen unless this is enabled, in which case the declaration is ignored.
+    ///
+    /// Defaults to `false`.
+    pub allow_entity_declarations: bool,
+}
+
+/// How closely a feed must follow the Atom specification to be read, see
+/// [`ReadOptions::conformance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Conformance {
+    /// Accept common deviations from the specification, as [`ReadOptions::default`] does.
+    Lenient,
+    /// Reject timestamps that are not valid RFC 3339, such as ones without a UTC offset.
+    Standard,
+    /// Reject every violation the reader checks for: invalid timestamps, invalid person URIs,
+    /// links without an `href` and empty required fields.
+    Strict,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for Conformance {
+    fn default() -> Self {
+        Conformance::Lenient
+    }
+}
+
+/// How the whitespace of a text value is trimmed when it is read, see
+/// [`ReadOptions::trim_text`] and [`ReadOptions::trim_html`].
+///
+/// Only the whitespace of XML, which is spaces, tabs, carriage returns and line feeds, is
+/// affected, so a non-breaking space is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TrimMode {
+    /// Keep the value as it appears in the input.
+    None,
+    /// Remove the whitespace at the start and the end of the value.
+    Ends,
+    /// Replace every run of whitespace, including one at the start or the end of the value,
+    /// with a single space.
+    Collapse,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for TrimMode {
+    fn default() -> Self {
+        TrimMode::None
+    }
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        ReadOptions {
+            strict: false,
+            max_depth: 128,
+            preserve_comments: false,
+            preserve_stylesheets: false,
+            preserve_declaration: false,
+            case_insensitive_elements: false,
+            assume_utc_timestamps: true,
+            html_entities: false,
+            max_duration: None,
+            raw_text: false,
+            split_email_names: false,
+            unknown_element_handler: None,
+            skip_malformed_entries: false,
+            epoch_published_element: None,
+            epoch_updated_element: None,
+            trim_text: TrimMode::None,
+            trim_html: TrimMode::None,
+            map_rss_dates: false,
+            max_text_len: None,
+            truncate_long_text: false,
+            #[cfg(feature = "url")]
+            resolve_links: None,
+            require_fields: false,
+            allow_entity_declarations: false,
+        }
+    }
+}
+
+impl ReadOptions {
+    /// Return the default options with the toggles that check the input set for a conformance
+    /// level.
+    ///
+    /// | Level                     | `assume_utc_timestamps` | `strict` | `require_fields` |
+    /// |---------------------------|-------------------------|----------|------------------|
+    /// | [`Conformance::Lenient`]  | `true`                  | `false`  | `false`          |
+    /// | [`Conformance::Standard`] | `false`                 | `false`  | `false`          |
+    /// | [`Conformance::Strict`]   | `false`                 | `true`   | `true`           |
+    ///
+    /// The toggles can still be changed individually afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Conformance, Feed, ReadOptions};
+    ///
+    /// let xml = "<feed><id>urn:feed</id><title>Feed</title>\
+    ///            <updated>2021-05-01T12:00:00</updated></feed>";
+    ///
+    /// let lenient = ReadOptions::conformance(Conformance::Lenient);
+    /// assert!(Feed::read_with(xml.as_bytes(), lenient).is_ok());
+    ///
+    /// let strict = ReadOptions::conformance(Conformance::Strict);
+    /// assert!(Feed::read_with(xml.as_bytes(), strict).is_err());
+    /// ```
+    pub fn conformance(conformance: Conformance) -> Self {
+        let mut options = ReadOptions::default();
+        match conformance {
+            Conformance::Lenient => {}
+            Conformance::Standard => options.assume_utc_timestamps = false,
+            Conformance::Strict => {
+                options.assume_utc_timestamps = false;
+                options.strict = true;
+                options.require_fields = true;
+            }
+        }
+        options
+    }
+}
+
+/// A callback for the elements skipped while reading, see
+/// [`ReadOptions::unknown_element_handler`].
+///
+/// Cloning the handler shares the callback, so options cloned from each other report to the
+/// same callback.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use atom_syndication::{Feed, ReadOptions, UnknownElementHandler};
+///
+/// let skipped = Arc::new(Mutex::new(Vec::new()));
+/// let names = Arc::clone(&skipped);
+///
+/// let mut options = ReadOptions::default();
+/// options.unknown_element_handler = Some(UnknownElementHandler::new(move |namespace, name| {
+///     names.lock().unwrap().push(format!("{{{}}}{}", namespace, name));
+/// }));
+///
+/// let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><unknown/></feed>"#;
+/// Feed::read_with(xml.as_bytes(), options).unwrap();
+/// assert_eq!(*skipped.lock().unwrap(), vec!["{http://www.w3.org/2005/Atom}unknown"]);
+/// ```
+#[derive(Clone)]
+pub struct UnknownElementHandler(Arc<Mutex<HandlerFn>>);
+
+type HandlerFn = dyn FnMut(&str, &str) + Send;
+
+impl UnknownElementHandler {
+    /// Create a handler calling `handler` with the namespace and local name of each skipped
+    /// element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::UnknownElementHandler;
+    ///
+    /// let handler = UnknownElementHandler::new(|namespace, name| {
+    ///     println!("skipped {} in {}", name, namespace);
+    /// });
+    /// ```
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: FnMut(&str, &str) + Send + 'static,
+    {
+        UnknownElementHandler(Arc::new(Mutex::new(handler)))
+    }
+
+    pub(crate) fn call(&self, namespace: &str, name: &str) {
+        let mut handler = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        (*handler)(namespace, name)
+    }
+}
+
+impl fmt::Debug for UnknownElementHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UnknownElementHandler")
+    }
+}
+
+impl PartialEq for UnknownElementHandler {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
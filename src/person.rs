@@ -3,17 +3,16 @@ use std::io::{BufRead, Write};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Reader;
-use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::{ToXmlNamed, WriterExt};
-use crate::util::atom_text;
+use crate::fromxml::{FromXml, XmlReader};
+use crate::options::UnnamedPersons;
+use crate::toxml::{ToXmlNamed, WriterExt, XmlWriter};
+use crate::util::{atom_text, is_uri_reference, unknown_attr};
 
 /// Represents a person in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -30,6 +29,9 @@ pub struct Person {
     pub email: Option<String>,
     /// A Web page for the person.
     pub uri: Option<String>,
+    /// Attributes that are not otherwise recognized, in the order they appeared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown_attrs: Vec<(String, String)>,
 }
 
 impl Person {
@@ -128,20 +130,170 @@ impl Person {
     {
         self.uri = uri.into()
     }
+
+    /// Return the attributes of this person that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// person.set_unknown_attrs(vec![("v:role".to_string(), "editor".to_string())]);
+    /// assert_eq!(person.unknown_attrs(), &[("v:role".to_string(), "editor".to_string())]);
+    /// ```
+    pub fn unknown_attrs(&self) -> &[(String, String)] {
+        &self.unknown_attrs
+    }
+
+    /// Set the attributes of this person that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// person.set_unknown_attrs(vec![("v:role".to_string(), "editor".to_string())]);
+    /// ```
+    pub fn set_unknown_attrs<V>(&mut self, unknown_attrs: V)
+    where
+        V: Into<Vec<(String, String)>>,
+    {
+        self.unknown_attrs = unknown_attrs.into();
+    }
+
+    /// Return whether this person is the same person as `other`.
+    ///
+    /// Names are compared exactly and emails case-insensitively, since mail domains are not
+    /// case-sensitive and addresses are rarely distinguished by case in practice. The `uri` and
+    /// unknown attributes are not compared. Equality with `==` stays strict.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut first = Person::default();
+    /// first.set_name("Jane Doe");
+    /// first.set_email("Jane@Example.com".to_string());
+    ///
+    /// let mut second = first.clone();
+    /// second.set_email("jane@example.com".to_string());
+    /// assert!(first.same_as(&second));
+    /// assert_ne!(first, second);
+    /// ```
+    pub fn same_as(&self, other: &Person) -> bool {
+        self.name == other.name
+            && match (self.email.as_deref(), other.email.as_deref()) {
+                (Some(email), Some(other)) => email.eq_ignore_ascii_case(other),
+                (email, other) => email == other,
+            }
+    }
+}
+
+#[cfg(feature = "url")]
+impl Person {
+    /// Return the Web page for this person if it is an absolute `http` or `https` URL.
+    ///
+    /// Other schemes such as `javascript:` and relative references are ignored, so the result
+    /// is safe to use as a link target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// person.set_uri("https://example.com/~jane".to_string());
+    /// assert_eq!(person.homepage_url().unwrap().as_str(), "https://example.com/~jane");
+    ///
+    /// person.set_uri("javascript:alert(1)".to_string());
+    /// assert!(person.homepage_url().is_none());
+    /// ```
+    pub fn homepage_url(&self) -> Option<url::Url> {
+        let url = url::Url::parse(self.uri.as_deref()?.trim()).ok()?;
+        match url.scheme() {
+            "http" | "https" => Some(url),
+            _ => None,
+        }
+    }
+}
+
+/// Read the `uri` of a person, rejecting values that are not URI references in strict mode.
+fn person_uri<B: BufRead>(reader: &mut XmlReader<B>) -> Result<Option<String>, Error> {
+    let uri = atom_text(reader)?;
+    match uri {
+        Some(ref value) if reader.options().strict && !is_uri_reference(value.trim()) => {
+            Err(Error::InvalidUri(value.clone()))
+        }
+        _ => Ok(uri),
+    }
+}
+
+/// Split an `email` value that holds a display name as well as an address, such as
+/// `Jane Doe jane@example.com`, `Jane Doe <jane@example.com>` or `jane@example.com (Jane Doe)`,
+/// into the name and the address.
+///
+/// Returns `None` when the value is a bare address or no address can be found in it.
+fn split_email(value: &str) -> Option<(String, String)> {
+    let value = value.trim();
+    if !value.contains(|c: char| c.is_whitespace() || c == '<' || c == '(') {
+        return None;
+    }
+
+    let (name, address) = if let (Some(start), Some(end)) = (value.find('<'), value.rfind('>')) {
+        if start > end {
+            return None;
+        }
+        let name = format!("{} {}", &value[..start], &value[end + 1..]);
+        (name, value[start + 1..end].to_string())
+    } else if let (Some(start), true) = (value.find('('), value.ends_with(')')) {
+        let name = value[start + 1..value.len() - 1].to_string();
+        (name, value[..start].to_string())
+    } else {
+        let address = value
+            .split_whitespace()
+            .rev()
+            .find(|word| word.contains('@'))?;
+        let name = value
+            .split_whitespace()
+            .filter(|word| *word != address)
+            .collect::<Vec<_>>()
+            .join(" ");
+        (name, address.to_string())
+    };
+
+    let address = address.trim();
+    if !address.contains('@') || address.contains(char::is_whitespace) {
+        return None;
+    }
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    let name = name.trim_matches(|c| c == '"' || c == '\'');
+    Some((name.to_string(), address.to_string()))
 }
 
 impl FromXml for Person {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes<'_>) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut XmlReader<B>,
+        mut atts: Attributes<'_>,
+    ) -> Result<Self, Error> {
         let mut person = Person::default();
         let mut buf = Vec::new();
 
+        for att in atts.with_checks(false).flatten() {
+            if att.key != b"xmlns" {
+                person.unknown_attrs.push(unknown_attr(reader, &att)?);
+            }
+        }
+
         loop {
             match reader.read_event(&mut buf)? {
-                Event::Start(element) => match element.name() {
+                Event::Start(element) => match &*reader.element_name(element.name()) {
                     b"name" => person.name = atom_text(reader)?.unwrap_or_default(),
                     b"email" => person.email = atom_text(reader)?,
-                    b"uri" => person.uri = atom_text(reader)?,
-                    n => reader.read_to_end(n, &mut Vec::new())?,
+                    b"uri" => person.uri = person_uri(reader)?,
+                    _ => reader.skip_element(element.name())?,
                 },
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
@@ -151,26 +303,61 @@ impl FromXml for Person {
             buf.clear();
         }
 
+        if reader.options().split_email_names {
+            if let Some((name, address)) = person.email.as_deref().and_then(split_email) {
+                if person.name.trim().is_empty() {
+                    person.name = name;
+                }
+                person.email = Some(address);
+            }
+        }
+
         Ok(person)
     }
 }
 
+/// Append a field name such as `author[1] name` for every person without a name to `fields`,
+/// each prefixed by `prefix`.
+pub(crate) fn unnamed_fields(
+    persons: &[Person],
+    element: &str,
+    prefix: &str,
+    fields: &mut Vec<String>,
+) {
+    for (index, person) in persons.iter().enumerate() {
+        if person.name.trim().is_empty() {
+            fields.push(format!("{}{}[{}] name", prefix, element, index));
+        }
+    }
+}
+
 impl ToXmlNamed for Person {
-    fn to_xml_named<W, N>(&self, writer: &mut Writer<W>, name: N) -> Result<(), XmlError>
+    fn to_xml_named<W, N>(&self, writer: &mut XmlWriter<'_, W>, name: N) -> Result<(), XmlError>
     where
         W: Write,
         N: AsRef<[u8]>,
     {
+        if self.name.trim().is_empty() && writer.options().unnamed_persons == UnnamedPersons::Skip {
+            return Ok(());
+        }
+
         let name = name.as_ref();
-        writer.write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
+        let mut element = BytesStart::borrowed(name, name.len());
+        element.extend_attributes(
+            self.unknown_attrs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
+        writer.write_event(Event::Start(element))?;
         writer.write_text_element(b"name", &*self.name)?;
 
         if let Some(ref email) = self.email {
-            writer.write_text_element(b"email", &*email)?;
+            writer.write_text_element(b"email", email)?;
         }
 
         if let Some(ref uri) = self.uri {
-            writer.write_text_element(b"uri", &*uri)?;
+            writer.write_text_element(b"uri", uri)?;
         }
 
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
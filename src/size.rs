@@ -0,0 +1,247 @@
+//! Cheap estimates of the serialized size of feed elements.
+//!
+//! Every element is counted as its start and end tags plus its escaped text, and every
+//! attribute as ` name=""` plus its escaped value. Escaping is assumed to expand each of
+//! `&`, `<`, `>`, `"` and `'` to a six byte entity, so the estimate errs on the large side for
+//! text that contains them. Dates are counted at the length of an RFC 3339 timestamp with an
+//! offset. The estimate ignores indentation and the XML declaration.
+
+use crate::category::Category;
+use crate::content::Content;
+use crate::entry::Entry;
+use crate::extension::Extension;
+use crate::feed::Feed;
+use crate::generator::Generator;
+use crate::link::Link;
+use crate::person::Person;
+use crate::source::Source;
+use crate::text::Text;
+
+/// The length of a date written as RFC 3339, e.g. `2017-06-03T15:15:44-05:00`.
+const DATE_LEN: usize = 25;
+
+pub(crate) trait SizeEstimate {
+    fn size_estimate(&self) -> usize;
+}
+
+impl<T: SizeEstimate> SizeEstimate for Option<T> {
+    fn size_estimate(&self) -> usize {
+        self.as_ref().map_or(0, SizeEstimate::size_estimate)
+    }
+}
+
+impl<T: SizeEstimate> SizeEstimate for Vec<T> {
+    fn size_estimate(&self) -> usize {
+        self.iter().map(SizeEstimate::size_estimate).sum()
+    }
+}
+
+fn escaped(text: &str) -> usize {
+    text.len() + 5 * text.bytes().filter(|b| b"&<>\"'".contains(b)).count()
+}
+
+/// `<name>` and `</name>`.
+fn tags(name: &str) -> usize {
+    2 * name.len() + 5
+}
+
+/// ` name="value"`.
+fn attr(name: &str, value: Option<&String>) -> usize {
+    value.map_or(0, |value| name.len() + 4 + escaped(value))
+}
+
+fn unknown_attrs(attrs: &[(String, String)]) -> usize {
+    attrs
+        .iter()
+        .map(|(name, value)| attr(name, Some(value)))
+        .sum()
+}
+
+/// `<!--comment-->` for each comment.
+fn comments(comments: &[String]) -> usize {
+    comments.iter().map(|comment| comment.len() + 7).sum()
+}
+
+fn text_element(name: &str, value: Option<&String>) -> usize {
+    value.map_or(0, |value| tags(name) + escaped(value))
+}
+
+fn date_element(name: &str) -> usize {
+    tags(name) + DATE_LEN
+}
+
+fn named_text(name: &str, text: &Text) -> usize {
+    tags(name)
+        + escaped(&text.value)
+        + attr("xml:base", text.base.as_ref())
+        + attr("xml:lang", text.lang.as_ref())
+        + " type=\"xhtml\"".len()
+        + unknown_attrs(&text.unknown_attrs)
+}
+
+fn named_texts(name: &str, text: Option<&Text>) -> usize {
+    text.map_or(0, |text| named_text(name, text))
+}
+
+fn people(name: &str, people: &[Person]) -> usize {
+    people
+        .iter()
+        .map(|person| {
+            tags(name)
+                + unknown_attrs(&person.unknown_attrs)
+                + text_element("name", Some(&person.name))
+                + text_element("email", person.email.as_ref())
+                + text_element("uri", person.uri.as_ref())
+        })
+        .sum()
+}
+
+impl SizeEstimate for Category {
+    fn size_estimate(&self) -> usize {
+        "<category/>".len()
+            + unknown_attrs(&self.unknown_attrs)
+            + attr("term", Some(&self.term))
+            + attr("scheme", self.scheme.as_ref())
+            + attr("label", self.label.as_ref())
+    }
+}
+
+impl SizeEstimate for Content {
+    fn size_estimate(&self) -> usize {
+        tags("content")
+            + unknown_attrs(&self.unknown_attrs)
+            + self.value.as_deref().map_or(0, escaped)
+            + attr("xml:base", self.base.as_ref())
+            + attr("xml:lang", self.lang.as_ref())
+            + attr("type", self.content_type.as_ref())
+            + attr("src", self.src.as_ref())
+    }
+}
+
+impl SizeEstimate for Extension {
+    fn size_estimate(&self) -> usize {
+        tags(&self.name)
+            + self.value.as_deref().map_or(0, escaped)
+            + self
+                .attrs
+                .iter()
+                .map(|(name, value)| attr(name, Some(value)))
+                .sum::<usize>()
+            + self
+                .children
+                .values()
+                .map(SizeEstimate::size_estimate)
+                .sum::<usize>()
+    }
+}
+
+impl SizeEstimate for Generator {
+    fn size_estimate(&self) -> usize {
+        text_element("generator", Some(&self.value))
+            + unknown_attrs(&self.unknown_attrs)
+            + attr("uri", self.uri.as_ref())
+            + attr("version", self.version.as_ref())
+    }
+}
+
+impl SizeEstimate for Link {
+    fn size_estimate(&self) -> usize {
+        "<link/>".len()
+            + unknown_attrs(&self.unknown_attrs)
+            + attr("href", Some(&self.href))
+            + attr("rel", Some(&self.rel))
+            + attr("hreflang", self.hreflang.as_ref())
+            + attr("type", self.mime_type.as_ref())
+            + attr("title", self.title.as_ref())
+            + attr("length", self.length.as_ref())
+            + if self.thr_count.is_some() || self.thr_updated.is_some() {
+                " xmlns:thr=\"http://purl.org/syndication/thread/1.0\"".len()
+            } else {
+                0
+            }
+            + attr("thr:count", self.thr_count.as_ref())
+            + attr("thr:updated", self.thr_updated.as_ref())
+    }
+}
+
+impl SizeEstimate for Source {
+    fn size_estimate(&self) -> usize {
+        tags("source")
+            + unknown_attrs(&self.unknown_attrs)
+            + text_element("id", Some(&self.id))
+            + named_text("title", &self.title)
+            + date_element("updated")
+            + people("author", &self.authors)
+            + self.categories.size_estimate()
+            + people("contributor", &self.contributors)
+            + self.generator.size_estimate()
+            + text_element("icon", self.icon.as_ref())
+            + self.links.size_estimate()
+            + text_element("logo", self.logo.as_ref())
+            + named_texts("rights", self.rights.as_ref())
+            + named_texts("subtitle", self.subtitle.as_ref())
+    }
+}
+
+impl SizeEstimate for Entry {
+    fn size_estimate(&self) -> usize {
+        comments(&self.leading_comments)
+            + tags("entry")
+            + attr("xml:base", self.base.as_ref())
+            + attr("xml:lang", self.lang.as_ref())
+            + unknown_attrs(&self.unknown_attrs)
+            + comments(&self.comments)
+            + text_element("id", Some(&self.id))
+            + named_text("title", &self.title)
+            + date_element("updated")
+            + self.published.map_or(0, |_| date_element("published"))
+            + people("author", &self.authors)
+            + self.categories.size_estimate()
+            + people("contributor", &self.contributors)
+            + self.links.size_estimate()
+            + named_texts("rights", self.rights.as_ref())
+            + self.source.size_estimate()
+            + named_texts("summary", self.summary.as_ref())
+            + self.content.size_estimate()
+            + self
+                .extensions
+                .values()
+                .flat_map(|map| map.values())
+                .map(SizeEstimate::size_estimate)
+                .sum::<usize>()
+    }
+}
+
+impl SizeEstimate for Feed {
+    fn size_estimate(&self) -> usize {
+        tags("feed")
+            + unknown_attrs(&self.unknown_attrs)
+            + " xmlns=\"http://www.w3.org/2005/Atom\"".len()
+            + self
+                .namespaces
+                .iter()
+                .map(|(prefix, uri)| "xmlns:".len() + attr(prefix, Some(uri)))
+                .sum::<usize>()
+            + attr("xml:base", self.base.as_ref())
+            + attr("xml:lang", self.lang.as_ref())
+            + text_element("id", Some(&self.id))
+            + named_text("title", &self.title)
+            + date_element("updated")
+            + people("author", &self.authors)
+            + self.categories.size_estimate()
+            + people("contributor", &self.contributors)
+            + self.generator.size_estimate()
+            + text_element("icon", self.icon.as_ref())
+            + self.links.size_estimate()
+            + text_element("logo", self.logo.as_ref())
+            + named_texts("rights", self.rights.as_ref())
+            + named_texts("subtitle", self.subtitle.as_ref())
+            + self.entries.size_estimate()
+            + self
+                .extensions
+                .values()
+                .flat_map(|map| map.values())
+                .map(SizeEstimate::size_estimate)
+                .sum::<usize>()
+    }
+}
@@ -3,22 +3,20 @@ use std::io::{BufRead, Write};
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Reader;
-use quick_xml::Writer;
 
 use crate::category::Category;
 use crate::error::Error;
-use crate::fromxml::FromXml;
+use crate::fromxml::{FromXml, XmlReader};
 use crate::generator::Generator;
 use crate::link::Link;
 use crate::person::Person;
 use crate::text::Text;
-use crate::toxml::{ToXml, WriterExt};
-use crate::util::{atom_datetime, atom_text, default_fixed_datetime, FixedDateTime};
+use crate::toxml::{ToXml, WriterExt, XmlWriter};
+use crate::util::{atom_datetime, atom_text, default_fixed_datetime, unknown_attr, FixedDateTime};
 
 /// Represents the source of an Atom entry
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -57,6 +55,9 @@ pub struct Source {
     pub rights: Option<Text>,
     /// A human-readable description or subtitle for the feed.
     pub subtitle: Option<Text>,
+    /// Attributes that are not otherwise recognized, in the order they appeared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown_attrs: Vec<(String, String)>,
 }
 
 impl Source {
@@ -447,16 +448,57 @@ impl Source {
     {
         self.subtitle = subtitle.into()
     }
+
+    /// Return the attributes of this source that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// source.set_unknown_attrs(vec![("v:origin".to_string(), "mirror".to_string())]);
+    /// assert_eq!(source.unknown_attrs(), &[("v:origin".to_string(), "mirror".to_string())]);
+    /// ```
+    pub fn unknown_attrs(&self) -> &[(String, String)] {
+        &self.unknown_attrs
+    }
+
+    /// Set the attributes of this source that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// source.set_unknown_attrs(vec![("v:origin".to_string(), "mirror".to_string())]);
+    /// ```
+    pub fn set_unknown_attrs<V>(&mut self, unknown_attrs: V)
+    where
+        V: Into<Vec<(String, String)>>,
+    {
+        self.unknown_attrs = unknown_attrs.into();
+    }
 }
 
 impl FromXml for Source {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes<'_>) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut XmlReader<B>,
+        mut atts: Attributes<'_>,
+    ) -> Result<Self, Error> {
         let mut source = Source::default();
         let mut buf = Vec::new();
 
+        for att in atts.with_checks(false).flatten() {
+            if att.key != b"xmlns" {
+                source.unknown_attrs.push(unknown_attr(reader, &att)?);
+            }
+        }
+
         loop {
             match reader.read_event(&mut buf)? {
-                Event::Start(element) => match element.name() {
+                Event::Start(element) => match &*reader.element_name(element.name()) {
                     b"id" => source.id = atom_text(reader)?.unwrap_or_default(),
                     b"title" => source.title = Text::from_xml(reader, element.attributes())?,
                     b"updated" => {
@@ -478,7 +520,7 @@ impl FromXml for Source {
                     b"icon" => source.icon = atom_text(reader)?,
                     b"link" => source
                         .links
-                        .push(Link::from_xml(reader, element.attributes())?),
+                        .extend(Link::read_xml(reader, element.attributes())?),
                     b"logo" => source.logo = atom_text(reader)?,
                     b"rights" => {
                         source.rights = Some(Text::from_xml(reader, element.attributes())?)
@@ -486,7 +528,7 @@ impl FromXml for Source {
                     b"subtitle" => {
                         source.subtitle = Some(Text::from_xml(reader, element.attributes())?)
                     }
-                    n => reader.read_to_end(n, &mut Vec::new())?,
+                    _ => reader.skip_element(element.name())?,
                 },
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
@@ -501,12 +543,19 @@ impl FromXml for Source {
 }
 
 impl ToXml for Source {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError> {
         let name = b"source";
-        writer.write_event(Event::Start(BytesStart::borrowed(name, name.len())))?;
-        writer.write_object_named(&self.title, b"title")?;
+        let mut element = BytesStart::borrowed(name, name.len());
+        element.extend_attributes(
+            self.unknown_attrs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
+        writer.write_event(Event::Start(element))?;
         writer.write_text_element(b"id", &*self.id)?;
-        writer.write_text_element(b"updated", &self.updated.to_rfc3339())?;
+        writer.write_object_named(&self.title, b"title")?;
+        writer.write_text_element(b"updated", self.updated.to_rfc3339())?;
         writer.write_objects_named(&self.authors, "author")?;
         writer.write_objects(&self.categories)?;
         writer.write_objects_named(&self.contributors, "contributor")?;
@@ -515,21 +564,29 @@ impl ToXml for Source {
             writer.write_object(generator)?;
         }
 
-        if let Some(ref icon) = self.icon {
-            writer.write_text_element(b"icon", &**icon)?;
+        if let Some(icon) = self.icon.as_deref().filter(|icon| !icon.is_empty()) {
+            writer.write_text_element(b"icon", icon)?;
         }
 
         writer.write_objects(&self.links)?;
 
-        if let Some(ref logo) = self.logo {
-            writer.write_text_element(b"logo", &**logo)?;
+        if let Some(logo) = self.logo.as_deref().filter(|logo| !logo.is_empty()) {
+            writer.write_text_element(b"logo", logo)?;
         }
 
-        if let Some(ref rights) = self.rights {
+        if let Some(rights) = self
+            .rights
+            .as_ref()
+            .filter(|rights| !rights.value.is_empty())
+        {
             writer.write_object_named(rights, b"rights")?;
         }
 
-        if let Some(ref subtitle) = self.subtitle {
+        if let Some(subtitle) = self
+            .subtitle
+            .as_ref()
+            .filter(|subtitle| !subtitle.value.is_empty())
+        {
             writer.write_object_named(subtitle, b"subtitle")?;
         }
 
@@ -554,6 +611,7 @@ impl Default for Source {
             logo: None,
             rights: None,
             subtitle: None,
+            unknown_attrs: Vec::new(),
         }
     }
 }
@@ -0,0 +1,94 @@
+/// The components of a `tag:` URI (RFC 4151), such as
+/// `tag:example.com,2005-06-01:/blog/1#comments`.
+///
+/// Returned by [`Entry::id_tag_parts`](crate::Entry::id_tag_parts).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TagUri {
+    /// The domain name or email address that minted the tag, such as `example.com`.
+    pub authority: String,
+    /// The date the authority owned the name, as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`.
+    pub date: String,
+    /// The part chosen by the authority, such as `/blog/1`, which may be empty.
+    pub specific: String,
+    /// The fragment following a `#`, if any.
+    pub fragment: Option<String>,
+}
+
+/// Parse a `tag:` URI, returning `None` when the value is not a well-formed one.
+///
+/// The scheme is matched case-insensitively. The authority must be a domain name or an email
+/// address without whitespace, and the date must have a valid month and day when present.
+pub(crate) fn parse_tag_uri(value: &str) -> Option<TagUri> {
+    let scheme = value.get(..4)?;
+    if !scheme.eq_ignore_ascii_case("tag:") {
+        return None;
+    }
+
+    let mut parts = value[4..].splitn(2, ':');
+    let entity = parts.next()?;
+    let rest = parts.next()?;
+    let mut parts = entity.rsplitn(2, ',');
+    let date = parts.next()?;
+    let authority = parts.next()?;
+    if !is_authority(authority) || !is_date(date) {
+        return None;
+    }
+
+    let mut parts = rest.splitn(2, '#');
+    let specific = parts.next().unwrap_or(rest);
+    let fragment = parts.next().map(str::to_string);
+    if specific.chars().any(char::is_whitespace) {
+        return None;
+    }
+
+    Some(TagUri {
+        authority: authority.to_string(),
+        date: date.to_string(),
+        specific: specific.to_string(),
+        fragment,
+    })
+}
+
+/// Whether the value is a domain name or an email address.
+fn is_authority(value: &str) -> bool {
+    let domain = match value.rfind('@') {
+        Some(at) => {
+            let (local, domain) = (&value[..at], &value[at + 1..]);
+            let local_char =
+                |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(c);
+            if local.is_empty() || !local.chars().all(local_char) {
+                return false;
+            }
+            domain
+        }
+        None => value,
+    };
+
+    !domain.is_empty()
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// Whether the value is a date formatted as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`.
+fn is_date(value: &str) -> bool {
+    let digits =
+        |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+    let number = |part: &str, max: u32| {
+        digits(part, 2)
+            && part
+                .parse::<u32>()
+                .map(|n| (1..=max).contains(&n))
+                .unwrap_or(false)
+    };
+
+    match value.split('-').collect::<Vec<_>>()[..] {
+        [year] => digits(year, 4),
+        [year, month] => digits(year, 4) && number(month, 12),
+        [year, month, day] => digits(year, 4) && number(month, 12) && number(day, 31),
+        _ => false,
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::cmp::PartialEq;
 use std::convert::{AsRef, From};
 use std::io::{BufRead, Write};
@@ -7,16 +8,17 @@ use std::str::FromStr;
 use quick_xml::events::attributes::Attributes;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Error as XmlError;
-use quick_xml::Reader;
-use quick_xml::Writer;
 
 use crate::error::Error;
-use crate::fromxml::FromXml;
-use crate::toxml::ToXmlNamed;
-use crate::util::{atom_text, atom_xhtml};
+use crate::fromxml::{FromXml, XmlReader};
+#[cfg(feature = "html")]
+use crate::html::collapse_whitespace;
+use crate::html::decode_entities;
+use crate::toxml::{ToXmlNamed, XmlWriter};
+use crate::util::{atom_text_trimmed, atom_xhtml, unknown_attr};
 
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 /// Represents the value of the [`type` attribute of a text construct](https://tools.ietf.org/html/rfc4287#section-3.1.1)
 /// in an Atom feed, e.g. the type of the content stored in the element.
 pub enum TextType {
@@ -28,6 +30,7 @@ pub enum TextType {
     Xhtml,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for TextType {
     fn default() -> Self {
         TextType::Text
@@ -61,7 +64,7 @@ impl FromStr for TextType {
 }
 
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -81,6 +84,9 @@ pub struct Text {
     pub lang: Option<String>,
     /// Type of content stored in the element.
     pub r#type: TextType,
+    /// Attributes that are not otherwise recognized, in the order they appeared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown_attrs: Vec<(String, String)>,
 }
 
 impl Text {
@@ -115,6 +121,128 @@ impl Text {
     pub fn as_str(&self) -> &str {
         &self.value
     }
+
+    /// Return the value of this text construct as a `Cow`.
+    ///
+    /// The value is always borrowed for now, since a `Text` owns its value. This lets code be
+    /// written against a `Cow` today so it keeps working if values are ever borrowed from the
+    /// input; [`ParseStats`](crate::ParseStats) reports how many values could have been.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use atom_syndication::Text;
+    ///
+    /// let text = Text::plain("Fish & Chips");
+    /// assert!(matches!(text.as_cow(), Cow::Borrowed("Fish & Chips")));
+    /// ```
+    pub fn as_cow(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.value)
+    }
+
+    /// Return the value with character references decoded once more if it looks double
+    /// encoded.
+    ///
+    /// Some publishers escape their text twice, so `&amp;lt;b&amp;gt;` in the feed is read as
+    /// `&lt;b&gt;` rather than `<b>`. The value is considered double encoded when it contains no
+    /// raw `<` but does contain a character reference such as `&lt;`, `&amp;` or `&#39;`; in
+    /// that case the references are decoded, otherwise the value is returned unchanged.
+    ///
+    /// This is a heuristic meant for sources known to double encode. Text that legitimately
+    /// talks about entities, such as a plain text value of `use &amp; for an ampersand`, is
+    /// decoded as well, and escaped markup in HTML becomes live markup, so the result must be
+    /// sanitized like any other untrusted HTML before rendering it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Text;
+    ///
+    /// let text = Text::html("&lt;p&gt;Fish &amp;amp; Chips&lt;/p&gt;");
+    /// assert_eq!(text.decoded_html(), "<p>Fish &amp; Chips</p>");
+    ///
+    /// let text = Text::html("<p>Fish &amp; Chips</p>");
+    /// assert_eq!(text.decoded_html(), "<p>Fish &amp; Chips</p>");
+    /// ```
+    pub fn decoded_html(&self) -> Cow<'_, str> {
+        if self.value.contains('<') {
+            return Cow::Borrowed(&self.value);
+        }
+
+        let decoded = decode_entities(&self.value);
+        if decoded == self.value {
+            Cow::Borrowed(&self.value)
+        } else {
+            Cow::Owned(decoded)
+        }
+    }
+
+    /// Return `true` if the value contains something that looks like an HTML tag, such as
+    /// `<b>`, `</b>`, `<br/>` or `<!-- -->`, regardless of the type of this text construct.
+    ///
+    /// Some feeds leave out `type="html"` even though their text holds escaped markup, like
+    /// `<title>Foo &lt;b&gt;Bar&lt;/b&gt;</title>`, which is read as the plain text
+    /// `Foo <b>Bar</b>`. This heuristic helps decide whether to render such text as HTML; the
+    /// stored type is left unchanged.
+    ///
+    /// A `<` counts as the start of a tag when it is directly followed by a letter, `/` and a
+    /// letter, or `!`, and a `>` follows later in the value. Plain text that merely mentions a
+    /// tag, such as `Use the <b> element`, is therefore reported as HTML, while text like
+    /// `1 < 2 > 0` is not. Entity references such as `&amp;` are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Text;
+    ///
+    /// assert!(Text::plain("Foo <b>Bar</b>").looks_like_html());
+    /// assert!(!Text::plain("1 < 2 > 0").looks_like_html());
+    /// ```
+    pub fn looks_like_html(&self) -> bool {
+        let value = self.value.as_str();
+        value.match_indices('<').any(|(index, _)| {
+            let rest = &value[index + 1..];
+            let tag = if rest.starts_with('/') {
+                rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+            } else {
+                rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '!')
+            };
+            tag && rest.contains('>')
+        })
+    }
+
+    /// Return the attributes of this text construct that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Text;
+    ///
+    /// let mut text = Text::default();
+    /// text.set_unknown_attrs(vec![("v:style".to_string(), "bold".to_string())]);
+    /// assert_eq!(text.unknown_attrs(), &[("v:style".to_string(), "bold".to_string())]);
+    /// ```
+    pub fn unknown_attrs(&self) -> &[(String, String)] {
+        &self.unknown_attrs
+    }
+
+    /// Set the attributes of this text construct that are not otherwise recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Text;
+    ///
+    /// let mut text = Text::default();
+    /// text.set_unknown_attrs(vec![("v:style".to_string(), "bold".to_string())]);
+    /// ```
+    pub fn set_unknown_attrs<V>(&mut self, unknown_attrs: V)
+    where
+        V: Into<Vec<(String, String)>>,
+    {
+        self.unknown_attrs = unknown_attrs.into();
+    }
 }
 
 impl From<String> for Text {
@@ -157,26 +285,27 @@ impl PartialEq<Text> for str {
 
 impl FromXml for Text {
     fn from_xml<B: BufRead>(
-        reader: &mut Reader<B>,
+        reader: &mut XmlReader<B>,
         mut atts: Attributes<'_>,
     ) -> Result<Self, Error> {
         let mut text = Text::default();
 
         for att in atts.with_checks(false).flatten() {
             match att.key {
-                b"xml:base" => text.base = Some(att.unescape_and_decode_value(reader)?),
-                b"xml:lang" => text.lang = Some(att.unescape_and_decode_value(reader)?),
-                b"type" => text.r#type = att.unescape_and_decode_value(reader)?.parse()?,
-                _ => {}
+                b"xml:base" => text.base = Some(reader.unescape_attr(&att)?),
+                b"xml:lang" => text.lang = Some(reader.unescape_attr(&att)?),
+                b"type" => text.r#type = reader.unescape_attr(&att)?.parse()?,
+                b"xmlns" => {}
+                _ => text.unknown_attrs.push(unknown_attr(reader, &att)?),
             }
         }
 
-        let content = if text.r#type == TextType::Xhtml {
-            atom_xhtml(reader)?
-        } else {
-            atom_text(reader)?
+        // Trimming is applied while reading, so `max_text_len` applies to the trimmed value.
+        let content = match text.r#type {
+            TextType::Text => atom_text_trimmed(reader, reader.options().trim_text)?,
+            TextType::Html => atom_text_trimmed(reader, reader.options().trim_html)?,
+            TextType::Xhtml => atom_xhtml(reader)?,
         };
-
         text.value = content.unwrap_or_default();
 
         Ok(text)
@@ -184,7 +313,7 @@ impl FromXml for Text {
 }
 
 impl ToXmlNamed for Text {
-    fn to_xml_named<W, N>(&self, writer: &mut Writer<W>, name: N) -> Result<(), XmlError>
+    fn to_xml_named<W, N>(&self, writer: &mut XmlWriter<'_, W>, name: N) -> Result<(), XmlError>
     where
         W: Write,
         N: AsRef<[u8]>,
@@ -200,12 +329,22 @@ impl ToXmlNamed for Text {
         if self.r#type != TextType::default() {
             element.push_attribute(("type", self.r#type.as_str()));
         }
+        element.extend_attributes(
+            self.unknown_attrs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+
         writer.write_event(Event::Start(element))?;
-        if self.r#type == TextType::Xhtml {
-            writer.write_event(Event::Text(BytesText::from_escaped(self.value.as_bytes())))?;
-        } else {
-            writer.write_event(Event::Text(BytesText::from_plain_str(self.value.as_str())))?;
-        }
+        let text = match self.r#type {
+            TextType::Xhtml => BytesText::from_escaped(self.value.as_bytes()),
+            #[cfg(feature = "html")]
+            TextType::Html if writer.options().collapse_html_whitespace => {
+                BytesText::from_plain_str(&collapse_whitespace(&self.value)).into_owned()
+            }
+            _ => BytesText::from_plain_str(self.value.as_str()),
+        };
+        writer.write_event(Event::Text(text))?;
         writer.write_event(Event::End(BytesEnd::borrowed(name)))?;
 
         Ok(())
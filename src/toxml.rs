@@ -1,28 +1,160 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::io::Write;
 
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Error as XmlError;
 use quick_xml::Writer;
 
+use crate::options::WriteOptions;
+
+/// A `Writer` that applies the `WriteOptions` to the events written through it.
+pub struct XmlWriter<'a, W: Write> {
+    writer: Writer<W>,
+    options: WriteOptions<'a>,
+    /// The namespaces declared on the root element, keyed by prefix.
+    namespaces: BTreeMap<String, String>,
+    /// The number of entries started so far.
+    entries: usize,
+}
+
+impl<'a, W: Write> XmlWriter<'a, W> {
+    pub fn new(writer: Writer<W>, options: WriteOptions<'a>) -> Self {
+        XmlWriter {
+            writer,
+            options,
+            namespaces: BTreeMap::new(),
+            entries: 0,
+        }
+    }
+
+    /// Return the index of the entry being started in the feed, counting from 0.
+    pub fn start_entry(&mut self) -> usize {
+        self.entries += 1;
+        self.entries - 1
+    }
+
+    /// Record a namespace declared on the root element.
+    pub fn declare_namespace(&mut self, prefix: &str, namespace: &str) {
+        self.namespaces
+            .insert(prefix.to_string(), namespace.to_string());
+    }
+
+    /// Return whether a prefix is bound to any namespace on the root element.
+    pub fn is_prefix_declared(&self, prefix: &str) -> bool {
+        self.namespaces.contains_key(prefix)
+    }
+
+    /// Return whether a prefix is bound to the given namespace on the root element.
+    pub fn is_declared(&self, prefix: &str, namespace: &str) -> bool {
+        self.namespaces.get(prefix).map(String::as_str) == Some(namespace)
+    }
+
+    pub fn options(&self) -> &WriteOptions<'a> {
+        &self.options
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    #[cfg(feature = "tokio")]
+    pub fn inner(&mut self) -> &mut W {
+        self.writer.inner()
+    }
+
+    /// Write an XML comment, separating hyphens that would otherwise end it early or make it
+    /// ill-formed.
+    pub fn write_comment(&mut self, comment: &str) -> Result<(), XmlError> {
+        let comment = escape_comment(comment);
+        self.write_event(Event::Comment(BytesText::from_escaped(comment.as_bytes())))
+    }
+
+    /// Write an event, adding the configured namespace prefix to unprefixed element names.
+    pub fn write_event<'e, E: AsRef<Event<'e>>>(&mut self, event: E) -> Result<(), XmlError> {
+        let prefix = match self.options.namespace_prefix {
+            Some(prefix) => prefix.as_bytes(),
+            None => return self.writer.write_event(event),
+        };
+
+        let prefixed = |name: &[u8]| -> Option<Vec<u8>> {
+            if name.contains(&b':') {
+                return None;
+            }
+
+            let mut prefixed = Vec::with_capacity(prefix.len() + 1 + name.len());
+            prefixed.extend_from_slice(prefix);
+            prefixed.push(b':');
+            prefixed.extend_from_slice(name);
+            Some(prefixed)
+        };
+
+        match *event.as_ref() {
+            Event::Start(ref element) | Event::Empty(ref element) => {
+                let name = match prefixed(element.name()) {
+                    Some(name) => name,
+                    None => return self.writer.write_event(event),
+                };
+                let name_len = name.len();
+                let mut content = name;
+                content.extend_from_slice(&element[element.name().len()..]);
+                let element = BytesStart::owned(content, name_len);
+
+                if let Event::Start(_) = *event.as_ref() {
+                    self.writer.write_event(Event::Start(element))
+                } else {
+                    self.writer.write_event(Event::Empty(element))
+                }
+            }
+            Event::End(ref element) => match prefixed(element.name()) {
+                Some(name) => self.writer.write_event(Event::End(BytesEnd::owned(name))),
+                None => self.writer.write_event(event),
+            },
+            _ => self.writer.write_event(event),
+        }
+    }
+}
+
+/// Insert a space after every hyphen that follows another one or ends the comment, since XML
+/// comments may neither contain `--` nor end with `-`.
+fn escape_comment(comment: &str) -> Cow<'_, str> {
+    if !comment.contains("--") && !comment.ends_with('-') {
+        return Cow::Borrowed(comment);
+    }
+
+    let mut escaped = String::with_capacity(comment.len() + 2);
+    for c in comment.chars() {
+        if c == '-' && escaped.ends_with('-') {
+            escaped.push(' ');
+        }
+        escaped.push(c);
+    }
+    if escaped.ends_with('-') {
+        escaped.push(' ');
+    }
+
+    Cow::Owned(escaped)
+}
+
 pub trait ToXml {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError>;
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError>;
 }
 
-impl<'a, T: ToXml> ToXml for &'a T {
-    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+impl<T: ToXml> ToXml for &T {
+    fn to_xml<W: Write>(&self, writer: &mut XmlWriter<'_, W>) -> Result<(), XmlError> {
         (*self).to_xml(writer)
     }
 }
 
 pub trait ToXmlNamed {
-    fn to_xml_named<W, N>(&self, writer: &mut Writer<W>, name: N) -> Result<(), XmlError>
+    fn to_xml_named<W, N>(&self, writer: &mut XmlWriter<'_, W>, name: N) -> Result<(), XmlError>
     where
         W: Write,
         N: AsRef<[u8]>;
 }
 
-impl<'a, T: ToXmlNamed> ToXmlNamed for &'a T {
-    fn to_xml_named<W, N>(&self, writer: &mut Writer<W>, name: N) -> Result<(), XmlError>
+impl<T: ToXmlNamed> ToXmlNamed for &T {
+    fn to_xml_named<W, N>(&self, writer: &mut XmlWriter<'_, W>, name: N) -> Result<(), XmlError>
     where
         W: Write,
         N: AsRef<[u8]>,
@@ -37,12 +169,6 @@ pub trait WriterExt {
         N: AsRef<[u8]>,
         T: AsRef<[u8]>;
 
-    fn write_text_elements<N, T, I>(&mut self, name: N, values: I) -> Result<(), XmlError>
-    where
-        N: AsRef<[u8]>,
-        T: AsRef<[u8]>,
-        I: IntoIterator<Item = T>;
-
     fn write_object<T>(&mut self, object: T) -> Result<(), XmlError>
     where
         T: ToXml;
@@ -64,7 +190,7 @@ pub trait WriterExt {
         N: AsRef<[u8]>;
 }
 
-impl<W: Write> WriterExt for Writer<W> {
+impl<W: Write> WriterExt for XmlWriter<'_, W> {
     fn write_text_element<N, T>(&mut self, name: N, text: T) -> Result<(), XmlError>
     where
         N: AsRef<[u8]>,
@@ -77,19 +203,6 @@ impl<W: Write> WriterExt for Writer<W> {
         Ok(())
     }
 
-    fn write_text_elements<N, T, I>(&mut self, name: N, values: I) -> Result<(), XmlError>
-    where
-        N: AsRef<[u8]>,
-        T: AsRef<[u8]>,
-        I: IntoIterator<Item = T>,
-    {
-        for value in values {
-            self.write_text_element(name.as_ref(), value)?;
-        }
-
-        Ok(())
-    }
-
     fn write_object<T>(&mut self, object: T) -> Result<(), XmlError>
     where
         T: ToXml,
@@ -1,18 +1,165 @@
+use std::borrow::Cow;
 use std::io::BufRead;
+use std::str;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::fromxml::XmlReader;
+use quick_xml::events::attributes::Attribute;
 use quick_xml::events::Event;
-use quick_xml::Reader;
 
 use crate::error::Error;
+use crate::options::TrimMode;
+use crate::warning::Warning;
 use std::str::FromStr;
 
 /// Alias of `::chrono::DateTime<::chrono::FixedOffset>`
+///
+/// Every timestamp carries its UTC offset and is written in RFC 3339 format with that offset,
+/// so written timestamps are always valid. Timestamps read without an offset are assumed to be
+/// in UTC, see
+/// [`ReadOptions::assume_utc_timestamps`](crate::ReadOptions::assume_utc_timestamps), and so
+/// are those set from a `SystemTime`. A naive timestamp from another source has to be given
+/// its offset when it is converted:
+///
+/// ```
+/// use atom_syndication::{Entry, FixedDateTime};
+/// use chrono::{FixedOffset, NaiveDate, TimeZone};
+///
+/// let naive = NaiveDate::from_ymd_opt(2021, 5, 1)
+///     .and_then(|date| date.and_hms_opt(12, 0, 0))
+///     .unwrap();
+/// let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+/// let updated: FixedDateTime = offset.from_local_datetime(&naive).unwrap();
+///
+/// let mut entry = Entry::default();
+/// entry.set_updated(updated);
+/// assert_eq!(entry.updated().to_rfc3339(), "2021-05-01T12:00:00+02:00");
+/// ```
 pub type FixedDateTime = ::chrono::DateTime<::chrono::FixedOffset>;
 
 pub fn default_fixed_datetime() -> FixedDateTime {
     FixedDateTime::from_str("1970-01-01T00:00:00Z").unwrap()
 }
 
+/// Convert a timestamp to a `SystemTime`, failing if it is out of the range of `SystemTime`.
+pub fn system_time(datetime: &FixedDateTime) -> Result<SystemTime, Error> {
+    let seconds = datetime.timestamp();
+    // The magnitude of the timestamp, which also holds for `i64::MIN`.
+    let magnitude = if seconds >= 0 {
+        seconds as u64
+    } else {
+        (seconds as u64).wrapping_neg()
+    };
+    let offset = Duration::new(magnitude, 0);
+    let time = if seconds >= 0 {
+        UNIX_EPOCH.checked_add(offset)
+    } else {
+        UNIX_EPOCH.checked_sub(offset)
+    };
+
+    time.and_then(|time| {
+        time.checked_add(Duration::from_nanos(
+            datetime.timestamp_subsec_nanos().into(),
+        ))
+    })
+    .ok_or_else(|| Error::WrongDatetime(datetime.to_rfc3339()))
+}
+
+/// Convert a `SystemTime` to a timestamp in UTC, keeping its fractional seconds.
+///
+/// Fails if the time is out of the range of `FixedDateTime`, which spans more than 200,000
+/// years around the epoch.
+pub fn from_system_time(time: SystemTime) -> Result<FixedDateTime, Error> {
+    use chrono::{TimeZone, Utc};
+    use std::convert::TryFrom;
+
+    let (seconds, nanos) = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => (
+            i64::try_from(duration.as_secs()).ok(),
+            duration.subsec_nanos(),
+        ),
+        Err(err) => {
+            let duration = err.duration();
+            let seconds = i64::try_from(duration.as_secs())
+                .ok()
+                .and_then(i64::checked_neg);
+            match duration.subsec_nanos() {
+                0 => (seconds, 0),
+                nanos => (
+                    seconds.and_then(|seconds| seconds.checked_sub(1)),
+                    1_000_000_000 - nanos,
+                ),
+            }
+        }
+    };
+
+    seconds
+        .and_then(|seconds| Utc.timestamp_opt(seconds, nanos).single())
+        .map(Into::into)
+        .ok_or_else(|| Error::WrongDatetime(format!("{:?}", time)))
+}
+
+/// Format a timestamp as used by RSS `pubDate`, failing for years RFC 822 cannot represent.
+pub fn rfc822(datetime: &FixedDateTime) -> Result<String, Error> {
+    use chrono::Datelike;
+
+    if (0..=9999).contains(&datetime.year()) {
+        Ok(datetime.to_rfc2822())
+    } else {
+        Err(Error::WrongDatetime(datetime.to_rfc3339()))
+    }
+}
+
+/// Derive a stable `urn:uuid:` URI from the seed using a version 5 UUID in the URL namespace.
+#[cfg(feature = "uuid")]
+pub fn uuid_urn(seed: &str) -> String {
+    uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_URL, seed.as_bytes())
+        .urn()
+        .to_string()
+}
+
+/// Decode an attribute that is kept as an unknown attribute.
+pub fn unknown_attr<B: BufRead>(
+    reader: &XmlReader<B>,
+    attr: &Attribute<'_>,
+) -> Result<(String, String), Error> {
+    let key = str::from_utf8(attr.key)?.to_string();
+    let value = reader.unescape_attr(attr)?;
+    Ok((key, value))
+}
+
+/// Whether the value is a syntactically valid URI reference as defined by RFC 3986.
+///
+/// Characters outside of ASCII are accepted so IRIs pass as well.
+pub fn is_uri_reference(value: &str) -> bool {
+    let forbidden = |c: char| c.is_whitespace() || c.is_control() || "<>\"{}|\\^`".contains(c);
+    if value.chars().any(forbidden) {
+        return false;
+    }
+
+    let bytes = value.as_bytes();
+    for (i, _) in value.match_indices('%') {
+        let valid = bytes.len() > i + 2
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit();
+        if !valid {
+            return false;
+        }
+    }
+
+    let end = value.find(['/', '?', '#']).unwrap_or(value.len());
+    match value[..end].find(':') {
+        Some(colon) => {
+            let scheme = &value[..colon];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        }
+        None => true,
+    }
+}
+
 fn non_empty(string: String) -> Option<String> {
     if !string.is_empty() {
         Some(string)
@@ -21,20 +168,143 @@ fn non_empty(string: String) -> Option<String> {
     }
 }
 
-pub fn atom_text<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, Error> {
+/// Return whether a character is XML whitespace: a space, tab, carriage return or line feed.
+fn is_xml_space(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\r' || c == '\n'
+}
+
+/// A text value being read, trimmed as it is accumulated so that `max_text_len` applies to the
+/// value after trimming.
+struct TextValue {
+    value: String,
+    mode: TrimMode,
+    /// With `TrimMode::Ends`, the whitespace after the value so far, which is only kept once
+    /// more text follows it.
+    pending: String,
+    /// The most whitespace worth holding in `pending`, one byte more than `max_text_len`.
+    pending_cap: usize,
+    /// The length the value was truncated to, if it was.
+    truncated: Option<usize>,
+}
+
+impl TextValue {
+    fn new<B: BufRead>(reader: &XmlReader<B>, mode: TrimMode) -> Self {
+        TextValue {
+            value: String::new(),
+            mode,
+            pending: String::new(),
+            pending_cap: reader
+                .options()
+                .max_text_len
+                .map_or(std::usize::MAX, |limit| limit.saturating_add(1)),
+            truncated: None,
+        }
+    }
+
+    /// Append a text chunk, taking over the chunk's allocation when it has one, nothing has
+    /// been accumulated yet and the value is not trimmed.
+    ///
+    /// Large text values usually arrive as a single chunk, so this avoids copying an unescaped
+    /// chunk and growing the value through repeated reallocation, while borrowed chunks are
+    /// copied once.
+    fn push(&mut self, text: Cow<'_, str>) {
+        match text {
+            Cow::Owned(text)
+                if self.mode == TrimMode::None
+                    && self.value.is_empty()
+                    && self.value.capacity() < text.len() =>
+            {
+                self.value = text
+            }
+            text => self.push_str(&text),
+        }
+    }
+
+    /// Append markup or text, trimming its whitespace.
+    fn push_str(&mut self, text: &str) {
+        if self.mode == TrimMode::None {
+            self.value.push_str(text);
+            return;
+        }
+
+        let mut rest = text;
+        while !rest.is_empty() {
+            let content_start = rest.find(|c| !is_xml_space(c)).unwrap_or(rest.len());
+            if content_start > 0 {
+                match self.mode {
+                    TrimMode::Collapse if !self.value.ends_with(' ') => self.value.push(' '),
+                    TrimMode::Ends if !self.value.is_empty() => {
+                        let space = &rest[..content_start];
+                        let len = space.len().min(self.pending_cap - self.pending.len());
+                        self.pending.push_str(&space[..len]);
+                    }
+                    _ => {}
+                }
+            }
+
+            rest = &rest[content_start..];
+            let content_end = rest.find(is_xml_space).unwrap_or(rest.len());
+            if content_end > 0 {
+                self.value.push_str(&self.pending);
+                self.pending.clear();
+                self.value.push_str(&rest[..content_end]);
+            }
+            rest = &rest[content_end..];
+        }
+    }
+
+    /// Enforce `max_text_len` on the value, failing or truncating it once it is too long.
+    fn limit_len<B: BufRead>(&mut self, reader: &mut XmlReader<B>) -> Result<(), Error> {
+        if let Some(len) = self.truncated {
+            self.value.truncate(len);
+            return Ok(());
+        }
+        let limit = match reader.options().max_text_len {
+            Some(limit) if self.value.len() > limit => limit,
+            _ => return Ok(()),
+        };
+        if !reader.options().truncate_long_text {
+            return Err(Error::TextTooLong);
+        }
+
+        let mut end = limit;
+        while !self.value.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.value.truncate(end);
+        self.truncated = Some(end);
+        let position = reader.buffer_position();
+        reader.warn(Warning::TextTruncated { limit, position });
+        Ok(())
+    }
+
+    fn into_value(self) -> Option<String> {
+        non_empty(self.value)
+    }
+}
+
+pub fn atom_text<B: BufRead>(reader: &mut XmlReader<B>) -> Result<Option<String>, Error> {
+    atom_text_trimmed(reader, TrimMode::None)
+}
+
+/// Read a text value, trimming its whitespace as `mode` does before `max_text_len` is applied.
+pub fn atom_text_trimmed<B: BufRead>(
+    reader: &mut XmlReader<B>,
+    mode: TrimMode,
+) -> Result<Option<String>, Error> {
     reader.expand_empty_elements(false);
 
     let mut innerbuf = Vec::new();
     let mut depth = 0;
-    let mut result = String::new();
+    let mut result = TextValue::new(reader, mode);
 
     loop {
         match reader.read_event(&mut innerbuf)? {
             Event::Start(start) => {
                 depth += 1;
-                result.push('<');
-                result.push_str(&start.unescape_and_decode(reader)?);
-                result.push('>');
+                result.push_str("<");
+                result.push_str(&reader.unescape_start(&start)?);
+                result.push_str(">");
             }
             Event::End(end) => {
                 if depth <= 0 {
@@ -43,23 +313,23 @@ pub fn atom_text<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, E
                 depth -= 1;
                 result.push_str("</");
                 result.push_str(&reader.decode(end.name()));
-                result.push('>');
+                result.push_str(">");
             }
             Event::Empty(start) => {
-                result.push('<');
-                result.push_str(&start.unescape_and_decode(reader)?);
+                result.push_str("<");
+                result.push_str(&reader.unescape_start(&start)?);
                 result.push_str("/>");
             }
-            Event::CData(text) => {
-                let decoded = text.unescape_and_decode(reader)?;
-                result.push_str(&decoded);
+            Event::CData(text) if reader.options().raw_text => {
+                result.push_str("<![CDATA[");
+                result.push_str(&reader.decode(&text.unescaped()?));
+                result.push_str("]]>");
             }
-            Event::Text(text) => {
-                let decoded = text.unescape_and_decode(reader)?;
-                result.push_str(&decoded);
+            Event::CData(text) | Event::Text(text) => {
+                result.push(reader.unescape_text(&text)?);
             }
             Event::Comment(text) => {
-                let decoded = text.unescape_and_decode(reader)?;
+                let decoded = reader.unescape_text(&text)?;
                 result.push_str("<!--");
                 result.push_str(&decoded);
                 result.push_str("-->");
@@ -70,28 +340,29 @@ pub fn atom_text<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, E
             Event::Eof => return Err(Error::Eof),
         }
 
+        result.limit_len(reader)?;
         innerbuf.clear();
     }
 
     reader.expand_empty_elements(true);
 
-    Ok(non_empty(result))
+    Ok(result.into_value())
 }
 
-pub fn atom_xhtml<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, Error> {
+pub fn atom_xhtml<B: BufRead>(reader: &mut XmlReader<B>) -> Result<Option<String>, Error> {
     reader.expand_empty_elements(false);
 
     let mut innerbuf = Vec::new();
     let mut depth = 0;
-    let mut result = String::new();
+    let mut result = TextValue::new(reader, TrimMode::None);
 
     loop {
         match reader.read_event(&mut innerbuf)? {
             Event::Start(start) => {
                 depth += 1;
-                result.push('<');
-                result.push_str(&start.unescape_and_decode(reader)?);
-                result.push('>');
+                result.push_str("<");
+                result.push_str(&reader.unescape_start(&start)?);
+                result.push_str(">");
             }
             Event::End(end) => {
                 if depth <= 0 {
@@ -100,23 +371,18 @@ pub fn atom_xhtml<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>,
                 depth -= 1;
                 result.push_str("</");
                 result.push_str(&reader.decode(end.name()));
-                result.push('>');
+                result.push_str(">");
             }
             Event::Empty(start) => {
-                result.push('<');
-                result.push_str(&start.unescape_and_decode(reader)?);
+                result.push_str("<");
+                result.push_str(&reader.unescape_start(&start)?);
                 result.push_str("/>");
             }
-            Event::CData(text) => {
-                let decoded = reader.decode(text.escaped());
-                result.push_str(&decoded);
-            }
-            Event::Text(text) => {
-                let decoded = reader.decode(text.escaped());
-                result.push_str(&decoded);
+            Event::CData(text) | Event::Text(text) => {
+                result.push(reader.decode(text.escaped()));
             }
             Event::Comment(text) => {
-                let decoded = text.unescape_and_decode(reader)?;
+                let decoded = reader.unescape_text(&text)?;
                 result.push_str("<!--");
                 result.push_str(&decoded);
                 result.push_str("-->");
@@ -127,33 +393,193 @@ pub fn atom_xhtml<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>,
             Event::Eof => return Err(Error::Eof),
         }
 
+        result.limit_len(reader)?;
         innerbuf.clear();
     }
 
     reader.expand_empty_elements(true);
 
-    Ok(non_empty(result))
+    Ok(result.into_value())
+}
+
+/// Read a timestamp given as the number of seconds since the Unix epoch, such as `1620000000`.
+pub fn atom_epoch<B: BufRead>(reader: &mut XmlReader<B>) -> Result<Option<FixedDateTime>, Error> {
+    use chrono::{TimeZone, Utc};
+
+    let text = match atom_text(reader)? {
+        Some(text) => text,
+        None => return Ok(None),
+    };
+
+    text.trim()
+        .parse::<i64>()
+        .ok()
+        .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single())
+        .map(|datetime| Some(datetime.into()))
+        .ok_or(Error::WrongDatetime(text))
 }
 
-pub fn atom_datetime<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<FixedDateTime>, Error> {
-    if let Some(datetime_text) = atom_text(reader)? {
-        match diligent_date_parser::parse_date(&datetime_text) {
+pub fn atom_datetime<B: BufRead>(
+    reader: &mut XmlReader<B>,
+) -> Result<Option<FixedDateTime>, Error> {
+    let datetime_text = match atom_text(reader)? {
+        Some(datetime_text) => datetime_text,
+        None => return Ok(None),
+    };
+
+    if let Some(datetime) = utc_datetime(&datetime_text) {
+        let options = reader.options();
+        let datetime = if options.assume_utc_timestamps {
+            Some(datetime)
+        } else if options.strict {
+            None
+        } else {
+            // The lenient parser also reads the formats it knows as times in UTC.
+            diligent_date_parser::parse_date(&datetime_text)
+        };
+
+        return match datetime {
+            Some(datetime) => {
+                reader.warn(Warning::AssumedUtc(datetime_text.trim().to_string()));
+                Ok(Some(datetime))
+            }
             None => Err(Error::WrongDatetime(datetime_text)),
-            Some(datetime) => Ok(Some(datetime)),
+        };
+    }
+
+    match diligent_date_parser::parse_date(&datetime_text) {
+        None => Err(Error::WrongDatetime(datetime_text)),
+        Some(datetime) => Ok(Some(datetime)),
+    }
+}
+
+/// Read the date of an RSS element that is mapped to an Atom one, see
+/// `ReadOptions::map_rss_dates`.
+///
+/// Unlike an Atom timestamp, a date that cannot be parsed is ignored with a warning, because
+/// the element would otherwise have been skipped.
+pub fn rss_datetime<B: BufRead>(
+    reader: &mut XmlReader<B>,
+    name: &str,
+) -> Result<Option<FixedDateTime>, Error> {
+    let position = reader.buffer_position();
+    Ok(atom_text(reader)?.and_then(|text| rss_date(reader, name, &text, position)))
+}
+
+/// Parse the text of an RSS date element that started at `position`, see [`rss_datetime`].
+pub fn rss_date<B: BufRead>(
+    reader: &mut XmlReader<B>,
+    name: &str,
+    text: &str,
+    position: usize,
+) -> Option<FixedDateTime> {
+    let datetime = parse_datetime(text);
+    if datetime.is_none() {
+        reader.warn(Warning::UnparseableRssDate {
+            name: name.to_string(),
+            value: text.trim().to_string(),
+            position,
+        });
+    }
+    datetime
+}
+
+/// The local names of RSS elements that have no Atom element of the same name.
+const RSS_ELEMENTS: &[&str] = &[
+    "channel",
+    "item",
+    "description",
+    "pubDate",
+    "lastBuildDate",
+    "guid",
+    "enclosure",
+    "comments",
+    "language",
+    "copyright",
+    "managingEditor",
+    "webMaster",
+    "docs",
+    "cloud",
+    "ttl",
+    "image",
+    "textInput",
+    "skipHours",
+    "skipDays",
+];
+
+/// Skip an unknown unprefixed element of `parent`, recording a warning if it is an RSS element.
+pub fn skip_unknown<B: BufRead>(
+    reader: &mut XmlReader<B>,
+    name: &[u8],
+    parent: &str,
+) -> Result<(), Error> {
+    if let Some(rss) = RSS_ELEMENTS
+        .iter()
+        .find(|rss| rss.as_bytes().eq_ignore_ascii_case(name))
+    {
+        let position = reader.buffer_position();
+        reader.warn(Warning::SkippedRssElement {
+            name: rss.to_string(),
+            parent: parent.to_string(),
+            position,
+        });
+    }
+    reader.skip_element(name)
+}
+
+/// Parse a date and time as it is accepted when reading, assuming UTC if it has no offset.
+pub fn parse_datetime(value: &str) -> Option<FixedDateTime> {
+    utc_datetime(value).or_else(|| diligent_date_parser::parse_date(value))
+}
+
+/// Parse a duration given as seconds, `MM:SS` or `HH:MM:SS`, where the seconds may have a
+/// fraction, such as `90`, `1:30` or `0:01:30.5`.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+
+    let parts = value.trim().split(':').collect::<Vec<_>>();
+    let (last, rest) = parts.split_last()?;
+    if rest.len() > 2 {
+        return None;
+    }
+    let (whole, fraction) = match last.find('.') {
+        Some(dot) if digits(&last[dot + 1..]) => (&last[..dot], &last[dot + 1..]),
+        Some(_) => return None,
+        None => (*last, ""),
+    };
+
+    let mut seconds = 0u64;
+    for part in rest.iter().chain(Some(&whole)) {
+        if !digits(part) {
+            return None;
         }
-    } else {
-        Ok(None)
+        seconds = seconds.checked_mul(60)?.checked_add(part.parse().ok()?)?;
     }
+
+    let nanos = format!("{:0<9}", &fraction[..fraction.len().min(9)]);
+    Some(Duration::new(seconds, nanos.parse().ok()?))
+}
+
+/// Parse an RFC 3339 date and time that lacks the UTC offset as a time in UTC.
+fn utc_datetime(value: &str) -> Option<FixedDateTime> {
+    use chrono::{FixedOffset, NaiveDateTime, TimeZone};
+
+    let value = value.trim();
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f"))
+        .ok()?;
+    Some(FixedOffset::east_opt(0)?.from_utc_datetime(&naive))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::error::Error;
+    use crate::fromxml::xml_reader;
+    use crate::options::ReadOptions;
 
     fn read_x(xml: &str) -> Result<Option<String>, Error> {
-        let mut reader = Reader::from_reader(xml.as_bytes());
-        reader.expand_empty_elements(true);
+        let mut reader = xml_reader(xml.as_bytes(), ReadOptions::default());
         loop {
             let mut buf = Vec::new();
             match reader.read_event(&mut buf)? {
@@ -191,4 +617,16 @@ mod test {
             r#"<div>a line<br/>&amp; one more</div>"#
         );
     }
+
+    #[test]
+    fn test_is_uri_reference() {
+        assert!(is_uri_reference("https://example.com/~jane?a=1#top"));
+        assert!(is_uri_reference("mailto:jane@example.com"));
+        assert!(is_uri_reference("/relative/path%20with%20space"));
+        assert!(is_uri_reference("http://example.com/caf\u{e9}"));
+        assert!(!is_uri_reference("not a uri"));
+        assert!(!is_uri_reference("1http://example.com"));
+        assert!(!is_uri_reference("http://example.com/%zz"));
+        assert!(!is_uri_reference("http://example.com/<script>"));
+    }
 }
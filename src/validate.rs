@@ -0,0 +1,628 @@
+use std::fmt;
+use std::io::BufRead;
+
+use quick_xml::events::Event;
+
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::extension::util::{extension_prefixes, well_known_namespace};
+use crate::feed::Feed;
+use crate::fromxml::{split_name, xml_reader, XmlReader, ATOM_NAMESPACE};
+use crate::link::{find_link, Link};
+use crate::options::{ReadOptions, UnnamedPersons, WriteOptions};
+use crate::person::unnamed_fields;
+use crate::util::{atom_text, default_fixed_datetime, FixedDateTime};
+
+/// A repair applied by [`Feed::validate_and_fix`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Fix {
+    /// The feed had no id, so a `urn:uuid` one was generated.
+    FeedId(String),
+    /// The feed had no `updated` timestamp, so the newest entry's one was used.
+    FeedUpdated(FixedDateTime),
+    /// The entry at `index` had no id, so a `urn:uuid` one was generated.
+    EntryId {
+        /// The position of the entry in the feed.
+        index: usize,
+        /// The generated id.
+        id: String,
+    },
+    /// The entry at `index` had no `updated` timestamp, so its `published` one was used.
+    EntryUpdated {
+        /// The position of the entry in the feed.
+        index: usize,
+        /// The timestamp that was copied from `published`.
+        updated: FixedDateTime,
+    },
+    /// A link had an empty `rel`, so it was set to `alternate`.
+    LinkRel {
+        /// The URI of the repaired link.
+        href: String,
+    },
+}
+
+impl fmt::Display for Fix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Fix::FeedId(ref id) => write!(f, "generated feed id {}", id),
+            Fix::FeedUpdated(ref updated) => {
+                write!(f, "set feed updated to {}", updated.to_rfc3339())
+            }
+            Fix::EntryId { index, ref id } => write!(f, "generated id {} for entry {}", id, index),
+            Fix::EntryUpdated { index, ref updated } => write!(
+                f,
+                "set updated of entry {} to {}",
+                index,
+                updated.to_rfc3339()
+            ),
+            Fix::LinkRel { ref href } => write!(f, "set rel of link {} to alternate", href),
+        }
+    }
+}
+
+/// A violation of RFC 4287 reported by [`Feed::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The feed has no id.
+    MissingFeedId,
+    /// The feed has no title.
+    MissingFeedTitle,
+    /// The feed has no `updated` timestamp.
+    MissingFeedUpdated,
+    /// The entry at the given index has no id.
+    MissingEntryId(usize),
+    /// The entry at the given index has no title.
+    MissingEntryTitle(usize),
+    /// The entry at the given index has no `updated` timestamp.
+    MissingEntryUpdated(usize),
+    /// Neither the entry at the given index, its source nor the feed has an author.
+    MissingAuthor(usize),
+    /// The entry at the given index has no content and no alternate link.
+    MissingAlternateLink(usize),
+    /// The document could not be read completely; the message describes the first error.
+    Malformed(String),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ValidationError::MissingFeedId => write!(f, "feed has no id"),
+            ValidationError::MissingFeedTitle => write!(f, "feed has no title"),
+            ValidationError::MissingFeedUpdated => write!(f, "feed has no updated timestamp"),
+            ValidationError::MissingEntryId(index) => write!(f, "entry {} has no id", index),
+            ValidationError::MissingEntryTitle(index) => {
+                write!(f, "entry {} has no title", index)
+            }
+            ValidationError::MissingEntryUpdated(index) => {
+                write!(f, "entry {} has no updated timestamp", index)
+            }
+            ValidationError::MissingAuthor(index) => write!(f, "entry {} has no author", index),
+            ValidationError::MissingAlternateLink(index) => write!(
+                f,
+                "entry {} has neither content nor an alternate link",
+                index
+            ),
+            ValidationError::Malformed(ref message) => write!(f, "malformed feed: {}", message),
+        }
+    }
+}
+
+/// A timestamp that is not a valid RFC 3339 date, reported by [`Feed::validate_timestamps`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TimestampIssue {
+    /// Where the timestamp appears, such as `feed updated`, `entry[3] published` or
+    /// `entry[3] source updated`. Entries are numbered from 0 in document order.
+    pub location: String,
+    /// The timestamp as it appears in the input, without surrounding whitespace.
+    pub value: String,
+}
+
+impl fmt::Display for TimestampIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is not an RFC 3339 timestamp: '{}'",
+            self.location, self.value
+        )
+    }
+}
+
+/// Return the local name of an element in the Atom namespace, or of an unprefixed element
+/// outside of any default namespace.
+fn atom_local_name<'n, B: BufRead>(reader: &XmlReader<B>, name: &'n [u8]) -> Option<&'n [u8]> {
+    let (prefix, local_name) = split_name(name);
+    match reader.resolve_prefix(prefix) {
+        Some(namespace) if namespace == ATOM_NAMESPACE.as_bytes() => Some(local_name),
+        Some(b"") | None if prefix.is_empty() => Some(local_name),
+        _ => None,
+    }
+}
+
+fn has_author(feed: &Feed, entry: &Entry) -> bool {
+    !entry.authors.is_empty()
+        || !feed.authors.is_empty()
+        || entry.source.iter().any(|source| !source.authors.is_empty())
+}
+
+fn fix_link_rels(links: &mut [Link], fixes: &mut Vec<Fix>) {
+    for link in links.iter_mut().filter(|link| link.rel.is_empty()) {
+        link.rel = "alternate".into();
+        fixes.push(Fix::LinkRel {
+            href: link.href.clone(),
+        });
+    }
+}
+
+#[cfg(feature = "uuid")]
+fn id_seed<'a>(links: &'a [Link], fallback: &'a str) -> &'a str {
+    links
+        .iter()
+        .find(|link| {
+            let rel = link.relation();
+            rel == "alternate" || rel == "self"
+        })
+        .map(|link| link.href.as_str())
+        .unwrap_or(fallback)
+}
+
+impl Entry {
+    /// Append the names of the persons of this entry and its source that have no name to
+    /// `fields`, for the entry at `index` of its feed.
+    pub(crate) fn unnamed_person_fields(&self, index: usize, fields: &mut Vec<String>) {
+        let prefix = format!("entry[{}] ", index);
+        unnamed_fields(&self.authors, "author", &prefix, fields);
+        unnamed_fields(&self.contributors, "contributor", &prefix, fields);
+
+        if let Some(ref source) = self.source {
+            let prefix = format!("entry[{}] source ", index);
+            unnamed_fields(&source.authors, "author", &prefix, fields);
+            unnamed_fields(&source.contributors, "contributor", &prefix, fields);
+        }
+    }
+
+    /// Append the names of the fields of this entry, at `index` of its feed, that are empty
+    /// but required by `options` to `fields`.
+    fn missing_write_fields(
+        &self,
+        index: usize,
+        options: &WriteOptions<'_>,
+        fields: &mut Vec<String>,
+    ) {
+        // Without the `uuid` feature no id can be generated in place of an empty one.
+        let id_required = if options.generate_missing_ids {
+            cfg!(not(feature = "uuid"))
+        } else {
+            options.require_fields
+        };
+        if id_required && self.id.trim().is_empty() {
+            fields.push(format!("entry[{}] id", index));
+        }
+
+        if options.require_fields && self.title.value.trim().is_empty() {
+            fields.push(format!("entry[{}] title", index));
+        }
+
+        if options.unnamed_persons == UnnamedPersons::Reject {
+            self.unnamed_person_fields(index, fields);
+        }
+    }
+
+    /// Check that this entry, at `index` of its feed, can be written with `options`, see
+    /// [`Feed::check_write`].
+    pub(crate) fn check_write(
+        &self,
+        index: usize,
+        options: &WriteOptions<'_>,
+        declared_on_feed: &dyn Fn(&str) -> bool,
+    ) -> Result<(), Error> {
+        let mut fields = Vec::new();
+        self.missing_write_fields(index, options, &mut fields);
+        if !fields.is_empty() {
+            return Err(Error::MissingRequiredField(fields));
+        }
+
+        match self.undeclared_prefix(declared_on_feed) {
+            Some(prefix) => Err(Error::UndeclaredPrefix(prefix.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Return a prefix of the extensions of this entry that is not declared on the entry, by
+    /// `declared_on_feed` for the feed containing it, or well known, if there is one.
+    pub(crate) fn undeclared_prefix(
+        &self,
+        declared_on_feed: &dyn Fn(&str) -> bool,
+    ) -> Option<&str> {
+        let namespaces = self.namespaces_in_scope();
+        extension_prefixes(&self.extensions)
+            .into_iter()
+            .find(|prefix| {
+                !namespaces.contains_key(*prefix)
+                    && !declared_on_feed(prefix)
+                    && well_known_namespace(prefix).is_none()
+            })
+    }
+}
+
+impl Feed {
+    /// Check this feed against the requirements of RFC 4287.
+    ///
+    /// The feed and every entry must have an id, a title and an `updated` timestamp, every
+    /// entry must have an author (either its own, its source's or the feed's) and an entry
+    /// without content must have an alternate link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ValidationError};
+    ///
+    /// let feed = Feed::default();
+    /// assert_eq!(
+    ///     feed.validate(),
+    ///     Err(vec![
+    ///         ValidationError::MissingFeedId,
+    ///         ValidationError::MissingFeedTitle,
+    ///         ValidationError::MissingFeedUpdated,
+    ///     ])
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let missing = default_fixed_datetime();
+
+        if self.id.is_empty() {
+            errors.push(ValidationError::MissingFeedId);
+        }
+
+        if self.title.is_empty() {
+            errors.push(ValidationError::MissingFeedTitle);
+        }
+
+        if self.updated == missing {
+            errors.push(ValidationError::MissingFeedUpdated);
+        }
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            if entry.id.is_empty() {
+                errors.push(ValidationError::MissingEntryId(index));
+            }
+
+            if entry.title.is_empty() {
+                errors.push(ValidationError::MissingEntryTitle(index));
+            }
+
+            if entry.updated == missing {
+                errors.push(ValidationError::MissingEntryUpdated(index));
+            }
+
+            if !has_author(self, entry) {
+                errors.push(ValidationError::MissingAuthor(index));
+            }
+
+            if entry.content.is_none() && find_link(&entry.links, "alternate").is_none() {
+                errors.push(ValidationError::MissingAlternateLink(index));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Return the names of the required elements of this feed and its entries that are empty,
+    /// as reported by [`Error::MissingRequiredField`].
+    pub(crate) fn missing_required_fields(&self) -> Vec<String> {
+        let errors = self.validate().err().unwrap_or_default();
+        errors
+            .into_iter()
+            .filter_map(|error| match error {
+                ValidationError::MissingFeedId => Some("id".to_string()),
+                ValidationError::MissingFeedTitle => Some("title".to_string()),
+                ValidationError::MissingFeedUpdated => Some("updated".to_string()),
+                ValidationError::MissingEntryId(index) => Some(format!("entry[{}] id", index)),
+                ValidationError::MissingEntryTitle(index) => {
+                    Some(format!("entry[{}] title", index))
+                }
+                ValidationError::MissingEntryUpdated(index) => {
+                    Some(format!("entry[{}] updated", index))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Return whether the prefix is declared on the root element when writing this feed with
+    /// `options`.
+    pub(crate) fn declares_prefix(&self, prefix: &str, options: &WriteOptions<'_>) -> bool {
+        self.namespaces.contains_key(prefix) || options.namespace_prefix == Some(prefix)
+    }
+
+    /// Check that this feed, and its entries if `with_entries` is set, can be written with
+    /// `options`, before anything is written.
+    ///
+    /// Fails with [`Error::MissingRequiredField`] naming the fields that are empty but required
+    /// by `options`, or with [`Error::UndeclaredPrefix`] naming a prefix of an extension that
+    /// would be written without being declared.
+    pub(crate) fn check_write(
+        &self,
+        options: &WriteOptions<'_>,
+        with_entries: bool,
+    ) -> Result<(), Error> {
+        let mut fields = Vec::new();
+        if options.require_fields {
+            if self.id.trim().is_empty() {
+                fields.push("id".to_string());
+            }
+            if self.title.value.trim().is_empty() {
+                fields.push("title".to_string());
+            }
+        }
+        if options.unnamed_persons == UnnamedPersons::Reject {
+            unnamed_fields(&self.authors, "author", "", &mut fields);
+            unnamed_fields(&self.contributors, "contributor", "", &mut fields);
+        }
+        if with_entries {
+            for (index, entry) in self.entries.iter().enumerate() {
+                entry.missing_write_fields(index, options, &mut fields);
+            }
+        }
+        if !fields.is_empty() {
+            return Err(Error::MissingRequiredField(fields));
+        }
+
+        let declared = |prefix: &str| self.declares_prefix(prefix, options);
+        let undeclared = extension_prefixes(&self.extensions)
+            .into_iter()
+            .find(|prefix| !declared(prefix) && well_known_namespace(prefix).is_none());
+        let undeclared = undeclared.or_else(|| {
+            self.entries
+                .iter()
+                .filter(|_| with_entries)
+                .find_map(|entry| entry.undeclared_prefix(&declared))
+        });
+
+        match undeclared {
+            Some(prefix) => Err(Error::UndeclaredPrefix(prefix.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Read an Atom feed from the reader and check it against the requirements of RFC 4287.
+    ///
+    /// The feed is read leniently with [`Feed::read_from_best_effort`], so a document that cannot
+    /// be read completely is reported as [`ValidationError::Malformed`], after the other errors
+    /// found in the part that was read. Either way the parsed feed is returned, alongside its
+    /// errors if there are any. See [`Feed::validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ValidationError};
+    ///
+    /// let xml = r#"
+    ///     <feed>
+    ///         <id>urn:feed</id>
+    ///         <title>Feed Title</title>
+    ///         <updated>2021-05-01T12:00:00Z</updated>
+    ///     </feed>
+    /// "#;
+    /// let feed = Feed::read_and_validate(xml.as_bytes()).unwrap();
+    /// assert_eq!(feed.title(), "Feed Title");
+    ///
+    /// let (feed, errors) = Feed::read_and_validate("<feed><title>Partial".as_bytes()).unwrap_err();
+    /// assert_eq!(feed.title(), "");
+    /// assert!(matches!(errors.last(), Some(ValidationError::Malformed(_))));
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn read_and_validate<B: BufRead>(reader: B) -> Result<Feed, (Feed, Vec<ValidationError>)> {
+        let (feed, error) = Feed::read_from_best_effort(reader);
+        let mut errors = feed.validate().err().unwrap_or_default();
+        if let Some(error) = error {
+            errors.push(ValidationError::Malformed(error.to_string()));
+        }
+
+        if errors.is_empty() {
+            Ok(feed)
+        } else {
+            Err((feed, errors))
+        }
+    }
+
+    /// Read an Atom document from the reader and return every `updated` and `published`
+    /// timestamp of the feed, its entries and their sources that is not a valid RFC 3339 date.
+    ///
+    /// Parsing a feed either rejects such timestamps or, for those lacking a UTC offset or
+    /// written in another format, silently accepts them, so the offending values are not kept
+    /// in a [`Feed`]. This scans the document instead, without building the feed or running
+    /// [`Feed::validate`]. Empty timestamps are reported with an empty value.
+    ///
+    /// Elements are matched in the Atom namespace, so an `updated` element of an extension is
+    /// not reported. As when reading a feed, unprefixed elements outside of any default
+    /// namespace are taken to be Atom elements.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the document is not well-formed XML or ends inside a timestamp.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let xml = r#"
+    ///     <feed>
+    ///         <updated>2021-05-01T12:00:00Z</updated>
+    ///         <entry><published>Sat, 01 May 2021 12:00:00 GMT</published></entry>
+    ///     </feed>
+    /// "#;
+    /// let issues = Feed::validate_timestamps(xml.as_bytes()).unwrap();
+    /// assert_eq!(issues.len(), 1);
+    /// assert_eq!(issues[0].location, "entry[0] published");
+    /// assert_eq!(issues[0].value, "Sat, 01 May 2021 12:00:00 GMT");
+    /// ```
+    pub fn validate_timestamps<B: BufRead>(reader: B) -> Result<Vec<TimestampIssue>, Error> {
+        let mut reader = xml_reader(reader, ReadOptions::default());
+        let mut buf = Vec::new();
+        let mut path: Vec<Vec<u8>> = Vec::new();
+        let mut entries = 0;
+        let mut issues = Vec::new();
+
+        loop {
+            match reader.read_event(&mut buf)? {
+                Event::Start(element) => {
+                    // Elements outside the Atom namespace never match the names below.
+                    let name = atom_local_name(&reader, element.name())
+                        .map(<[u8]>::to_vec)
+                        .unwrap_or_default();
+                    let parents = path.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                    let parent = match parents.as_slice() {
+                        [b"feed"] => Some("feed".to_string()),
+                        [b"feed", b"entry"] => Some(format!("entry[{}]", entries - 1)),
+                        [b"feed", b"entry", b"source"] => {
+                            Some(format!("entry[{}] source", entries - 1))
+                        }
+                        _ => None,
+                    };
+
+                    match (parent, &name[..]) {
+                        (Some(parent), b"updated") | (Some(parent), b"published") => {
+                            let value = atom_text(&mut reader)?.unwrap_or_default();
+                            let value = value.trim();
+                            if FixedDateTime::parse_from_rfc3339(value).is_err() {
+                                issues.push(TimestampIssue {
+                                    location: format!(
+                                        "{} {}",
+                                        parent,
+                                        String::from_utf8_lossy(&name)
+                                    ),
+                                    value: value.to_string(),
+                                });
+                            }
+                        }
+                        _ => {
+                            if path.len() == 1 && name == b"entry" {
+                                entries += 1;
+                            }
+                            path.push(name);
+                        }
+                    }
+                }
+                Event::End(_) => {
+                    path.pop();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+
+            buf.clear();
+        }
+
+        Ok(issues)
+    }
+
+    /// Repair common violations of RFC 4287 in place and return the repairs that were applied.
+    ///
+    /// The following repairs are performed:
+    ///
+    /// * links with an empty `rel` get the default `alternate` relation,
+    /// * entries without an `updated` timestamp get their `published` timestamp,
+    /// * a feed without an `updated` timestamp gets the newest entry `updated` timestamp,
+    /// * with the `uuid` feature, a feed or entry without an id gets a `urn:uuid` id derived
+    ///   from its alternate (or self) link, falling back to its title. Entry ids are also
+    ///   derived from the feed id, the entry timestamps and the entry position, so the same
+    ///   input always yields the same ids and entries sharing a title get distinct ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, Fix, FixedDateTime};
+    /// use std::str::FromStr;
+    ///
+    /// let published = FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap();
+    /// let mut entry = Entry::default();
+    /// entry.set_id("urn:example:1");
+    /// entry.set_published(published);
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_id("urn:example");
+    /// feed.set_entries(vec![entry]);
+    ///
+    /// let fixes = feed.validate_and_fix();
+    /// assert_eq!(fixes.len(), 2);
+    /// assert_eq!(fixes[1], Fix::FeedUpdated(published));
+    /// assert_eq!(feed.updated(), &published);
+    /// ```
+    pub fn validate_and_fix(&mut self) -> Vec<Fix> {
+        let mut fixes = Vec::new();
+        let missing = default_fixed_datetime();
+
+        fix_link_rels(&mut self.links, &mut fixes);
+
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            fix_link_rels(&mut entry.links, &mut fixes);
+
+            if entry.updated == missing {
+                if let Some(published) = entry.published {
+                    entry.updated = published;
+                    fixes.push(Fix::EntryUpdated {
+                        index,
+                        updated: published,
+                    });
+                }
+            }
+        }
+
+        if self.updated == missing {
+            let newest = self
+                .entries
+                .iter()
+                .map(|entry| entry.updated)
+                .filter(|updated| *updated != missing)
+                .max();
+
+            if let Some(updated) = newest {
+                self.updated = updated;
+                fixes.push(Fix::FeedUpdated(updated));
+            }
+        }
+
+        #[cfg(feature = "uuid")]
+        {
+            use crate::util::uuid_urn;
+
+            if self.id.is_empty() {
+                self.id = uuid_urn(id_seed(&self.links, &self.title));
+                fixes.push(Fix::FeedId(self.id.clone()));
+            }
+
+            for (index, entry) in self.entries.iter_mut().enumerate() {
+                if entry.id.is_empty() {
+                    let link_or_title = id_seed(&entry.links, &entry.title);
+                    let published = entry.published.map(|published| published.to_rfc3339());
+                    // The index and dates keep entries with the same link or title apart.
+                    let seed = format!(
+                        "{}#{}:{}\n{}\n{}\n{}",
+                        self.id,
+                        link_or_title.len(),
+                        link_or_title,
+                        entry.updated.to_rfc3339(),
+                        published.unwrap_or_default(),
+                        index
+                    );
+                    entry.id = uuid_urn(&seed);
+                    fixes.push(Fix::EntryId {
+                        index,
+                        id: entry.id.clone(),
+                    });
+                }
+            }
+        }
+
+        fixes
+    }
+}
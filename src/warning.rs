@@ -0,0 +1,97 @@
+use std::fmt;
+
+/// A problem in the input that was recovered from while reading.
+///
+/// Returned by [`Feed::read_with_warnings`](crate::Feed::read_with_warnings). The `Display`
+/// implementation describes the problem in a sentence, including the byte position in the input
+/// where it is known.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Warning {
+    /// A timestamp without a UTC offset, such as `2021-05-01T12:00:00`, was read as a time in
+    /// UTC.
+    AssumedUtc(String),
+    /// A text value longer than `ReadOptions::max_text_len` was truncated.
+    TextTruncated {
+        /// The maximum length in bytes.
+        limit: usize,
+        /// The byte position in the input after the text.
+        position: usize,
+    },
+    /// An RSS element with no Atom equivalent was skipped.
+    SkippedRssElement {
+        /// The name of the RSS element, such as `pubDate`.
+        name: String,
+        /// The name of the Atom element it was found in, `feed`, `entry` or `source`.
+        parent: String,
+        /// The byte position in the input after the start tag.
+        position: usize,
+    },
+    /// An RSS date element mapped to an Atom one could not be parsed and was ignored.
+    UnparseableRssDate {
+        /// The name of the RSS element, such as `pubDate`.
+        name: String,
+        /// The text of the element.
+        value: String,
+        /// The byte position in the input after the start tag.
+        position: usize,
+    },
+    /// A `link` element without an `href` attribute was skipped.
+    LinkWithoutHref {
+        /// The relation type of the link.
+        rel: String,
+        /// The byte position in the input after the start tag.
+        position: usize,
+    },
+    /// A malformed entry was skipped, see `ReadOptions::skip_malformed_entries`.
+    MalformedEntry {
+        /// The byte position in the input after the start tag of the entry.
+        position: usize,
+        /// The description of the error reading the entry.
+        error: String,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Warning::AssumedUtc(ref timestamp) => write!(
+                f,
+                "timestamp `{}` has no UTC offset, assuming UTC",
+                timestamp
+            ),
+            Warning::TextTruncated { limit, position } => write!(
+                f,
+                "truncated a text value longer than {} bytes at byte {}",
+                limit, position
+            ),
+            Warning::SkippedRssElement {
+                ref name,
+                ref parent,
+                position,
+            } => write!(
+                f,
+                "skipped RSS element <{}> in {} at byte {}",
+                name, parent, position
+            ),
+            Warning::UnparseableRssDate {
+                ref name,
+                ref value,
+                position,
+            } => write!(
+                f,
+                "ignored RSS element <{}> with unparseable date `{}` at byte {}",
+                name, value, position
+            ),
+            Warning::LinkWithoutHref { ref rel, position } => write!(
+                f,
+                "skipped a link with rel=\"{}\" and no href at byte {}",
+                rel, position
+            ),
+            Warning::MalformedEntry {
+                position,
+                ref error,
+            } => write!(f, "skipped malformed entry at byte {}: {}", position, error),
+        }
+    }
+}
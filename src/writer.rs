@@ -0,0 +1,133 @@
+use std::io::Write;
+
+use quick_xml::Writer;
+
+use crate::entry::Entry;
+use crate::error::Error;
+use crate::extension::ExtensionMap;
+use crate::feed::Feed;
+use crate::options::WriteOptions;
+use crate::toxml::{WriterExt, XmlWriter};
+
+/// Writes an Atom feed one entry at a time.
+///
+/// The metadata of the feed is written by [`FeedWriter::start`], every entry passed to
+/// [`FeedWriter::write_entry`] is written immediately, and [`FeedWriter::finish`] closes the
+/// document. Only the entry being written is held in memory, so feeds with millions of entries
+/// can be streamed from a database cursor straight to a file or socket.
+///
+/// The output is identical to writing a [`Feed`] that holds the same metadata and entries.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::{Entry, Feed, FeedWriter};
+///
+/// let mut metadata = Feed::default();
+/// metadata.set_title("Feed Title");
+///
+/// let mut writer = FeedWriter::start(Vec::new(), &metadata).unwrap();
+/// for id in 0..3 {
+///     let mut entry = Entry::default();
+///     entry.set_id(format!("urn:entry:{}", id));
+///     writer.write_entry(&entry).unwrap();
+/// }
+/// let xml = String::from_utf8(writer.finish().unwrap()).unwrap();
+///
+/// let feed = xml.parse::<Feed>().unwrap();
+/// assert_eq!(feed.title(), "Feed Title");
+/// assert_eq!(feed.entries().len(), 3);
+/// ```
+pub struct FeedWriter<'a, W: Write> {
+    writer: XmlWriter<'a, W>,
+    extensions: ExtensionMap,
+    /// The number of entries written so far.
+    entries: usize,
+}
+
+impl<'a, W: Write> FeedWriter<'a, W> {
+    /// Write the XML declaration and the metadata of a feed, without its entries.
+    ///
+    /// The entries of `metadata` are ignored. Its extensions are written by
+    /// [`FeedWriter::finish`], after the entries, as they are when writing a [`Feed`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FeedWriter};
+    ///
+    /// let writer = FeedWriter::start(Vec::new(), &Feed::default()).unwrap();
+    /// ```
+    pub fn start(writer: W, metadata: &Feed) -> Result<Self, Error> {
+        Self::start_with(writer, metadata, WriteOptions::default())
+    }
+
+    /// Write the XML declaration and the metadata of a feed using the given options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FeedWriter, WriteOptions};
+    ///
+    /// let mut options = WriteOptions::default();
+    /// options.namespace_prefix = Some("atom");
+    ///
+    /// let writer = FeedWriter::start_with(Vec::new(), &Feed::default(), options).unwrap();
+    /// let xml = String::from_utf8(writer.finish().unwrap()).unwrap();
+    /// assert!(xml.ends_with("</atom:feed>"));
+    /// ```
+    pub fn start_with(
+        writer: W,
+        metadata: &Feed,
+        options: WriteOptions<'a>,
+    ) -> Result<Self, Error> {
+        metadata.check_write(&options, false)?;
+
+        let mut writer = XmlWriter::new(Writer::new(writer), options);
+        metadata.write_prolog(&mut writer, None)?;
+        metadata.write_head(&mut writer)?;
+
+        Ok(FeedWriter {
+            writer,
+            extensions: metadata.extensions.clone(),
+            entries: 0,
+        })
+    }
+
+    /// Write an entry of the feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, FeedWriter};
+    ///
+    /// let mut writer = FeedWriter::start(Vec::new(), &Feed::default()).unwrap();
+    /// writer.write_entry(&Entry::default()).unwrap();
+    /// ```
+    pub fn write_entry(&mut self, entry: &Entry) -> Result<(), Error> {
+        let writer = &self.writer;
+        entry.check_write(self.entries, writer.options(), &|prefix| {
+            writer.is_prefix_declared(prefix)
+        })?;
+
+        self.writer.write_object(entry)?;
+        self.entries += 1;
+        Ok(())
+    }
+
+    /// Write the extensions of the feed and the closing `feed` tag, and return the writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FeedWriter};
+    ///
+    /// let writer = FeedWriter::start(Vec::new(), &Feed::default()).unwrap();
+    /// let xml = String::from_utf8(writer.finish().unwrap()).unwrap();
+    /// assert!(xml.ends_with("</feed>"));
+    /// ```
+    pub fn finish(mut self) -> Result<W, Error> {
+        Feed::write_tail(&self.extensions, &mut self.writer)?;
+        Ok(self.writer.into_inner())
+    }
+}
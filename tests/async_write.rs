@@ -0,0 +1,84 @@
+#![cfg(feature = "tokio")]
+
+use atom_syndication::{Entry, Feed};
+
+fn feed_with_entries(count: usize) -> Feed {
+    let mut feed = Feed::default();
+    feed.set_id("urn:feed");
+    feed.set_title("Feed Title");
+    feed.set_entries(
+        (1..=count)
+            .map(|i| {
+                let mut entry = Entry::default();
+                entry.set_id(format!("urn:entry:{}", i));
+                entry.set_title(format!("Entry {}", i));
+                entry
+            })
+            .collect::<Vec<_>>(),
+    );
+    feed
+}
+
+#[tokio::test]
+async fn write_to_async() {
+    let feed = feed_with_entries(3);
+
+    let mut xml = Vec::new();
+    feed.write_to_async(&mut xml).await.unwrap();
+
+    assert_eq!(xml, feed.write_to(Vec::new()).unwrap());
+}
+
+#[tokio::test]
+async fn write_to_async_missing_required_fields() {
+    use atom_syndication::Error;
+
+    let mut feed = feed_with_entries(1);
+    feed.set_id("");
+
+    let mut xml = Vec::new();
+    assert!(matches!(
+        feed.write_to_async(&mut xml).await,
+        Err(Error::MissingRequiredField(ref fields)) if *fields == ["id"]
+    ));
+    assert!(xml.is_empty());
+}
+
+#[tokio::test]
+async fn write_to_async_writes_each_entry() {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use tokio::io::AsyncWrite;
+
+    #[derive(Default)]
+    struct Chunks(Vec<Vec<u8>>);
+
+    impl AsyncWrite for Chunks {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.0.push(buf.to_vec());
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let feed = feed_with_entries(2);
+
+    let mut chunks = Chunks::default();
+    feed.write_to_async(&mut chunks).await.unwrap();
+
+    assert_eq!(chunks.0.len(), 4);
+    assert!(String::from_utf8_lossy(&chunks.0[1]).starts_with("<entry>"));
+    assert_eq!(chunks.0[3], b"</feed>");
+}
@@ -100,8 +100,8 @@ fn test_builders() {
             r#"
                 <?xml version="1.0"?>
                 <feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com">
-                    <title>Feed Title</title>
                     <id>urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6</id>
+                    <title>Feed Title</title>
                     <updated>2017-06-03T15:15:44-05:00</updated>
                     <author>
                         <name>John Doe</name>
@@ -125,9 +125,10 @@ fn test_builders() {
                     <rights>© 2017 John Doe</rights>
                     <subtitle>Feed subtitle</subtitle>
                     <entry>
-                        <title>Entry Title</title>
                         <id>http://example.com/article/1</id>
+                        <title>Entry Title</title>
                         <updated>2017-06-03T15:15:44-05:00</updated>
+                        <published>2017-06-01T15:15:44-05:00</published>
                         <author>
                             <name>John Doe</name>
                         </author>
@@ -144,11 +145,10 @@ fn test_builders() {
                         </contributor>
                         <link href="http://example.com/article/" rel="alternate"/>
                         <link href="http://example.com/audio.mp3" rel="enclosure" type="audio/mpeg" length="1000"/>
-                        <published>2017-06-01T15:15:44-05:00</published>
                         <rights>© 2017 John Doe</rights>
                         <source>
-                            <title>Entry Title</title>
                             <id>http://source.example.com/content/article/1</id>
+                            <title>Entry Title</title>
                             <updated>2017-06-03T15:15:44-05:00</updated>
                         </source>
                         <summary>Entry summary</summary>
@@ -160,3 +160,106 @@ fn test_builders() {
         )
     );
 }
+
+#[test]
+fn test_feed_entries_from_iterators() {
+    let ids = ["urn:1", "urn:2", "urn:3"];
+
+    let feed = FeedBuilder::default()
+        .entries(ids.iter().map(|id| EntryBuilder::default().id(*id).build()))
+        .build();
+    assert_eq!(feed.entries().len(), 3);
+
+    let feed = FeedBuilder::default()
+        .entries_from_builders(ids.iter().map(|id| {
+            let mut builder = EntryBuilder::default();
+            builder.id(*id);
+            builder
+        }))
+        .entry(EntryBuilder::default().id("urn:4").build())
+        .build();
+    let built = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.id())
+        .collect::<Vec<_>>();
+    assert_eq!(built, vec!["urn:1", "urn:2", "urn:3", "urn:4"]);
+}
+
+#[test]
+fn test_feed_default_generator() {
+    let feed = FeedBuilder::default().build();
+    assert!(feed.generator().is_none());
+
+    let feed = FeedBuilder::default().with_default_generator(true).build();
+    assert_eq!(feed.generator(), Some(&Generator::default_for_crate()));
+
+    let custom = GeneratorBuilder::default().value("Custom").build();
+    let feed = FeedBuilder::default()
+        .generator(custom.clone())
+        .with_default_generator(true)
+        .build();
+    assert_eq!(feed.generator(), Some(&custom));
+
+    let feed = FeedBuilder::default()
+        .with_default_generator(true)
+        .generator(custom.clone())
+        .build();
+    assert_eq!(feed.generator(), Some(&custom));
+
+    let feed = FeedBuilder::default()
+        .with_default_generator(true)
+        .with_default_generator(false)
+        .build();
+    assert!(feed.generator().is_none());
+
+    let feed = FeedBuilder::default()
+        .generator(None)
+        .with_default_generator(true)
+        .build();
+    assert!(feed.generator().is_none());
+}
+
+#[test]
+fn into_builder_round_trip() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com" xml:lang="en">
+            <title>Feed Title</title>
+            <id>urn:feed</id>
+            <updated>2021-05-01T12:00:00Z</updated>
+            <author><name>Jane Doe</name></author>
+            <link rel="self" href="http://example.com/feed.atom"/>
+            <ext:note>Kept</ext:note>
+            <entry>
+                <title>Entry Title</title>
+                <id>urn:entry</id>
+                <updated>2021-05-01T12:00:00Z</updated>
+                <category term="rust"/>
+                <content type="html">&lt;p&gt;Content&lt;/p&gt;</content>
+            </entry>
+        </feed>
+    "#;
+    let feed = Feed::from_str(xml).unwrap();
+
+    assert_eq!(feed.clone().into_builder().build(), feed);
+    let entry = feed.entries()[0].clone();
+    assert_eq!(entry.clone().into_builder().build(), entry);
+
+    let edited = feed
+        .clone()
+        .into_builder()
+        .title("New Title")
+        .entries(
+            feed.entries()
+                .iter()
+                .map(|entry| entry.clone().into_builder().id("urn:new").build()),
+        )
+        .build();
+    let written = Feed::from_str(&edited.to_string()).unwrap();
+    assert_eq!(written, edited);
+    assert_eq!(written.title(), "New Title");
+    assert_eq!(written.lang(), Some("en"));
+    assert_eq!(written.authors(), feed.authors());
+    assert_eq!(written.entries()[0].id(), "urn:new");
+    assert_eq!(written.entries()[0].categories(), entry.categories());
+}
@@ -0,0 +1,137 @@
+use atom_syndication::{Category, Feed};
+
+#[test]
+fn display_label() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <category term="rust" label="Rust Programming"/>
+            <category term="go"/>
+            <category term="empty" label=""/>
+            <category term="blank" label="  "/>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let labels = feed
+        .categories()
+        .iter()
+        .map(Category::display_label)
+        .collect::<Vec<_>>();
+    assert_eq!(labels, vec!["Rust Programming", "go", "empty", "blank"]);
+}
+
+#[test]
+fn has_category() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <category term="News"/>
+            <category term=""/>
+            <entry>
+                <category term="rust"/>
+                <category term="Straße"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    assert!(feed.has_category("News"));
+    assert!(!feed.has_category("news"));
+    assert!(feed.has_category_ci("news"));
+    assert!(!feed.has_category(""));
+    assert!(!feed.has_category_ci(""));
+
+    let entry = &feed.entries()[0];
+    assert!(entry.has_category("rust"));
+    assert!(!entry.has_category("News"));
+    assert!(entry.has_category_ci("RUST"));
+    assert!(entry.has_category_ci("STRAßE"));
+}
+
+#[test]
+fn write_optional_attributes() {
+    let term_only = Category {
+        term: "rust".to_string(),
+        ..Category::default()
+    };
+    let empty_attributes = Category {
+        scheme: Some(String::new()),
+        label: Some(String::new()),
+        ..term_only.clone()
+    };
+    let full = Category {
+        term: "rust".to_string(),
+        scheme: Some("http://example.com/tags".to_string()),
+        label: Some("Rust".to_string()),
+        ..Category::default()
+    };
+
+    let mut feed = Feed::default();
+    feed.set_categories(vec![term_only.clone(), empty_attributes, full.clone()]);
+    let xml = feed.to_string();
+    assert_eq!(xml.matches(r#"<category term="rust"/>"#).count(), 2);
+    assert!(
+        xml.contains(r#"<category term="rust" scheme="http://example.com/tags" label="Rust"/>"#)
+    );
+
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(feed.categories(), &[term_only.clone(), term_only, full]);
+}
+
+#[test]
+fn category_with_content() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <category term="x">ignored</category>
+            <category term="y"><category term="nested"/><b>ignored</b> text</category>
+            <title>Feed Title</title>
+            <entry>
+                <category term="z"><![CDATA[ignored]]></category>
+                <id>urn:1</id>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let terms = feed
+        .categories()
+        .iter()
+        .map(Category::term)
+        .collect::<Vec<_>>();
+    assert_eq!(terms, vec!["x", "y"]);
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.entries()[0].categories()[0].term(), "z");
+    assert_eq!(feed.entries()[0].id(), "urn:1");
+}
+
+#[test]
+fn category_frequencies() {
+    let feed = r#"
+        <feed>
+            <category term="web"/>
+            <category term="Rust"/>
+            <category term="rust"/>
+            <entry><category term="rust"/><category term="rust"/><category term=""/></entry>
+            <entry><category term="web"/><category term="rust"/></entry>
+            <entry><category term="async"/><category term="Rust"/></entry>
+            <entry/>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let terms = feed
+        .sorted_categories()
+        .iter()
+        .map(|category| category.term())
+        .collect::<Vec<_>>();
+    assert_eq!(terms, vec!["Rust", "rust", "web"]);
+
+    assert_eq!(
+        feed.category_frequencies(),
+        vec![("rust", 2), ("Rust", 1), ("async", 1), ("web", 1)]
+    );
+    assert!(Feed::default().category_frequencies().is_empty());
+}
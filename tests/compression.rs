@@ -0,0 +1,46 @@
+#![cfg(feature = "compression")]
+
+use std::io::Write;
+
+use atom_syndication::Feed;
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+
+const FEED: &str = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Compressed</title></feed>"#;
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn read_gzip() {
+    let feed = Feed::read_gzip(&gzip(FEED.as_bytes())[..]).unwrap();
+    assert_eq!(feed.title(), "Compressed");
+    assert!(Feed::read_gzip(FEED.as_bytes()).is_err());
+}
+
+#[test]
+fn read_deflate() {
+    let feed = Feed::read_deflate(&zlib(FEED.as_bytes())[..]).unwrap();
+    assert_eq!(feed.title(), "Compressed");
+}
+
+#[test]
+fn read_auto() {
+    let feed = Feed::read_auto(&gzip(FEED.as_bytes())[..]).unwrap();
+    assert_eq!(feed.title(), "Compressed");
+
+    let feed = Feed::read_auto(FEED.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "Compressed");
+
+    assert!(Feed::read_auto(&b"<"[..]).is_err());
+    assert!(Feed::read_auto(&b""[..]).is_err());
+}
@@ -94,3 +94,125 @@ fn content_text_xhtml() {
     );
     assert_eq!(content.content_type(), Some("xhtml"));
 }
+
+#[test]
+fn content_xml() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <content type="application/xml"><order xmlns="urn:orders" id="1"><item>A &lt; B</item><empty/></order></content>
+            </entry>
+            <entry>
+                <content type="text">&lt;b&gt;text&lt;/b&gt;</content>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let content = feed.entries()[0].content().unwrap();
+    assert_eq!(
+        content.xml_value(),
+        Some(r#"<order xmlns="urn:orders" id="1"><item>A &lt; B</item><empty/></order>"#)
+    );
+    assert_eq!(feed.entries()[1].content().unwrap().xml_value(), None);
+
+    let written = feed.to_string().parse::<Feed>().unwrap();
+    assert_eq!(written, feed);
+}
+
+#[test]
+fn content_src_empty_forms() {
+    let read = |content: &str| {
+        format!(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom"><entry>{}<title>After</title></entry></feed>"#,
+            content
+        )
+        .parse::<Feed>()
+        .unwrap()
+    };
+
+    let self_closed = read(r#"<content type="image/png" src="http://example.com/image.png"/>"#);
+    let expanded =
+        read(r#"<content type="image/png" src="http://example.com/image.png"></content>"#);
+    assert_eq!(self_closed, expanded);
+
+    let entry = &self_closed.entries()[0];
+    let content = entry.content().unwrap();
+    assert_eq!(content.src(), Some("http://example.com/image.png"));
+    assert_eq!(content.value(), None);
+    assert_eq!(entry.title(), "After");
+
+    let bytes = Feed::read_from(
+        &br#"<feed><entry><content src="http://example.com/image.png"/><title>After</title></entry></feed>"#[..],
+    )
+    .unwrap();
+    assert_eq!(bytes.entries()[0].content().unwrap().value(), None);
+    assert_eq!(bytes.entries()[0].title(), "After");
+}
+
+#[test]
+fn content_external_ref() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry><content type="image/jpeg" src="http://example.com/image.jpg">
+            </content></entry>
+            <entry><content src="http://example.com/page"/></entry>
+            <entry><content type="html">&lt;p&gt;Inline&lt;/p&gt;</content></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let contents = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.content().unwrap())
+        .collect::<Vec<_>>();
+
+    assert!(contents[0].is_external());
+    assert_eq!(contents[0].value(), None);
+    assert_eq!(
+        contents[0].external_ref(),
+        Some(("http://example.com/image.jpg", Some("image/jpeg")))
+    );
+    assert_eq!(
+        contents[1].external_ref(),
+        Some(("http://example.com/page", None))
+    );
+    assert!(!contents[2].is_external());
+    assert_eq!(contents[2].external_ref(), None);
+    assert_eq!(contents[2].value(), Some("<p>Inline</p>"));
+}
+
+#[test]
+fn xhtml_wrapper_div() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:xhtml="http://www.w3.org/1999/xhtml">
+            <entry>
+                <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><p>Default</p></div></content>
+                <summary type="xhtml"><xhtml:div><xhtml:div>Nested</xhtml:div></xhtml:div></summary>
+            </entry>
+            <entry>
+                <content type="xhtml"><xhtml:div><xhtml:p>Prefixed</xhtml:p></xhtml:div></content>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.content_html().as_deref(), Some("<p>Default</p>"));
+    assert_eq!(
+        entry.summary_html().as_deref(),
+        Some("<xhtml:div>Nested</xhtml:div>")
+    );
+    assert_eq!(
+        feed.entries()[1].content_html().as_deref(),
+        Some("<xhtml:p>Prefixed</xhtml:p>")
+    );
+    assert!(entry
+        .content_raw()
+        .unwrap()
+        .starts_with(r#"<div xmlns="http://www.w3.org/1999/xhtml">"#));
+}
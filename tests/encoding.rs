@@ -0,0 +1,49 @@
+#![cfg(feature = "encoding_rs")]
+
+use atom_syndication::{Entry, Feed, Text};
+
+#[test]
+fn write_to_encoding() {
+    let mut entry = Entry::default();
+    entry.set_id("urn:entry");
+    entry.set_title("Ünïcödé ☃");
+    entry.set_summary(Some(Text::html("<p>5 € &amp; 10 ¥</p>")));
+
+    let mut feed = Feed::default();
+    feed.set_id("urn:feed");
+    feed.set_title("Café – naïve");
+    feed.set_entries(vec![entry]);
+
+    let latin1 = encoding_rs::Encoding::for_label(b"ISO-8859-1").unwrap();
+    let xml = feed.write_to_encoding(Vec::new(), latin1).unwrap();
+    assert!(xml.starts_with(br#"<?xml version="1.0" encoding="windows-1252"?>"#));
+    assert!(xml.iter().all(|&b| b != 0xc3), "output must not be UTF-8");
+
+    let reread = Feed::read_from(&xml[..]).unwrap();
+    assert_eq!(reread, feed);
+
+    let xml = feed
+        .write_to_encoding(Vec::new(), encoding_rs::ISO_8859_7)
+        .unwrap();
+    let text = xml.iter().map(|&b| b as char).collect::<String>();
+    assert!(text.contains("&#9731;"));
+    assert_eq!(Feed::read_from(&xml[..]).unwrap(), feed);
+
+    let xml = feed
+        .write_to_encoding(Vec::new(), encoding_rs::UTF_16LE)
+        .unwrap();
+    assert!(xml.starts_with(br#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert_eq!(
+        String::from_utf8(xml).unwrap(),
+        feed.to_string().replacen(
+            "<?xml version=\"1.0\"?>",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+            1
+        )
+    );
+
+    feed.set_title("");
+    assert!(feed
+        .write_to_encoding(Vec::new(), encoding_rs::UTF_8)
+        .is_err());
+}
@@ -0,0 +1,921 @@
+use atom_syndication::{Category, Content, Entry, Feed, Text};
+
+fn content(content_type: &str, value: &str) -> Content {
+    let mut content = Content::default();
+    content.set_content_type(content_type.to_string());
+    content.set_value(value.to_string());
+    content
+}
+
+#[test]
+fn word_count() {
+    let mut entry = Entry::default();
+    assert_eq!(entry.word_count(), 0);
+    assert_eq!(entry.reading_time_minutes(200), 0);
+
+    entry.set_summary(Text::html("<p>A <b>short</b> summary</p>"));
+    assert_eq!(entry.word_count(), 3);
+
+    entry.set_content(content("html", "<p>One&nbsp;two</p><p>three</p>"));
+    assert_eq!(entry.word_count(), 3);
+
+    entry.set_content(content("text", "plain <text> content here"));
+    assert_eq!(entry.word_count(), 4);
+
+    entry.set_content(content("image/png", "iVBORw0KGgo="));
+    assert_eq!(entry.word_count(), 3);
+}
+
+#[test]
+fn reading_time_minutes() {
+    let words = vec!["word"; 401].join(" ");
+    let mut entry = Entry::default();
+    entry.set_content(content("text", &words));
+    assert_eq!(entry.reading_time_minutes(200), 3);
+    assert_eq!(entry.reading_time_minutes(401), 1);
+    assert_eq!(entry.reading_time_minutes(0), 0);
+}
+
+#[test]
+fn typed_setters() {
+    let mut entry = Entry::default();
+
+    entry.set_content_text("a < b");
+    entry.set_summary_text("plain");
+    let xml = write_entry(&entry);
+    assert!(xml.contains(r#"<content type="text">a &lt; b</content>"#));
+    assert!(xml.contains("<summary>plain</summary>"));
+
+    entry.set_content_html("<p>html</p>");
+    entry.set_summary_html("<b>html</b>");
+    let xml = write_entry(&entry);
+    assert!(xml.contains(r#"<content type="html">&lt;p&gt;html&lt;/p&gt;</content>"#));
+    assert!(xml.contains(r#"<summary type="html">&lt;b&gt;html&lt;/b&gt;</summary>"#));
+
+    entry.set_content_xhtml("<p>xhtml</p>");
+    let feed = write_entry(&entry).parse::<Feed>().unwrap();
+    let content = feed.entries()[0].content().unwrap();
+    assert_eq!(content.content_type(), Some("xhtml"));
+    assert_eq!(
+        content.value(),
+        Some(r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>xhtml</p></div>"#)
+    );
+}
+
+fn write_entry(entry: &Entry) -> String {
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry.clone()]);
+    feed.to_string()
+}
+
+#[test]
+fn image_url() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:media="http://search.yahoo.com/mrss/"
+              xmlns:mrss="http://search.yahoo.com/mrss/">
+            <entry>
+                <link rel="enclosure" type="image/jpeg" href="http://example.com/enclosure.jpg"/>
+                <media:thumbnail url="http://example.com/thumbnail.jpg"/>
+            </entry>
+            <entry>
+                <media:group>
+                    <media:thumbnail url="http://example.com/group.jpg"/>
+                </media:group>
+            </entry>
+            <entry>
+                <link rel="enclosure" type="audio/mpeg" href="http://example.com/audio.mp3"/>
+                <link rel="enclosure" type="image/png" href="http://example.com/enclosure.png"/>
+            </entry>
+            <entry xml:base="http://example.com/">
+                <content type="html" xml:base="posts/">&lt;img src="hero.png"&gt;</content>
+            </entry>
+            <entry>
+                <content type="text">&lt;img src="hero.png"&gt;</content>
+            </entry>
+            <entry>
+                <mrss:content><mrss:thumbnail url="http://example.com/prefix.jpg"/></mrss:content>
+            </entry>
+            <entry xmlns:media="http://example.com/not-media-rss">
+                <media:thumbnail url="http://example.com/other.jpg"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let images = feed
+        .entries()
+        .iter()
+        .map(Entry::image_url)
+        .collect::<Vec<_>>();
+    let content_image = if cfg!(feature = "url") {
+        "http://example.com/posts/hero.png"
+    } else {
+        "hero.png"
+    };
+    assert_eq!(
+        images,
+        vec![
+            Some("http://example.com/thumbnail.jpg".to_string()),
+            Some("http://example.com/group.jpg".to_string()),
+            Some("http://example.com/enclosure.png".to_string()),
+            Some(content_image.to_string()),
+            None,
+            Some("http://example.com/prefix.jpg".to_string()),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn base_and_lang() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry xml:base="http://example.com/blog/" xml:lang="en" data-id="7">
+                <id>urn:one</id>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.base(), Some("http://example.com/blog/"));
+    assert_eq!(entry.lang(), Some("en"));
+    assert_eq!(
+        entry.unknown_attrs(),
+        &[("data-id".to_string(), "7".to_string())][..]
+    );
+
+    let xml = write_entry(entry);
+    assert!(
+        xml.contains(r#"<entry xml:base="http://example.com/blog/" xml:lang="en" data-id="7">"#)
+    );
+    assert_eq!(xml.parse::<Feed>().unwrap().entries()[0], *entry);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn ensure_id_from() {
+    let mut first = Entry::default();
+    let mut second = Entry::default();
+    assert!(first.ensure_id_from("seed"));
+    assert!(second.ensure_id_from("seed"));
+    assert_eq!(first.id(), second.id());
+    assert!(first.id().starts_with("urn:uuid:"));
+
+    let mut other = Entry::default();
+    other.ensure_id_from("other seed");
+    assert_ne!(other.id(), first.id());
+
+    let mut existing = Entry::default();
+    existing.set_id("urn:existing");
+    assert!(!existing.ensure_id_from("seed"));
+    assert_eq!(existing.id(), "urn:existing");
+}
+
+#[test]
+fn effective_authors() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <author><name>Feed Author</name></author>
+            <entry>
+                <author><name>Entry Author</name></author>
+            </entry>
+            <entry></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let names = |entry: &Entry| {
+        entry
+            .effective_authors(&feed)
+            .iter()
+            .map(|person| person.name().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&feed.entries()[0]), vec!["Entry Author"]);
+    assert_eq!(names(&feed.entries()[1]), vec!["Feed Author"]);
+    assert!(Entry::default()
+        .effective_authors(&Feed::default())
+        .is_empty());
+}
+
+#[test]
+fn effective_authors_and_rights_with_source() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <author><name>Feed Author</name></author>
+            <rights>Feed Rights</rights>
+            <entry>
+                <author><name>Entry Author</name></author>
+                <rights>Entry Rights</rights>
+                <source>
+                    <author><name>Source Author</name></author>
+                    <rights>Source Rights</rights>
+                </source>
+            </entry>
+            <entry>
+                <source>
+                    <author><name>Source Author</name></author>
+                    <rights>Source Rights</rights>
+                </source>
+            </entry>
+            <entry><source><title>No Authors</title></source></entry>
+            <entry></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let effective = |entry: &Entry| {
+        let authors = entry
+            .effective_authors_with_source(&feed)
+            .iter()
+            .map(|person| person.name().to_string())
+            .collect::<Vec<_>>();
+        let rights = entry
+            .effective_rights_with_source(&feed)
+            .map(str::to_string);
+        (authors, rights)
+    };
+    let entries = feed.entries();
+    assert_eq!(
+        effective(&entries[0]),
+        (
+            vec!["Entry Author".to_string()],
+            Some("Entry Rights".to_string())
+        )
+    );
+    assert_eq!(
+        effective(&entries[1]),
+        (
+            vec!["Source Author".to_string()],
+            Some("Source Rights".to_string())
+        )
+    );
+    assert_eq!(
+        effective(&entries[2]),
+        (
+            vec!["Feed Author".to_string()],
+            Some("Feed Rights".to_string())
+        )
+    );
+    assert_eq!(
+        effective(&entries[3]),
+        (
+            vec!["Feed Author".to_string()],
+            Some("Feed Rights".to_string())
+        )
+    );
+}
+
+#[test]
+fn preserve_comments() {
+    use atom_syndication::ReadOptions;
+
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <!-- about the title -->
+            <title>Feed</title>
+            <!-- before the first entry -->
+            <entry>
+                <!-- inside the first entry -->
+                <id>urn:1</id>
+            </entry>
+            <entry><id>urn:2</id></entry>
+        </feed>
+    "#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert!(feed
+        .entries()
+        .iter()
+        .all(|entry| entry.comments().is_empty() && entry.leading_comments().is_empty()));
+
+    let mut options = ReadOptions::default();
+    options.preserve_comments = true;
+    let feed = Feed::read_with(xml.as_bytes(), options.clone()).unwrap();
+    assert_eq!(
+        feed.entries()[0].leading_comments(),
+        &[" before the first entry ".to_string()]
+    );
+    assert_eq!(
+        feed.entries()[0].comments(),
+        &[" inside the first entry ".to_string()]
+    );
+    assert!(feed.entries()[1].comments().is_empty());
+    assert!(feed.entries()[1].leading_comments().is_empty());
+
+    let written = feed.to_string();
+    assert!(written.contains(
+        "<!-- before the first entry --><entry><!-- inside the first entry --><id>urn:1</id>"
+    ));
+    let reread = Feed::read_with(written.as_bytes(), options.clone()).unwrap();
+    assert_eq!(reread.entries(), feed.entries());
+
+    let mut entry = Entry::default();
+    entry.set_leading_comments(vec!["ends with -".to_string()]);
+    entry.set_comments(vec!["a -- b".to_string(), "-->".to_string()]);
+    let written = write_entry(&entry);
+    assert!(written.contains("<!--ends with - --><entry><!--a - - b--><!--- ->-->"));
+    let reread = Feed::read_with(written.as_bytes(), options).unwrap();
+    assert_eq!(reread.entries()[0].comments(), &["a - - b", "- ->"]);
+}
+
+#[test]
+fn system_time() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry><updated>2021-05-01T12:00:00Z</updated></entry>
+            <entry><updated>2021-05-01T14:00:00+02:00</updated></entry>
+            <entry><updated>2021-05-01T07:30:00-04:30</updated></entry>
+            <entry>
+                <updated>2021-05-01T12:00:00.250Z</updated>
+                <published>1969-12-31T23:59:59Z</published>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let expected = UNIX_EPOCH + Duration::from_secs(1_619_870_400);
+    let entries = feed.entries();
+    assert_eq!(entries[0].updated_system_time().unwrap(), expected);
+    assert_eq!(entries[1].updated_system_time().unwrap(), expected);
+    assert_eq!(entries[2].updated_system_time().unwrap(), expected);
+    assert_eq!(
+        entries[3].updated_system_time().unwrap(),
+        expected + Duration::from_millis(250)
+    );
+
+    assert_eq!(entries[0].published_system_time().unwrap(), None);
+    assert_eq!(
+        entries[3].published_system_time().unwrap(),
+        Some(UNIX_EPOCH - Duration::from_secs(1))
+    );
+}
+
+#[test]
+fn set_updated_time() {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let times = [
+        UNIX_EPOCH,
+        UNIX_EPOCH + Duration::new(1_619_870_400, 250_000_000),
+        UNIX_EPOCH - Duration::new(1, 500),
+        SystemTime::now(),
+    ];
+
+    for time in times.iter().copied() {
+        let mut entry = Entry::default();
+        entry.set_updated_time(time).unwrap();
+        assert_eq!(entry.updated_system_time().unwrap(), time);
+
+        let mut feed = Feed::default();
+        feed.set_updated_time(time).unwrap();
+        feed.set_entries(vec![entry]);
+        assert_eq!(feed.updated_system_time().unwrap(), time);
+
+        let reread = feed.to_string().parse::<Feed>().unwrap();
+        assert_eq!(reread.updated_system_time().unwrap(), time);
+        assert_eq!(reread.entries()[0].updated_system_time().unwrap(), time);
+    }
+
+    let far = Duration::from_secs(1 << 50);
+    for time in UNIX_EPOCH
+        .checked_add(far)
+        .into_iter()
+        .chain(UNIX_EPOCH.checked_sub(far))
+    {
+        let mut entry = Entry::default();
+        assert!(entry.set_updated_time(time).is_err());
+        assert_eq!(entry, Entry::default());
+
+        let mut feed = Feed::default();
+        assert!(feed.set_updated_time(time).is_err());
+    }
+}
+
+#[test]
+fn comment_count() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom"
+              xmlns:slash="http://purl.org/rss/1.0/modules/slash/"
+              xmlns:thr="http://purl.org/syndication/thread/1.0">
+            <entry><slash:comments> 42 </slash:comments><thr:total>7</thr:total></entry>
+            <entry><thr:total>7</thr:total></entry>
+            <entry xmlns:s="http://purl.org/rss/1.0/modules/slash/"><s:comments>3</s:comments></entry>
+            <entry><slash:comments>many</slash:comments></entry>
+            <entry></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let counts = feed
+        .entries()
+        .iter()
+        .map(Entry::comment_count)
+        .collect::<Vec<_>>();
+    assert_eq!(counts, vec![Some(42), Some(7), Some(3), None, None]);
+}
+
+#[test]
+fn strip_to_metadata() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <id>urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a</id>
+                <title>Entry Title</title>
+                <updated>2017-06-03T15:15:44-05:00</updated>
+                <author><name>John Doe</name></author>
+                <category term="rust"/>
+                <link href="http://example.com/entry"/>
+                <summary>Entry summary</summary>
+                <content type="html">&lt;p&gt;Entry content&lt;/p&gt;</content>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let full = feed.entries()[0].clone();
+    let entry = full.clone().into_metadata();
+
+    assert!(entry.summary().is_none());
+    assert!(entry.content().is_none());
+    assert_eq!(entry.id(), full.id());
+    assert_eq!(entry.title(), full.title());
+    assert_eq!(entry.updated(), full.updated());
+    assert_eq!(entry.authors(), full.authors());
+    assert_eq!(entry.categories(), full.categories());
+    assert_eq!(entry.links(), full.links());
+}
+
+#[test]
+fn entries_updated_after() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry><id>1</id><updated>2021-05-01T12:00:00Z</updated></entry>
+            <entry><id>2</id><updated>2021-05-01T14:00:01+02:00</updated></entry>
+            <entry><id>3</id><updated>2021-05-02T00:00:00Z</updated></entry>
+            <entry><id>4</id><updated>1960-01-01T00:00:00Z</updated></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let mark = UNIX_EPOCH + Duration::from_secs(1_619_870_400);
+    let ids = |entries: Vec<&Entry>| {
+        entries
+            .iter()
+            .map(|e| e.id().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(feed.entries_updated_after(mark)), vec!["2", "3"]);
+    assert_eq!(
+        ids(feed.entries_updated_after(UNIX_EPOCH - Duration::from_secs(1))),
+        vec!["1", "2", "3"]
+    );
+    assert!(feed.entries()[3].is_updated_after(UNIX_EPOCH - Duration::from_secs(1_000_000_000)));
+    assert!(feed
+        .entries_updated_after(mark + Duration::from_secs(86_400))
+        .is_empty());
+}
+
+#[test]
+fn hash() {
+    use std::collections::HashSet;
+
+    use atom_syndication::{Category, FixedDateTime, Link, Person};
+
+    let mut entry = Entry::default();
+    entry.set_id("urn:1");
+    entry.set_updated(FixedDateTime::parse_from_rfc3339("2021-05-01T12:00:00Z").unwrap());
+
+    let mut same_instant = entry.clone();
+    same_instant
+        .set_updated(FixedDateTime::parse_from_rfc3339("2021-05-01T14:00:00+02:00").unwrap());
+
+    let mut edited = entry.clone();
+    edited.set_title("Edited");
+
+    let entries = vec![entry.clone(), same_instant, edited.clone()]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!(entries.len(), 2);
+    assert!(entries.contains(&entry) && entries.contains(&edited));
+    assert_eq!(entry.id_hash(), edited.id_hash());
+
+    let mut other = Entry::default();
+    other.set_id("urn:2");
+    assert_ne!(entry.id_hash(), other.id_hash());
+
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry]);
+    let feeds = vec![feed.clone(), feed, Feed::default()]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!(feeds.len(), 2);
+
+    let people = vec![Person::default(), Person::default()]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    let links = vec![Link::alternate("http://example.com/"), Link::default()]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    let categories = vec![Category::default()]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!((people.len(), links.len(), categories.len()), (1, 2, 1));
+}
+
+#[test]
+fn effective_rights() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <rights>© 2021 Feed Owner</rights>
+            <entry><rights type="html">&lt;b&gt;CC BY 4.0&lt;/b&gt;</rights></entry>
+            <entry></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let entries = feed.entries();
+    assert_eq!(entries[0].effective_rights(&feed), Some("<b>CC BY 4.0</b>"));
+    assert_eq!(
+        entries[1].effective_rights(&feed),
+        Some("© 2021 Feed Owner")
+    );
+    assert_eq!(entries[1].effective_rights(&Feed::default()), None);
+}
+
+#[test]
+fn author_names() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <author><name>Jane Doe</name></author>
+            <author><email>anonymous@example.com</email></author>
+            <entry>
+                <author><name>John Doe</name></author>
+                <author><name></name></author>
+                <author><name>Jane Doe</name></author>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    assert_eq!(feed.author_names(), vec!["Jane Doe"]);
+    assert_eq!(
+        feed.entries()[0].author_names(),
+        vec!["John Doe", "Jane Doe"]
+    );
+}
+
+#[test]
+fn set_categories_from_terms() {
+    let tags = vec!["rust".to_string(), "atom".to_string()];
+    let mut entry = Entry::default();
+    entry.set_categories_from_terms(tags);
+    assert_eq!(
+        entry.categories(),
+        &[
+            Category {
+                term: "rust".to_string(),
+                ..Category::default()
+            },
+            Category {
+                term: "atom".to_string(),
+                ..Category::default()
+            },
+        ]
+    );
+
+    let mut feed = Feed::default();
+    feed.set_categories_from_terms(vec!["news"]);
+    assert_eq!(feed.categories().len(), 1);
+    assert_eq!(feed.categories()[0].term(), "news");
+    assert_eq!(feed.categories()[0].scheme(), None);
+    assert_eq!(feed.categories()[0].label(), None);
+}
+
+#[test]
+fn consuming_setters() {
+    let feed = Feed::default()
+        .with_title("Feed Title")
+        .with_entries(vec![Entry::default().with_id("urn:1")])
+        .map_entries(|entry| {
+            entry
+                .with_title("Entry Title")
+                .with_content(content("html", "<p>Hello</p>"))
+                .with_summary(Text::plain("Hello"))
+        });
+
+    assert_eq!(feed.title(), "Feed Title");
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.id(), "urn:1");
+    assert_eq!(entry.title(), "Entry Title");
+    assert_eq!(
+        entry.content().and_then(|content| content.value()),
+        Some("<p>Hello</p>")
+    );
+    assert_eq!(entry.summary().map(Text::as_str), Some("Hello"));
+}
+
+#[test]
+fn published_or_updated() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <updated>2021-05-02T12:00:00Z</updated>
+                <published>2021-05-01T12:00:00Z</published>
+            </entry>
+            <entry>
+                <updated>2021-05-03T12:00:00Z</updated>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let dates = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.published_or_updated().to_rfc3339())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        dates,
+        vec!["2021-05-01T12:00:00+00:00", "2021-05-03T12:00:00+00:00"]
+    );
+}
+
+#[test]
+fn content_and_summary_html() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <summary>Fish &amp; "Chips"&#13;
+Served &lt;hot&gt;</summary>
+                <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><p>Fish</p></div></content>
+            </entry>
+            <entry>
+                <summary type="html">&lt;p&gt;Fish &amp;amp; Chips&lt;/p&gt;</summary>
+                <content type="image/svg+xml"><svg xmlns="http://www.w3.org/2000/svg"/></content>
+            </entry>
+            <entry>
+                <content src="http://example.com/article"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let entries = feed.entries();
+    assert_eq!(
+        entries[0].summary_html().as_deref(),
+        Some("Fish &amp; &quot;Chips&quot;<br>Served &lt;hot&gt;")
+    );
+    assert_eq!(entries[0].content_html().as_deref(), Some("<p>Fish</p>"));
+    assert_eq!(
+        entries[1].summary_html().as_deref(),
+        Some("<p>Fish &amp; Chips</p>")
+    );
+    assert_eq!(entries[1].content_html(), None);
+    assert_eq!(entries[2].content_html(), None);
+    assert_eq!(entries[2].summary_html(), None);
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn content_links() {
+    let feed = r##"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry xml:base="http://example.com/blog/">
+                <summary type="html">&lt;a href="posts/2"&gt;Two&lt;/a&gt; &lt;a href="https://example.org/"&gt;Org&lt;/a&gt;</summary>
+                <content type="xhtml" xml:base="posts/">
+                    <div xmlns="http://www.w3.org/1999/xhtml">
+                        <a href="1">One</a>
+                        <a href="#comments">Comments</a>
+                        <a HREF=" https://example.org/ ">Org</a>
+                        <a name="anchor">Anchor</a>
+                        <a href="">Empty</a>
+                    </div>
+                </content>
+            </entry>
+            <entry>
+                <summary>&lt;a href="http://example.com/plain"&gt;Plain&lt;/a&gt;</summary>
+            </entry>
+        </feed>
+    "##
+    .parse::<Feed>()
+    .unwrap();
+
+    let links = feed.entries()[0].content_links();
+    #[cfg(feature = "url")]
+    assert_eq!(
+        links,
+        vec![
+            "http://example.com/blog/posts/1",
+            "https://example.org/",
+            "http://example.com/blog/posts/2",
+        ]
+    );
+    #[cfg(not(feature = "url"))]
+    assert_eq!(links, vec!["1", "https://example.org/", "posts/2"]);
+    assert!(feed.entries()[1].content_links().is_empty());
+}
+
+#[test]
+fn set_link_replaces_by_rel() {
+    use atom_syndication::Link;
+
+    let mut entry = Entry::default();
+    entry.add_link(Link::self_link("http://example.com/entry"));
+    entry.add_link(Link::alternate("http://example.com/1"));
+    entry.add_link(Link::related("http://example.com/related"));
+    entry.add_link(Link::alternate("http://example.com/2"));
+    assert_eq!(entry.links().len(), 4);
+
+    entry.set_link(Link::alternate("http://example.com/3"));
+    let hrefs = entry.links().iter().map(Link::href).collect::<Vec<_>>();
+    assert_eq!(
+        hrefs,
+        vec![
+            "http://example.com/entry",
+            "http://example.com/3",
+            "http://example.com/related"
+        ]
+    );
+
+    let mut iana = Link::default();
+    iana.set_rel("http://www.iana.org/assignments/relation/self");
+    iana.set_href("http://example.com/moved");
+    entry.set_link(iana);
+    assert_eq!(entry.links().len(), 3);
+    assert_eq!(entry.links()[0].href(), "http://example.com/moved");
+
+    entry.set_link(Link::enclosure(
+        "http://example.com/a.mp3",
+        "audio/mpeg",
+        1024,
+    ));
+    assert_eq!(entry.links().len(), 4);
+    assert_eq!(entry.links()[3].rel(), "enclosure");
+}
+
+#[test]
+fn id_tag_parts() {
+    use atom_syndication::TagUri;
+
+    let parts = |id: &str| Entry::default().with_id(id).id_tag_parts();
+
+    assert_eq!(
+        parts(" tag:timothy@hpl.hp.com,2001:web/externalHome "),
+        Some(TagUri {
+            authority: "timothy@hpl.hp.com".to_string(),
+            date: "2001".to_string(),
+            specific: "web/externalHome".to_string(),
+            fragment: None,
+        })
+    );
+    assert_eq!(
+        parts("TAG:sandro.example.org,2005-06-12:a:b#frag"),
+        Some(TagUri {
+            authority: "sandro.example.org".to_string(),
+            date: "2005-06-12".to_string(),
+            specific: "a:b".to_string(),
+            fragment: Some("frag".to_string()),
+        })
+    );
+    assert_eq!(parts("tag:example.com,2005:").unwrap().specific, "");
+
+    for &id in &[
+        "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6",
+        "http://example.com/tag:example.com,2005:1",
+        "tag:example.com:1",
+        "tag:example.com,05:1",
+        "tag:example.com,2005-13:1",
+        "tag:example.com,2005-01-00:1",
+        "tag:example.com,2005-1:1",
+        "tag:,2005:1",
+        "tag:-example.com,2005:1",
+        "tag:exa mple.com,2005:1",
+        "tag:@example.com,2005:1",
+        "tag:example.com,2005",
+        "tag:example.com,2005:a b",
+        "tag",
+    ] {
+        assert_eq!(parts(id), None, "{}", id);
+    }
+}
+
+#[test]
+fn content_raw() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry><content type="text">1 &lt; 2</content></entry>
+            <entry><content type="html"><![CDATA[<script>x()</script><p>Hi</p>]]></content></entry>
+            <entry><content src="http://example.com/1.html"/></entry>
+            <entry><title>No content</title></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let raw = feed
+        .entries()
+        .iter()
+        .map(Entry::content_raw)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        raw,
+        vec![
+            Some("1 < 2"),
+            Some("<script>x()</script><p>Hi</p>"),
+            None,
+            None
+        ]
+    );
+    assert_eq!(
+        feed.entries()[0].content_html().as_deref(),
+        Some("1 &lt; 2")
+    );
+}
+
+#[test]
+fn generate_summary() {
+    let mut entry = Entry::default();
+    assert_eq!(entry.generate_summary(10), "");
+
+    entry.set_content(content(
+        "html",
+        "<p>Grüße aus</p>\n<p>Köln und Düsseldorf</p>",
+    ));
+    assert_eq!(entry.generate_summary(100), "Grüße aus Köln und Düsseldorf");
+    assert_eq!(entry.generate_summary(16), "Grüße aus Köln…");
+    assert_eq!(entry.generate_summary(4), "Grü…");
+
+    entry.set_content(content("text", "日本語 テキスト"));
+    assert_eq!(entry.generate_summary(6), "日本語…");
+
+    entry.set_summary(Text::plain("  A   real summary "));
+    assert_eq!(entry.generate_summary(4), "A real summary");
+
+    entry.set_summary(Text::plain(" "));
+    assert_eq!(entry.generate_summary(6), "日本語…");
+}
+
+#[test]
+fn media_duration() {
+    use std::time::Duration;
+
+    let feed = r#"
+        <feed xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd"
+              xmlns:media="http://search.yahoo.com/mrss/"
+              xmlns:podcast="http://www.itunes.com/dtds/podcast-1.0.dtd">
+            <entry>
+                <itunes:duration>soon</itunes:duration>
+                <media:content url="http://example.com/1.mp3" duration="1:30.25"/>
+            </entry>
+            <entry>
+                <media:group><media:content url="http://example.com/2.mp3" duration="90"/></media:group>
+                <itunes:duration> 2:00 </itunes:duration>
+            </entry>
+            <entry xmlns:m="http://search.yahoo.com/mrss/">
+                <m:content url="http://example.com/3.mp3" duration="3723"/>
+            </entry>
+            <entry><itunes:duration>1:2:3:4</itunes:duration></entry>
+            <entry><podcast:duration>45</podcast:duration></entry>
+            <entry xmlns:itunes="http://example.com/not-itunes">
+                <itunes:duration>60</itunes:duration>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let durations = feed
+        .entries()
+        .iter()
+        .map(Entry::media_duration)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        durations,
+        vec![
+            Some(Duration::from_millis(90_250)),
+            Some(Duration::from_secs(120)),
+            Some(Duration::from_secs(3723)),
+            None,
+            Some(Duration::from_secs(45)),
+            None,
+        ]
+    );
+}
@@ -0,0 +1,930 @@
+use atom_syndication::{
+    Category, Entry, Error, Feed, FixedDateTime, PagingState, Person, PresentElements,
+};
+
+fn entry(term: &str, updated: &str) -> Entry {
+    let mut category = Category::default();
+    category.set_term(term);
+
+    let mut entry = Entry::default();
+    entry.set_categories(vec![category]);
+    entry.set_updated(FixedDateTime::parse_from_rfc3339(updated).unwrap());
+    entry
+}
+
+fn has_term(entry: &Entry, term: &str) -> bool {
+    entry
+        .categories()
+        .iter()
+        .any(|category| category.term() == term)
+}
+
+#[test]
+fn filter_entries() {
+    let mut feed = Feed::default();
+    feed.set_title("Master");
+    feed.set_id("urn:feed");
+    feed.set_updated(FixedDateTime::parse_from_rfc3339("2021-03-01T00:00:00Z").unwrap());
+    feed.set_entries(vec![
+        entry("rust", "2021-01-01T00:00:00Z"),
+        entry("go", "2021-03-01T00:00:00Z"),
+        entry("rust", "2021-02-01T00:00:00Z"),
+    ]);
+
+    let rust = feed.filter_entries(|entry| has_term(entry, "rust"));
+    assert_eq!(rust.title(), "Master");
+    assert_eq!(rust.id(), "urn:feed");
+    assert_eq!(rust.entries().len(), 2);
+    assert!(rust.entries().iter().all(|entry| has_term(entry, "rust")));
+    assert_eq!(rust.updated().to_rfc3339(), "2021-02-01T00:00:00+00:00");
+
+    let none = feed.filter_entries(|_| false);
+    assert!(none.entries().is_empty());
+    assert_eq!(none.updated(), feed.updated());
+    assert_eq!(feed.entries().len(), 3);
+}
+
+#[test]
+fn paging() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:history="http://purl.org/syndication/history/1.0">
+            <link rel="self" href="http://example.com/feed?page=2"/>
+            <link rel="first" href="http://example.com/feed"/>
+            <link rel="previous" href="http://example.com/feed?page=1"/>
+            <link rel="next" href="http://example.com/feed?page=3"/>
+            <link rel="last" href="http://example.com/feed?page=9"/>
+            <history:archive/>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    assert_eq!(feed.first_page(), Some("http://example.com/feed"));
+    assert_eq!(feed.prev_page(), Some("http://example.com/feed?page=1"));
+    assert_eq!(feed.next_page(), Some("http://example.com/feed?page=3"));
+    assert_eq!(feed.last_page(), Some("http://example.com/feed?page=9"));
+    assert!(feed.is_archive());
+    assert!(!feed.is_complete());
+
+    let feed = Feed::default();
+    assert_eq!(feed.next_page(), None);
+    assert!(!feed.is_archive());
+}
+
+#[test]
+fn paging_state() {
+    let read = |body: &str| {
+        format!(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:fh="http://purl.org/syndication/history/1.0">{}</feed>"#,
+            body
+        )
+        .parse::<Feed>()
+        .unwrap()
+        .paging_state()
+    };
+
+    assert_eq!(read(""), PagingState::Unknown);
+    assert_eq!(
+        read(r#"<link rel="self" href="http://example.com/feed"/>"#),
+        PagingState::Unknown
+    );
+    assert_eq!(
+        read(r#"<link rel="next" href="http://example.com/feed?page=2"/>"#),
+        PagingState::Paged
+    );
+    assert_eq!(
+        read(r#"<link rel="last" href="http://example.com/feed?page=9"/>"#),
+        PagingState::Paged
+    );
+    assert_eq!(
+        read(r#"<link rel="prev-archive" href="http://example.com/2020"/>"#),
+        PagingState::Paged
+    );
+    assert_eq!(read("<fh:complete/>"), PagingState::Complete);
+    assert_eq!(read("<fh:archive/>"), PagingState::Archive);
+    assert_eq!(
+        read(r#"<fh:archive/><link rel="prev-archive" href="http://example.com/2020"/>"#),
+        PagingState::Archive
+    );
+    assert_eq!(read("<fh:archive/><fh:complete/>"), PagingState::Complete);
+    assert_eq!(
+        read(r#"<fh:complete/><link rel="next" href="http://example.com/feed?page=2"/>"#),
+        PagingState::Complete
+    );
+}
+
+fn page(next: Option<&str>, id: &str) -> Feed {
+    let mut entry = Entry::default();
+    entry.set_id(id);
+
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry]);
+    if let Some(next) = next {
+        let mut link = atom_syndication::Link::default();
+        link.set_rel("next");
+        link.set_href(next);
+        feed.set_links(vec![link]);
+    }
+    feed
+}
+
+#[test]
+fn read_all_pages() {
+    let mut fetched = Vec::new();
+    let entries = Feed::read_all_pages(page(Some("page2"), "1"), |url| {
+        fetched.push(url.to_string());
+        Ok(match url {
+            "page2" => page(Some("page3"), "2"),
+            _ => page(None, "3"),
+        })
+    })
+    .unwrap();
+
+    assert_eq!(fetched, vec!["page2", "page3"]);
+    let ids = entries.iter().map(Entry::id).collect::<Vec<_>>();
+    assert_eq!(ids, vec!["1", "2", "3"]);
+}
+
+#[test]
+fn read_all_pages_cycle() {
+    let mut fetched = 0;
+    let result = Feed::read_all_pages(page(Some("page2"), "1"), |url| {
+        fetched += 1;
+        Ok(match url {
+            "page2" => page(Some("page3"), "2"),
+            _ => page(Some("page2"), "3"),
+        })
+    });
+    assert_eq!(fetched, 2);
+    assert!(matches!(result, Err(Error::PagingCycle(ref url)) if url == "page2"));
+
+    let mut initial = page(Some("self"), "1");
+    initial
+        .links
+        .push(atom_syndication::Link::self_link("self"));
+    let result = Feed::read_all_pages(initial, |_| unreachable!());
+    assert!(matches!(result, Err(Error::PagingCycle(ref url)) if url == "self"));
+
+    let mut fetched = 0;
+    let result = Feed::read_all_pages(page(Some("page2"), "1"), |url| {
+        fetched += 1;
+        Ok(page(Some(url), "2"))
+    });
+    assert_eq!(fetched, 1);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "the next links of the paged feed loop back to 'page2'"
+    );
+
+    let mut fetched = 0;
+    let entries = Feed::read_all_pages(page(Some("next"), "1"), |url| {
+        fetched += 1;
+        Ok(page(Some(&format!("{}-", url)), "n"))
+    })
+    .unwrap();
+    assert_eq!(fetched, Feed::MAX_PAGES - 1);
+    assert_eq!(entries.len(), Feed::MAX_PAGES);
+}
+
+#[test]
+fn read_all_pages_error() {
+    let result = Feed::read_all_pages(page(Some("page2"), "1"), |_| Err(Error::Eof));
+    assert!(matches!(result, Err(Error::Eof)));
+}
+
+#[test]
+fn byte_size_estimate() {
+    for path in &[
+        "tests/data/feed.xml",
+        "tests/data/entry.xml",
+        "tests/data/extension.xml",
+        "tests/data/source.xml",
+        "tests/data/content_text_html.xml",
+    ] {
+        let feed =
+            Feed::read_from(std::io::BufReader::new(std::fs::File::open(path).unwrap())).unwrap();
+        let written = feed.to_string().len();
+        let estimate = feed.byte_size_estimate();
+        assert!(estimate >= written, "{}: {} < {}", path, estimate, written);
+        assert!(
+            estimate <= written * 3 / 2,
+            "{}: {} > {}",
+            path,
+            estimate,
+            written
+        );
+    }
+}
+
+#[test]
+fn into_parts() {
+    let feed = Feed::read_from(std::io::BufReader::new(
+        std::fs::File::open("tests/data/feed.xml").unwrap(),
+    ))
+    .unwrap();
+
+    let mut parts = feed.clone().into_parts();
+    assert_eq!(parts.metadata.title, feed.title);
+    assert_eq!(parts.metadata.authors, feed.authors);
+    assert!(parts.metadata.entries.is_empty());
+    assert_eq!(parts.entries, feed.entries);
+    assert_eq!(Feed::from_parts(parts.clone()), feed);
+
+    parts.entries.push(entry("rust", "2021-01-01T00:00:00Z"));
+    let rebuilt = Feed::from_parts(parts);
+    assert_eq!(rebuilt.entries().len(), 1);
+    assert_eq!(rebuilt.links(), feed.links());
+}
+
+#[test]
+fn truncate_entries() {
+    let mut feed = Feed::default();
+    feed.set_entries(vec![
+        entry("a", "2021-01-01T00:00:00Z"),
+        entry("b", "2021-03-01T00:00:00Z"),
+        entry("c", "2021-02-01T00:00:00Z"),
+        entry("d", "2020-12-01T00:00:00Z"),
+    ]);
+
+    let terms = |feed: &Feed| {
+        feed.entries()
+            .iter()
+            .map(|entry| entry.categories()[0].term().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(terms(&feed.with_latest(2)), vec!["b", "c"]);
+    assert_eq!(terms(&feed.with_latest(10)), vec!["b", "c", "a", "d"]);
+    assert_eq!(terms(&feed), vec!["a", "b", "c", "d"]);
+
+    feed.truncate_entries(3);
+    assert_eq!(terms(&feed), vec!["b", "c", "a"]);
+    feed.truncate_entries(0);
+    assert!(feed.entries().is_empty());
+}
+
+#[test]
+fn dedup_by_content() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <id>urn:a</id>
+                <title>Cross-posted</title>
+                <content>Body</content>
+                <link href="https://example.com/a"/>
+            </entry>
+            <entry>
+                <id>urn:b</id>
+                <title>Cross-posted</title>
+                <content>Other body</content>
+                <link href="https://example.com/a"/>
+            </entry>
+            <entry>
+                <id>urn:c</id>
+                <updated>2021-01-01T00:00:00Z</updated>
+                <title>Cross-posted</title>
+                <content>Body</content>
+                <link href="https://example.com/a"/>
+            </entry>
+            <entry>
+                <id>urn:d</id>
+                <title>Cross-posted</title>
+                <content>Body</content>
+                <link href="https://example.com/d"/>
+            </entry>
+        </feed>
+    "#;
+    let mut feed = feed.parse::<Feed>().unwrap();
+
+    let entries = feed.entries();
+    assert_eq!(entries[0].content_hash(), entries[2].content_hash());
+    assert_ne!(entries[0].content_hash(), entries[1].content_hash());
+    assert_ne!(entries[0].content_hash(), entries[3].content_hash());
+
+    feed.dedup_by_content();
+    let ids = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.id().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:a", "urn:b", "urn:d"]);
+}
+
+#[test]
+fn entries_in_language() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xml:lang="en-US">
+            <entry><id>urn:inherited</id></entry>
+            <entry xml:lang="EN-gb"><id>urn:british</id></entry>
+            <entry xml:lang="eo"><id>urn:esperanto</id></entry>
+            <entry xml:lang="de"><id>urn:german</id></entry>
+            <entry xml:lang=""><id>urn:unknown</id></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let ids = |lang: &str| {
+        feed.entries_in_language(lang)
+            .iter()
+            .map(|entry| entry.id().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids("en"), vec!["urn:inherited", "urn:british"]);
+    assert_eq!(ids("en-us"), vec!["urn:inherited"]);
+    assert_eq!(ids("e"), Vec::<String>::new());
+    assert_eq!(ids("de"), vec!["urn:german"]);
+    assert_eq!(ids("fr"), Vec::<String>::new());
+}
+
+#[test]
+fn try_from() {
+    use std::convert::TryFrom;
+
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Title</title></feed>"#;
+    let from_str = Feed::try_from(xml).unwrap();
+    let from_bytes = Feed::try_from(xml.as_bytes()).unwrap();
+    assert_eq!(from_str.title(), "Title");
+    assert_eq!(from_bytes, from_str);
+
+    assert!(matches!(
+        Feed::try_from(&b"<rss></rss>"[..]),
+        Err(Error::InvalidStartTag)
+    ));
+    assert!(matches!(Feed::try_from(""), Err(Error::Eof)));
+}
+
+#[test]
+fn updated_rfc822() {
+    use chrono::TimeZone;
+
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <updated>2021-12-05T09:03:07+05:30</updated>
+            <entry><updated>2020-02-29T23:59:59Z</updated></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+    assert_eq!(
+        feed.updated_rfc822().unwrap(),
+        "Sun, 5 Dec 2021 09:03:07 +0530"
+    );
+    assert_eq!(
+        feed.entries()[0].updated_rfc822().unwrap(),
+        "Sat, 29 Feb 2020 23:59:59 +0000"
+    );
+
+    let mut feed = Feed::default();
+    let far_future = chrono::FixedOffset::east_opt(0)
+        .unwrap()
+        .with_ymd_and_hms(10_000, 1, 1, 0, 0, 0)
+        .unwrap();
+    feed.set_updated(far_future);
+    assert!(matches!(
+        feed.updated_rfc822(),
+        Err(Error::WrongDatetime(_))
+    ));
+}
+
+#[test]
+fn retain_entries() {
+    let mut feed = Feed::default();
+    feed.set_updated(FixedDateTime::parse_from_rfc3339("2021-06-01T00:00:00Z").unwrap());
+    feed.set_entries(vec![
+        entry("a", "2021-01-01T00:00:00Z"),
+        entry("b", "2021-03-01T00:00:00Z"),
+        entry("c", "2021-02-01T00:00:00Z"),
+    ]);
+
+    let mut seen = 0;
+    feed.retain_entries(|entry| {
+        seen += 1;
+        entry.categories()[0].term() != "b"
+    });
+    assert_eq!(seen, 3);
+    assert_eq!(feed.entries().len(), 2);
+    assert_eq!(feed.updated().to_rfc3339(), "2021-06-01T00:00:00+00:00");
+
+    feed.update_from_entries();
+    assert_eq!(feed.updated().to_rfc3339(), "2021-02-01T00:00:00+00:00");
+
+    feed.retain_entries(|_| false);
+    feed.update_from_entries();
+    assert!(feed.entries().is_empty());
+    assert_eq!(feed.updated().to_rfc3339(), "2021-02-01T00:00:00+00:00");
+}
+
+#[test]
+fn all_people() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <author><name>Jane Doe</name><email>jane@example.com</email></author>
+            <contributor><name>John Doe</name></contributor>
+            <entry>
+                <author><name>Jane Doe</name><email>jane@example.com</email><uri>http://jane.example.com/</uri></author>
+                <author><name>Jane Doe</name><email>jane@example.org</email></author>
+            </entry>
+            <entry>
+                <author><name>John Doe</name></author>
+                <author><name>Jane Doe</name><email>Jane@Example.COM</email></author>
+                <contributor><name>Richard Roe</name></contributor>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let people = feed
+        .all_people()
+        .iter()
+        .map(|person| (person.name(), person.email()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        people,
+        vec![
+            ("Jane Doe", Some("jane@example.com")),
+            ("John Doe", None),
+            ("Jane Doe", Some("jane@example.org")),
+            ("Richard Roe", None),
+        ]
+    );
+    assert_eq!(feed.all_people()[0].uri(), None);
+    assert!(Feed::default().all_people().is_empty());
+}
+
+#[test]
+fn person_same_as() {
+    let mut jane = Person::default();
+    jane.set_name("Jane Doe");
+    jane.set_email("Jane@Example.com".to_string());
+
+    let mut lowercase = jane.clone();
+    lowercase.set_email("jane@example.com".to_string());
+    lowercase.set_uri("http://jane.example.com/".to_string());
+    assert!(jane.same_as(&lowercase));
+    assert_ne!(jane, lowercase);
+
+    let mut renamed = jane.clone();
+    renamed.set_name("jane doe");
+    assert!(!jane.same_as(&renamed));
+
+    let mut other = jane.clone();
+    other.set_email("jane@example.org".to_string());
+    assert!(!jane.same_as(&other));
+
+    let mut no_email = jane.clone();
+    no_email.set_email(None);
+    assert!(!jane.same_as(&no_email));
+    assert!(no_email.same_as(&no_email.clone()));
+}
+
+#[test]
+fn entries_page() {
+    let mut feed = Feed::default();
+    feed.set_entries(
+        (0..7)
+            .map(|i| {
+                let mut entry = Entry::default();
+                entry.set_id(i.to_string());
+                entry
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    fn ids<'a, I>(entries: I) -> Vec<&'a str>
+    where
+        I: IntoIterator<Item = &'a Entry>,
+    {
+        entries.into_iter().map(Entry::id).collect()
+    }
+
+    assert_eq!(ids(feed.entries_page(0, 3)), vec!["0", "1", "2"]);
+    assert_eq!(ids(feed.entries_page(1, 3)), vec!["3", "4", "5"]);
+    assert_eq!(ids(feed.entries_page(2, 3)), vec!["6"]);
+    assert!(feed.entries_page(3, 3).is_empty());
+    assert_eq!(ids(feed.entries_page(0, 7)), ids(feed.entries()));
+    assert_eq!(ids(feed.entries_page(0, 100)), ids(feed.entries()));
+    assert!(feed.entries_page(1, 7).is_empty());
+    assert!(feed.entries_page(0, 0).is_empty());
+    assert!(feed.entries_page(usize::MAX, 2).is_empty());
+    assert_eq!(ids(feed.entries_page(1, usize::MAX)), Vec::<&str>::new());
+    assert!(Feed::default().entries_page(0, 10).is_empty());
+
+    let updated = |time: &str| FixedDateTime::parse_from_rfc3339(time).unwrap();
+    feed.entries[2].set_updated(updated("2021-05-01T12:00:00Z"));
+    feed.entries[5].set_updated(updated("2021-05-02T12:00:00+02:00"));
+    feed.entries[6].set_updated(updated("2021-05-02T12:00:00Z"));
+    assert_eq!(ids(feed.entries_page(0, 3)), vec!["6", "5", "2"]);
+    assert_eq!(ids(feed.entries_page(1, 3)), vec!["0", "1", "3"]);
+    assert_eq!(ids(feed.entries_page(2, 3)), vec!["4"]);
+    assert_eq!(ids(feed.entries()), vec!["0", "1", "2", "3", "4", "5", "6"]);
+}
+
+#[test]
+fn map_entries() {
+    let mut feed = Feed::default();
+    feed.set_id("urn:feed");
+    feed.set_updated(FixedDateTime::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap());
+    feed.set_entries(vec![
+        entry("rust", "2021-05-01T00:00:00Z"),
+        entry("go", "2021-06-01T00:00:00Z"),
+    ]);
+
+    let expected = feed.clone();
+    let feed = feed.map_entries(|mut entry| {
+        let term = entry.categories()[0].term().to_uppercase();
+        entry.set_id(format!("urn:{}", term));
+        entry
+    });
+
+    let ids = feed.entries().iter().map(Entry::id).collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:RUST", "urn:GO"]);
+    assert_eq!(feed.id(), expected.id());
+    assert_eq!(feed.updated(), expected.updated());
+    assert_eq!(
+        feed.filter_entries(|_| false),
+        expected.filter_entries(|_| false)
+    );
+}
+
+#[test]
+fn into_pages() {
+    let mut feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Archive</title>
+            <link rel="self" href="http://example.com/old"/>
+            <link rel="next" href="http://example.com/old?page=2"/>
+            <link rel="alternate" href="http://example.com/"/>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+    feed.set_entries(
+        (1..=5)
+            .map(|i| {
+                let mut entry = Entry::default();
+                entry.set_id(format!("urn:entry:{}", i));
+                entry
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let pages = feed
+        .clone()
+        .into_pages(2, "http://example.com/feed?lang=en");
+    assert_eq!(pages.len(), 3);
+
+    let links = |page: &Feed| {
+        page.links()
+            .iter()
+            .map(|link| format!("{} {}", link.rel(), link.href()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        links(&pages[0]),
+        vec![
+            "alternate http://example.com/",
+            "self http://example.com/feed?lang=en",
+            "first http://example.com/feed?lang=en",
+            "last http://example.com/feed?lang=en&page=3",
+            "next http://example.com/feed?lang=en&page=2",
+        ]
+    );
+    assert_eq!(
+        links(&pages[1]),
+        vec![
+            "alternate http://example.com/",
+            "self http://example.com/feed?lang=en&page=2",
+            "first http://example.com/feed?lang=en",
+            "last http://example.com/feed?lang=en&page=3",
+            "previous http://example.com/feed?lang=en",
+            "next http://example.com/feed?lang=en&page=3",
+        ]
+    );
+    assert_eq!(pages[2].next_page(), None);
+    assert_eq!(pages[2].entries()[0].id(), "urn:entry:5");
+
+    for page in &pages {
+        assert_eq!(page.title(), "Archive");
+        assert_eq!(page.paging_state(), PagingState::Paged);
+    }
+
+    assert_eq!(
+        feed.clone().into_pages(0, "http://example.com/feed").len(),
+        1
+    );
+    feed.set_entries(Vec::new());
+    let pages = feed.into_pages(10, "http://example.com/feed");
+    assert_eq!(pages.len(), 1);
+    assert!(pages[0].entries().is_empty());
+}
+
+#[test]
+fn entries_between() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <id>april</id>
+                <updated>2021-05-10T00:00:00Z</updated>
+                <published>2021-04-30T23:59:59Z</published>
+            </entry>
+            <entry><id>may-start</id><updated>2021-05-01T00:00:00Z</updated></entry>
+            <entry><id>may-end</id><updated>2021-05-31T23:59:59+00:00</updated></entry>
+            <entry><id>june</id><updated>2021-06-01T00:00:00Z</updated></entry>
+            <entry><id>june-offset</id><updated>2021-06-01T01:00:00+02:00</updated></entry>
+            <entry><id>ancient</id><updated>0001-01-01T00:00:00Z</updated></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let may = UNIX_EPOCH + Duration::from_secs(1_619_827_200);
+    let june = UNIX_EPOCH + Duration::from_secs(1_622_505_600);
+    let ids = feed
+        .entries_between(may, june)
+        .into_iter()
+        .map(Entry::id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["may-start", "may-end", "june-offset"]);
+    assert!(feed.entries_between(june, may).is_empty());
+}
+
+#[test]
+fn changes_since() {
+    let with_id = |id: &str, updated: &str| {
+        let mut entry = entry("news", updated);
+        entry.set_id(id);
+        entry
+    };
+
+    let mut previous = Feed::default();
+    previous.set_entries(vec![
+        with_id("unchanged", "2021-01-01T00:00:00Z"),
+        with_id("updated", "2021-01-01T00:00:00Z"),
+        with_id("removed", "2021-01-01T00:00:00Z"),
+        with_id("backdated", "2021-01-02T00:00:00Z"),
+    ]);
+
+    let mut current = Feed::default();
+    current.set_title("Current");
+    current.set_entries(vec![
+        with_id("added", "2021-01-02T00:00:00Z"),
+        with_id("unchanged", "2021-01-01T00:00:00Z"),
+        with_id("updated", "2021-01-03T00:00:00+02:00"),
+        with_id("backdated", "2021-01-01T00:00:00Z"),
+    ]);
+
+    let changes = current.changes_since(&previous);
+    assert_eq!(changes.title(), "Current");
+    let ids = changes.entries().iter().map(Entry::id).collect::<Vec<_>>();
+    assert_eq!(ids, vec!["updated", "added"]);
+    assert!(current.changes_since(&current).entries().is_empty());
+}
+
+#[test]
+fn from_items() {
+    let item =
+        |title: &str, url: &str, date: &str| (title.to_string(), url.to_string(), date.to_string());
+
+    let feed = Feed::from_items(
+        "Links",
+        "urn:links",
+        vec![
+            item("First", "http://example.com/1", "2021-05-01T12:00:00+02:00"),
+            item(
+                "Second",
+                "http://example.com/2",
+                "Sun, 02 May 2021 08:00:00 GMT",
+            ),
+            item("Third", "http://example.com/3", "2021-04-30T00:00:00"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(feed.title(), "Links");
+    assert_eq!(feed.id(), "urn:links");
+    assert_eq!(feed.updated().to_rfc3339(), "2021-05-02T08:00:00+00:00");
+
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.title(), "First");
+    assert_eq!(entry.id(), "http://example.com/1");
+    assert_eq!(entry.links()[0].rel(), "alternate");
+    assert_eq!(entry.links()[0].href(), "http://example.com/1");
+    assert_eq!(entry.updated().to_rfc3339(), "2021-05-01T12:00:00+02:00");
+    assert_eq!(entry.published(), Some(entry.updated()));
+    assert_eq!(
+        feed.entries()[2].updated().to_rfc3339(),
+        "2021-04-30T00:00:00+00:00"
+    );
+
+    let error = Feed::from_items(
+        "Links",
+        "urn:links",
+        vec![item("Bad", "http://example.com/bad", "yesterday")],
+    )
+    .unwrap_err();
+    assert!(matches!(error, Error::WrongDatetime(ref date) if date == "yesterday"));
+
+    let empty = Feed::from_items("Empty", "urn:empty", Vec::new()).unwrap();
+    assert!(empty.entries().is_empty());
+}
+
+#[test]
+fn icon_and_logo_images() {
+    use atom_syndication::{Image, ImageAspect};
+
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <icon> http://example.com/icon.png </icon>
+            <logo>http://example.com/logo.png</logo>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    assert_eq!(
+        feed.icon_image(),
+        Some(Image {
+            url: "http://example.com/icon.png".to_string(),
+            aspect: ImageAspect::Square,
+        })
+    );
+    assert_eq!(
+        feed.logo_image(),
+        Some(Image {
+            url: "http://example.com/logo.png".to_string(),
+            aspect: ImageAspect::Wide,
+        })
+    );
+
+    let mut feed = Feed::default();
+    assert_eq!(feed.icon_image(), None);
+    feed.set_logo(" ".to_string());
+    assert_eq!(feed.logo_image(), None);
+}
+
+#[test]
+fn set_link_replaces_by_rel() {
+    use atom_syndication::Link;
+
+    let mut feed = Feed::default();
+    feed.add_link(Link::self_link("http://example.com/feed"));
+    feed.add_link(Link::self_link("http://example.com/feed"));
+    feed.add_link(Link::alternate("http://example.com/"));
+    feed.set_link(Link::self_link("http://example.com/atom"));
+
+    let hrefs = feed.links().iter().map(Link::href).collect::<Vec<_>>();
+    assert_eq!(
+        hrefs,
+        vec!["http://example.com/atom", "http://example.com/"]
+    );
+}
+
+#[test]
+fn into_entries() {
+    let feed = "<feed><title>Feed</title><entry><id>urn:1</id></entry><entry><id>urn:2</id></entry></feed>"
+        .parse::<Feed>()
+        .unwrap();
+
+    let mut entries = feed.into_entries();
+    assert_eq!(
+        entries.next().map(|entry| entry.id),
+        Some("urn:1".to_string())
+    );
+    assert_eq!(
+        entries.next().map(|entry| entry.id),
+        Some("urn:2".to_string())
+    );
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn updated_parsed() {
+    let feed = "<feed><updated>2021-05-01T12:00:00.123+02:00</updated></feed>"
+        .parse::<Feed>()
+        .unwrap();
+    let updated = feed.updated_parsed().unwrap();
+    assert_eq!(updated.datetime, *feed.updated());
+    assert_eq!(updated.offset.local_minus_utc(), 2 * 3600);
+    assert!(updated.had_fractional_seconds);
+    assert!(feed
+        .to_string()
+        .contains("<updated>2021-05-01T12:00:00.123+02:00</updated>"));
+
+    let feed = "<feed><updated>2021-05-01T12:00:00Z</updated></feed>"
+        .parse::<Feed>()
+        .unwrap();
+    let updated = feed.updated_parsed().unwrap();
+    assert_eq!(updated.offset.local_minus_utc(), 0);
+    assert!(!updated.had_fractional_seconds);
+
+    let feed = "<feed></feed>".parse::<Feed>().unwrap();
+    assert_eq!(feed.updated_parsed(), None);
+}
+
+#[test]
+fn retain_recent() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let xml = r#"
+        <feed>
+            <entry><id>old</id><updated>1970-01-01T00:16:39Z</updated></entry>
+            <entry><id>boundary</id><updated>1970-01-01T00:16:40Z</updated></entry>
+            <entry><id>published</id><published>1970-01-01T01:00:00+01:00</published><updated>1970-01-01T00:30:00Z</updated></entry>
+            <entry><id>new</id><updated>1970-01-01T00:30:00Z</updated></entry>
+            <entry><id>undated</id></entry>
+        </feed>
+    "#;
+    let ids = |feed: &Feed| {
+        feed.entries()
+            .iter()
+            .map(|entry| entry.id().to_string())
+            .collect::<Vec<_>>()
+    };
+    let now = UNIX_EPOCH + Duration::from_secs(2000);
+
+    let mut feed = xml.parse::<Feed>().unwrap();
+    feed.retain_recent(Duration::from_secs(1000), now);
+    assert_eq!(ids(&feed), vec!["boundary", "new", "undated"]);
+
+    let mut feed = xml.parse::<Feed>().unwrap();
+    feed.retain_recent_with(Duration::from_secs(1000), now, false);
+    assert_eq!(ids(&feed), vec!["boundary", "new"]);
+
+    let mut feed = xml.parse::<Feed>().unwrap();
+    feed.retain_recent(Duration::from_secs(5000), now);
+    assert_eq!(feed.entries().len(), 5);
+}
+
+#[test]
+fn merge_sorted() {
+    let entry = |id: &str, updated: &str| {
+        let mut entry = Entry::default();
+        entry.set_id(id);
+        entry.set_updated(FixedDateTime::parse_from_rfc3339(updated).unwrap());
+        entry
+    };
+    let feed = |entries: Vec<Entry>| Feed::default().with_entries(entries);
+
+    let feeds = vec![
+        feed(vec![
+            entry("a", "2021-05-05T00:00:00Z"),
+            entry("tie-1", "2021-05-03T00:00:00Z"),
+            entry("", "2021-05-01T00:00:00Z"),
+        ]),
+        feed(Vec::new()),
+        feed(vec![
+            entry("b", "2021-05-04T00:00:00+02:00"),
+            entry("tie-2", "2021-05-03T00:00:00Z"),
+            entry("a", "2021-05-02T00:00:00Z"),
+            entry("", "2021-05-01T00:00:00Z"),
+        ]),
+        feed(vec![entry("c", "2021-05-06T00:00:00Z")]),
+    ];
+
+    let merged = Feed::merge_sorted(feeds);
+    let ids = merged
+        .entries()
+        .iter()
+        .map(|entry| entry.id())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["c", "a", "b", "tie-1", "tie-2", "", ""]);
+    assert_eq!(merged.updated().to_rfc3339(), "2021-05-06T00:00:00+00:00");
+
+    assert!(Feed::merge_sorted(Vec::new()).entries().is_empty());
+}
+
+#[test]
+fn present_elements() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Feed</title>
+            <author><name>Jane Doe</name></author>
+            <generator>Example</generator>
+            <icon>http://example.com/icon.png</icon>
+            <logo> </logo>
+            <rights></rights>
+            <subtitle>Subtitle</subtitle>
+            <link href="http://example.com/"/>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let present = feed.present_elements();
+    assert_eq!(
+        present,
+        PresentElements {
+            authors: true,
+            generator: true,
+            icon: true,
+            links: true,
+            subtitle: true,
+            ..PresentElements::default()
+        }
+    );
+    assert_eq!(present.count(), 5);
+}
@@ -0,0 +1,375 @@
+use atom_syndication::{Feed, Link};
+
+#[test]
+fn edit_links() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <link href="http://example.com/1.html"/>
+                <link rel="edit-media" href="http://example.com/media/1.png"/>
+                <link rel="edit" href="http://example.com/entries/1"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.edit_link().map(Link::href),
+        Some("http://example.com/entries/1")
+    );
+    assert_eq!(
+        entry.edit_media_link().map(Link::href),
+        Some("http://example.com/media/1.png")
+    );
+}
+
+#[test]
+fn replies_link() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:thr="http://purl.org/syndication/thread/1.0">
+            <entry>
+                <link rel="replies" type="application/atom+xml" href="http://example.com/1/comments"
+                    thr:count="5" thr:updated="2005-07-28T12:10:00Z"/>
+            </entry>
+            <entry>
+                <link rel="replies" href="http://example.com/2/comments" thr:count="many"/>
+            </entry>
+            <entry>
+                <link href="http://example.com/3.html"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let replies = feed.entries()[0].replies_link().unwrap();
+    assert_eq!(replies.href, "http://example.com/1/comments");
+    assert_eq!(replies.count, Some(5));
+    assert_eq!(replies.updated.as_deref(), Some("2005-07-28T12:10:00Z"));
+
+    let replies = feed.entries()[1].replies_link().unwrap();
+    assert_eq!(replies.count, None);
+    assert_eq!(replies.updated, None);
+
+    assert_eq!(feed.entries()[2].replies_link(), None);
+
+    assert_eq!(feed.to_string().parse::<Feed>().unwrap(), feed);
+}
+
+#[test]
+fn replies_link_namespaces() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:t="http://purl.org/syndication/thread/1.0">
+            <entry>
+                <link rel="replies" href="http://example.com/1/comments" t:count="5"/>
+            </entry>
+            <entry>
+                <link rel="replies" href="http://example.com/2/comments"
+                    xmlns:thr="http://example.com/other" thr:count="7"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let replies = feed.entries()[0].replies_link().unwrap();
+    assert_eq!(replies.count, Some(5));
+
+    let link = &feed.entries()[1].links()[0];
+    assert_eq!(link.thr_count(), None);
+    assert_eq!(
+        link.unknown_attrs(),
+        &[
+            (
+                "xmlns:thr".to_string(),
+                "http://example.com/other".to_string()
+            ),
+            ("thr:count".to_string(), "7".to_string()),
+        ][..]
+    );
+}
+
+#[test]
+fn link_rels() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <link rel="self" href="http://example.com/feed"/>
+            <link href="http://example.com/"/>
+            <entry>
+                <link href="http://example.com/1"/>
+                <link rel="related" href="http://example.com/2"/>
+                <link rel="http://www.iana.org/assignments/relation/related" href="http://example.com/3"/>
+                <link rel="enclosure" href="http://example.com/1.mp3"/>
+                <link rel="http://www.iana.org/assignments/relation/alternate" href="http://example.com/4"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let mut rels = feed.link_rels().into_iter().collect::<Vec<_>>();
+    rels.sort();
+    assert_eq!(rels, vec!["alternate", "enclosure", "related", "self"]);
+
+    let related = feed.entries()[0]
+        .related_links()
+        .into_iter()
+        .map(Link::href)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        related,
+        vec!["http://example.com/2", "http://example.com/3"]
+    );
+
+    let mut link = Link::default();
+    link.set_rel("");
+    link.set_href("http://example.com/");
+    let mut feed = Feed::default();
+    feed.set_links(vec![link]);
+    assert!(feed.link_rels().contains("alternate"));
+    assert_eq!(
+        feed.to_opml_outline().html_url.as_deref(),
+        Some("http://example.com/")
+    );
+}
+
+#[test]
+fn constructors() {
+    let link = Link::alternate("http://example.com/1");
+    assert_eq!(
+        (link.rel(), link.href()),
+        ("alternate", "http://example.com/1")
+    );
+    assert_eq!(link.mime_type(), None);
+
+    let link = Link::self_link("http://example.com/feed.atom");
+    assert_eq!(link.rel(), "self");
+    assert_eq!(link.href(), "http://example.com/feed.atom");
+    assert_eq!(link.mime_type(), Some("application/atom+xml"));
+
+    let link = Link::enclosure("http://example.com/1.mp3", "audio/mpeg", 4096);
+    assert_eq!(link.rel(), "enclosure");
+    assert_eq!(link.href(), "http://example.com/1.mp3");
+    assert_eq!(link.mime_type(), Some("audio/mpeg"));
+    assert_eq!(link.length(), Some("4096"));
+
+    let link = Link::related("http://example.com/2");
+    assert_eq!(
+        (link.rel(), link.href()),
+        ("related", "http://example.com/2")
+    );
+}
+
+#[test]
+fn links_of_type() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <link rel="enclosure" type="application/pdf" href="http://example.com/1.pdf"/>
+                <link rel="enclosure" type="image/png" href="http://example.com/1.png"/>
+                <link rel="alternate" type="Image/JPEG; q=0.9" href="http://example.com/1.jpg"/>
+                <link href="http://example.com/untyped"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+    let entry = &feed.entries()[0];
+
+    let hrefs = |mime: &str| {
+        entry
+            .links_of_type(mime)
+            .into_iter()
+            .map(Link::href)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(hrefs("application/pdf"), vec!["http://example.com/1.pdf"]);
+    assert_eq!(hrefs("image/jpeg"), vec!["http://example.com/1.jpg"]);
+    assert_eq!(
+        hrefs("image/"),
+        vec!["http://example.com/1.png", "http://example.com/1.jpg"]
+    );
+    assert!(hrefs("image").is_empty());
+    assert!(hrefs("text/").is_empty());
+}
+
+#[test]
+fn normalized_rel() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <link rel="http://www.iana.org/assignments/relation/next" href="http://example.com/2"/>
+            <entry>
+                <link rel="http://www.iana.org/assignments/relation/edit" href="http://example.com/edit"/>
+                <link rel="http://www.iana.org/assignments/relation/related" href="http://example.com/r"/>
+                <link rel="http://example.com/rel/related" href="http://example.com/custom"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    assert_eq!(feed.next_page(), Some("http://example.com/2"));
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.edit_link().map(Link::href),
+        Some("http://example.com/edit")
+    );
+    assert_eq!(entry.related_links().len(), 1);
+    assert_eq!(
+        entry.links()[2].normalized_rel(),
+        "http://example.com/rel/related"
+    );
+
+    let mut rels = feed.link_rels().into_iter().collect::<Vec<_>>();
+    rels.sort();
+    assert_eq!(
+        rels,
+        vec!["edit", "http://example.com/rel/related", "next", "related"]
+    );
+}
+
+#[test]
+fn license() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <link rel="alternate" href="http://example.com/"/>
+            <link rel="http://www.iana.org/assignments/relation/license" href="https://creativecommons.org/licenses/by/4.0/"/>
+            <rights>All rights reserved</rights>
+            <entry>
+                <link rel="license" href="https://creativecommons.org/publicdomain/zero/1.0/"/>
+            </entry>
+            <entry></entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let license = "https://creativecommons.org/licenses/by/4.0/";
+    assert_eq!(feed.license_link().map(Link::href), Some(license));
+    assert_eq!(feed.license_url(), Some(license));
+    assert_eq!(feed.license_info(), Some(license));
+    assert_eq!(
+        feed.entries()[0].license_link().map(Link::href),
+        Some("https://creativecommons.org/publicdomain/zero/1.0/")
+    );
+    assert!(feed.entries()[1].license_link().is_none());
+
+    let mut feed = feed;
+    feed.set_links(Vec::new());
+    assert_eq!(feed.license_url(), None);
+    assert_eq!(feed.license_info(), Some("All rights reserved"));
+}
+
+#[test]
+fn canonical_url() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <id>http://example.com/id/1</id>
+                <link rel="self" href="http://example.com/self/1"/>
+                <link href="http://example.com/posts/1"/>
+                <link rel="alternate" href="http://example.com/posts/1.html"/>
+            </entry>
+            <entry>
+                <id>HTTPS://example.com/id/2</id>
+                <link rel="related" href="http://example.com/related/2"/>
+                <link rel="alternate" href=""/>
+            </entry>
+            <entry>
+                <id>tag:example.com,2021:3</id>
+                <link rel="http://www.iana.org/assignments/relation/alternate" href="http://example.com/posts/3"/>
+            </entry>
+            <entry>
+                <id>tag:example.com,2021:4</id>
+            </entry>
+            <entry>
+                <id>http://</id>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let urls = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.canonical_url())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        urls,
+        vec![
+            Some("http://example.com/posts/1"),
+            Some("HTTPS://example.com/id/2"),
+            Some("http://example.com/posts/3"),
+            None,
+            None,
+        ]
+    );
+}
+
+#[test]
+fn dedup_links() {
+    let mut feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <link rel="self" href="http://example.com/feed"/>
+            <link rel="alternate" href="http://example.com/"/>
+            <link rel="self" href="http://example.com/feed"/>
+            <link rel="http://www.iana.org/assignments/relation/self" href="http://example.com/feed"/>
+            <link rel="self" type="application/atom+xml" href="http://example.com/feed"/>
+            <entry>
+                <link href="http://example.com/1"/>
+                <link rel="alternate" href="http://example.com/1"/>
+                <link rel="related" href="http://example.com/1"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+    feed.dedup_links();
+
+    let links = feed
+        .links()
+        .iter()
+        .map(|link| (link.rel(), link.mime_type()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        links,
+        vec![
+            ("self", None),
+            ("alternate", None),
+            ("self", Some("application/atom+xml")),
+        ]
+    );
+    let rels = feed.entries()[0]
+        .links()
+        .iter()
+        .map(|link| link.rel())
+        .collect::<Vec<_>>();
+    assert_eq!(rels, vec!["alternate", "related"]);
+}
+
+#[test]
+fn rel_predicates() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <link href="http://example.com/1.html"/>
+                <link rel="" href="http://example.com/1.txt"/>
+                <link rel="http://www.iana.org/assignments/relation/alternate" href="http://example.com/1"/>
+                <link rel="enclosure" type="audio/mpeg" href="http://example.com/1.mp3"/>
+                <link rel="http://www.iana.org/assignments/relation/enclosure" href="http://example.com/1.ogg"/>
+                <link rel="related" href="http://example.com/2.html"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let links = feed.entries()[0].links();
+    let alternate = links.iter().map(Link::is_alternate).collect::<Vec<_>>();
+    assert_eq!(alternate, vec![true, true, true, false, false, false]);
+    let enclosure = links.iter().map(Link::is_enclosure).collect::<Vec<_>>();
+    assert_eq!(enclosure, vec![false, false, false, true, true, false]);
+}
@@ -0,0 +1,393 @@
+use std::fs;
+
+use atom_syndication::Feed;
+
+/// Exercise the accessors that interpret parsed values, which must not panic either.
+fn inspect(feed: &Feed) {
+    let _ = feed.to_string();
+    let _ = feed.byte_size_estimate();
+    let _ = feed.link_rels();
+    let _ = feed.validate();
+
+    for entry in feed.entries() {
+        let _ = entry.word_count();
+        let _ = entry.image_url();
+        let _ = entry.replies_link();
+        let _ = entry.content().map(|content| content.xml_value());
+    }
+}
+
+fn parse(input: &[u8]) {
+    if let Ok(feed) = Feed::read_from(input) {
+        inspect(&feed);
+    }
+}
+
+#[test]
+fn malformed_corpus() {
+    for file in fs::read_dir("tests/data/malformed").unwrap() {
+        let input = fs::read(file.unwrap().path()).unwrap();
+        parse(&input);
+    }
+}
+
+#[test]
+fn truncated_fixtures() {
+    for file in fs::read_dir("tests/data").unwrap() {
+        let path = file.unwrap().path();
+        if path.is_dir() {
+            continue;
+        }
+
+        let input = fs::read(path).unwrap();
+        for end in 0..input.len() {
+            parse(&input[..end]);
+        }
+    }
+}
+
+#[test]
+fn corrupted_fixtures() {
+    for file in fs::read_dir("tests/data").unwrap() {
+        let path = file.unwrap().path();
+        if path.is_dir() {
+            continue;
+        }
+
+        let input = fs::read(path).unwrap();
+        for (index, replacement) in input
+            .iter()
+            .enumerate()
+            .flat_map(|(index, _)| b"<>&\"/\xff".iter().map(move |b| (index, *b)))
+        {
+            let mut corrupted = input.clone();
+            corrupted[index] = replacement;
+            parse(&corrupted);
+        }
+    }
+}
+
+#[test]
+fn deeply_nested() {
+    use atom_syndication::{Error, ReadOptions};
+
+    let nested = |depth: usize| {
+        format!(
+            "<feed><entry><ext:a>{}{}</ext:a></entry></feed>",
+            "<ext:a>".repeat(depth),
+            "</ext:a>".repeat(depth)
+        )
+    };
+
+    let xml = nested(100_000);
+    assert!(matches!(
+        Feed::read_from(xml.as_bytes()),
+        Err(Error::MaxDepthExceeded)
+    ));
+
+    let xml = nested(100);
+    assert!(Feed::read_from(xml.as_bytes()).is_ok());
+
+    let mut options = ReadOptions::default();
+    options.max_depth = 10;
+    assert!(matches!(
+        Feed::read_with(xml.as_bytes(), options.clone()),
+        Err(Error::MaxDepthExceeded)
+    ));
+
+    let xml = "<feed><entry><title>a<b><c><d>deep</d></c></b></title></entry></feed>";
+    options.max_depth = 5;
+    assert!(Feed::read_with(xml.as_bytes(), options.clone()).is_err());
+    options.max_depth = 6;
+    assert!(Feed::read_with(xml.as_bytes(), options).is_ok());
+}
+
+#[test]
+fn case_insensitive_elements() {
+    use atom_syndication::ReadOptions;
+
+    let xml = r#"
+        <FEED xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com/ext">
+            <Title>Feed Title</Title>
+            <Link href="http://example.com/"/>
+            <Category Term="ignored"/>
+            <Unknown><Nested/></Unknown>
+            <ext:CamelCase>value</ext:CamelCase>
+            <ENTRY>
+                <ID>urn:entry</ID>
+                <Title type="html">Entry Title</Title>
+                <Author><NAME>John Doe</NAME></Author>
+                <Summary>Entry summary</Summary>
+            </ENTRY>
+        </FEED>
+    "#;
+
+    assert!(Feed::read_from(xml.as_bytes()).is_err());
+
+    let mut options = ReadOptions::default();
+    options.case_insensitive_elements = true;
+    let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.links()[0].href(), "http://example.com/");
+    assert_eq!(feed.categories().len(), 1);
+    assert!(feed.extensions()["ext"].contains_key("CamelCase"));
+    assert_eq!(feed.entries().len(), 1);
+
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.id(), "urn:entry");
+    assert_eq!(entry.title(), "Entry Title");
+    assert_eq!(entry.authors()[0].name(), "John Doe");
+    assert_eq!(entry.summary().map(|s| s.as_str()), Some("Entry summary"));
+}
+
+#[test]
+fn max_duration() {
+    use std::time::Duration;
+
+    use atom_syndication::{Error, ReadOptions};
+
+    let xml = format!(
+        "<feed>{}<unknown>{}</unknown></feed>",
+        "<entry><id>1</id></entry>".repeat(1_000),
+        "<a/>".repeat(10_000)
+    );
+
+    let mut options = ReadOptions::default();
+    options.max_duration = Some(Duration::from_secs(0));
+    assert!(matches!(
+        Feed::read_with(xml.as_bytes(), options.clone()),
+        Err(Error::Timeout)
+    ));
+
+    let xml = format!("<feed><unknown>{}</unknown></feed>", "<a/>".repeat(10_000));
+    assert!(matches!(
+        Feed::read_with(xml.as_bytes(), options.clone()),
+        Err(Error::Timeout)
+    ));
+
+    options.max_duration = Some(Duration::from_secs(3600));
+    assert!(Feed::read_with(xml.as_bytes(), options.clone()).is_ok());
+
+    options.max_duration = Some(Duration::MAX);
+    assert!(Feed::read_with(xml.as_bytes(), options).is_ok());
+}
+
+#[test]
+fn max_text_len() {
+    use atom_syndication::{Error, ErrorKind, ReadOptions, Warning};
+
+    let xml = format!(
+        r#"<feed>
+            <title>Short</title>
+            <entry>
+                <content type="html">{}&lt;b&gt;bold&lt;/b&gt;</content>
+                <summary type="xhtml"><div>café <b>au lait</b></div></summary>
+            </entry>
+        </feed>"#,
+        "x".repeat(10_000)
+    );
+
+    let mut options = ReadOptions::default();
+    assert!(Feed::read_with(xml.as_bytes(), options.clone()).is_ok());
+
+    options.max_text_len = Some(20);
+    let error = Feed::read_with(xml.as_bytes(), options.clone()).unwrap_err();
+    assert!(matches!(error, Error::TextTooLong));
+    assert_eq!(error.kind(), ErrorKind::Limit);
+
+    options.truncate_long_text = true;
+    let (feed, warnings) = Feed::read_with_warnings(xml.as_bytes(), options.clone()).unwrap();
+    assert_eq!(feed.title(), "Short");
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.content().and_then(|c| c.value()),
+        Some(&*"x".repeat(20))
+    );
+    assert_eq!(
+        entry.summary().map(|s| s.as_str()),
+        Some("<div>café <b>au lai")
+    );
+    assert_eq!(warnings.len(), 2);
+    assert!(matches!(
+        warnings[0],
+        Warning::TextTruncated { limit: 20, .. }
+    ));
+
+    options.max_text_len = Some(9);
+    let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+    assert_eq!(
+        feed.entries()[0].summary().map(|s| s.as_str()),
+        Some("<div>caf")
+    );
+}
+
+#[test]
+fn skip_malformed_entries() {
+    use atom_syndication::{ReadOptions, Warning};
+
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Feed Title</title>
+            <entry><id>urn:1</id></entry>
+            <entry><id>urn:2</id><title type="bogus">Two<b>bold</b></title><id>urn:x</id></entry>
+            <entry>
+                <id>urn:3</id>
+                <updated>yesterday</updated>
+                <content type="xhtml"><entry><id>urn:nested</id></entry></content>
+            </entry>
+            <entry><id>urn:4</id><link href="http://example.com/4"/></entry>
+            <entry><id>urn:5</id><title type="bogus">Five</title></entry>
+        </feed>
+    "#;
+
+    assert!(Feed::read_from(xml.as_bytes()).is_err());
+
+    let mut options = ReadOptions::default();
+    options.skip_malformed_entries = true;
+    let (feed, warnings) = Feed::read_with_warnings(xml.as_bytes(), options.clone()).unwrap();
+
+    assert_eq!(feed.title(), "Feed Title");
+    let ids = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.id())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:1", "urn:4"]);
+    assert_eq!(feed.entries()[1].links()[0].href(), "http://example.com/4");
+    assert_eq!(warnings.len(), 3);
+    assert!(warnings
+        .iter()
+        .all(|warning| matches!(warning, Warning::MalformedEntry { .. })));
+    assert!(warnings[0]
+        .to_string()
+        .starts_with("skipped malformed entry at byte "));
+
+    let truncated = r#"<feed><entry><id>urn:1</id></entry><entry><updated>x</updated>"#;
+    assert!(Feed::read_with(truncated.as_bytes(), options.clone()).is_err());
+
+    let not_well_formed = r#"<feed><entry><id>urn:1</titel></entry></feed>"#;
+    assert!(Feed::read_with(not_well_formed.as_bytes(), options).is_err());
+}
+
+#[test]
+fn link_without_href() {
+    use atom_syndication::{Error, ErrorKind, ReadOptions, Warning};
+
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <link rel="self"/>
+            <link rel="alternate" href="http://example.com/"/>
+            <entry>
+                <id>urn:1</id>
+                <link rel="enclosure" type="audio/mpeg"></link>
+                <source><link/></source>
+            </entry>
+        </feed>
+    "#;
+
+    let (feed, warnings) =
+        Feed::read_with_warnings(xml.as_bytes(), ReadOptions::default()).unwrap();
+    assert_eq!(feed.links().len(), 1);
+    assert_eq!(feed.links()[0].href(), "http://example.com/");
+    assert!(feed.entries()[0].links().is_empty());
+    assert!(feed.entries()[0].source().unwrap().links().is_empty());
+    assert_eq!(warnings.len(), 3);
+    assert!(matches!(
+        warnings[0],
+        Warning::LinkWithoutHref { ref rel, .. } if rel == "self"
+    ));
+    assert!(warnings[0]
+        .to_string()
+        .starts_with("skipped a link with rel=\"self\" and no href at byte "));
+
+    let mut options = ReadOptions::default();
+    options.strict = true;
+    let error = Feed::read_with(xml.as_bytes(), options).unwrap_err();
+    assert!(matches!(error, Error::MissingLinkHref));
+    assert_eq!(error.kind(), ErrorKind::Structure);
+
+    let empty_href = r#"<feed><link rel="self" href=""/></feed>"#;
+    let feed = Feed::read_from(empty_href.as_bytes()).unwrap();
+    assert_eq!(feed.links()[0].href(), "");
+}
+
+#[test]
+fn conformance_levels() {
+    use atom_syndication::{Conformance, Error, ReadOptions};
+
+    let read = |xml: &str, conformance| {
+        Feed::read_with(xml.as_bytes(), ReadOptions::conformance(conformance))
+    };
+
+    let valid = r#"<feed><id>urn:feed</id><title>Feed</title><updated>2021-05-01T12:00:00Z</updated>
+        <entry><id>urn:1</id><title>One</title><updated>2021-05-01T12:00:00Z</updated></entry></feed>"#;
+    for &conformance in &[
+        Conformance::Lenient,
+        Conformance::Standard,
+        Conformance::Strict,
+    ] {
+        assert!(read(valid, conformance).is_ok());
+    }
+
+    let local_time = valid.replace("12:00:00Z", "12:00:00");
+    assert!(read(&local_time, Conformance::Lenient).is_ok());
+    assert!(read(&local_time, Conformance::Standard).is_ok());
+    assert!(matches!(
+        read(&local_time, Conformance::Strict),
+        Err(Error::WrongDatetime(_))
+    ));
+
+    let no_href = valid.replace("</feed>", r#"<link rel="self"/></feed>"#);
+    assert!(read(&no_href, Conformance::Standard).is_ok());
+    assert!(matches!(
+        read(&no_href, Conformance::Strict),
+        Err(Error::MissingLinkHref)
+    ));
+
+    let missing = valid
+        .replace("<title>One</title>", "")
+        .replace("<id>urn:feed</id>", "");
+    assert!(read(&missing, Conformance::Standard).is_ok());
+    match read(&missing, Conformance::Strict) {
+        Err(Error::MissingRequiredField(fields)) => {
+            assert_eq!(fields, vec!["id".to_string(), "entry[0] title".to_string()])
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut options = ReadOptions::default();
+    options.require_fields = true;
+    assert!(Feed::read_with(missing.as_bytes(), options).is_err());
+    assert_eq!(
+        ReadOptions::conformance(Conformance::default()),
+        ReadOptions::default()
+    );
+}
+
+#[test]
+fn entity_declarations() {
+    use atom_syndication::{Error, ErrorKind, ReadOptions};
+
+    let xxe = r#"<?xml version="1.0"?>
+<!DOCTYPE feed [
+  <!ENTITY xxe SYSTEM "file:///etc/passwd">
+]>
+<feed><title>&xxe;</title></feed>"#;
+
+    let error = Feed::read_from(xxe.as_bytes()).unwrap_err();
+    assert!(matches!(error, Error::ExternalEntity));
+    assert_eq!(error.kind(), ErrorKind::Structure);
+
+    let mut options = ReadOptions::default();
+    options.allow_entity_declarations = true;
+    let error = Feed::read_with(xxe.as_bytes(), options.clone()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Xml);
+
+    let unused = xxe.replace("&xxe;", "Title");
+    let feed = Feed::read_with(unused.as_bytes(), options).unwrap();
+    assert_eq!(feed.title().as_str(), "Title");
+
+    let external_subset = r#"<!DOCTYPE feed SYSTEM "http://example.com/feed.dtd"><feed/>"#;
+    assert!(Feed::read_from(external_subset.as_bytes()).is_ok());
+}
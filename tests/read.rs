@@ -4,7 +4,7 @@ use std::fs::File;
 use std::io::BufReader;
 
 use crate::atom::extension::ExtensionMap;
-use crate::atom::{Feed, Text};
+use crate::atom::{Error, ErrorKind, Feed, ReadOptions, Text, UnknownElementHandler, Warning};
 
 macro_rules! feed {
     ($f:expr) => {{
@@ -169,3 +169,912 @@ fn read_extension() {
     check_extensions(feed.extensions());
     check_extensions(entry.extensions());
 }
+
+#[test]
+fn read_entry_source() {
+    let feed = feed!("tests/data/entry_source.xml");
+
+    let entry = feed.entries().first().unwrap();
+    assert_eq!(entry.title(), "Entry Title");
+    assert_eq!(entry.id(), "http://example.com/article/1");
+    assert_eq!(entry.updated().to_rfc3339(), "2017-06-05T10:00:00+00:00");
+    assert_eq!(entry.rights().map(Text::as_str), Some("© 2017 Jane Doe"));
+
+    let source = entry.source().unwrap();
+    assert_eq!(source.id(), "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    assert_eq!(source.title(), "Original Feed");
+    assert_eq!(source.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    assert_eq!(source.icon(), Some("http://example.com/icon.png"));
+    assert_eq!(source.logo(), Some("http://example.com/logo.png"));
+    assert_eq!(source.rights().map(Text::as_str), Some("© 2017 John Doe"));
+}
+
+#[test]
+fn read_unknown_attrs() {
+    let feed = feed!("tests/data/unknown_attrs.xml");
+    let attrs = |attrs: &[(String, String)]| {
+        attrs
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        attrs(feed.unknown_attrs()),
+        vec!["v:channel=news", "data-x=1"]
+    );
+    assert_eq!(attrs(feed.title().unknown_attrs()), vec!["v:style=bold"]);
+    assert_eq!(
+        attrs(feed.authors()[0].unknown_attrs()),
+        vec!["v:role=editor"]
+    );
+    assert_eq!(
+        attrs(feed.generator().unwrap().unknown_attrs()),
+        vec!["v:build=42"]
+    );
+    assert_eq!(
+        attrs(feed.links()[0].unknown_attrs()),
+        vec!["v:rank=2", "v:kind=primary"]
+    );
+    assert_eq!(
+        attrs(feed.categories()[0].unknown_attrs()),
+        vec!["v:weight=0.5"]
+    );
+
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        attrs(entry.unknown_attrs()),
+        vec!["xmlns:media=http://search.yahoo.com/mrss/", "v:id=7"]
+    );
+    assert_eq!(entry.lang(), Some("en"));
+    assert_eq!(entry.links()[0].thr_count(), Some("3"));
+    assert!(entry.links()[0].unknown_attrs().is_empty());
+    assert_eq!(
+        attrs(entry.content().unwrap().unknown_attrs()),
+        vec!["v:format=md", "media:safe=yes"]
+    );
+    assert_eq!(
+        attrs(entry.source().unwrap().unknown_attrs()),
+        vec!["v:origin=mirror"]
+    );
+}
+
+#[test]
+fn read_person_uri() {
+    let xml = |uri: &str| {
+        format!(
+            "<feed><author><name>Jane</name><uri>{}</uri></author></feed>",
+            uri
+        )
+    };
+    let strict = || {
+        let mut options = ReadOptions::default();
+        options.strict = true;
+        options
+    };
+
+    let valid = xml("https://example.com/~jane");
+    let feed = Feed::read_with(valid.as_bytes(), strict()).unwrap();
+    assert_eq!(feed.authors()[0].uri(), Some("https://example.com/~jane"));
+
+    let malformed = xml("http://example.com/a b");
+    match Feed::read_with(malformed.as_bytes(), strict()) {
+        Err(Error::InvalidUri(uri)) => assert_eq!(uri, "http://example.com/a b"),
+        other => panic!("expected an invalid uri error, got {:?}", other),
+    }
+
+    let feed = Feed::read_from(malformed.as_bytes()).unwrap();
+    assert_eq!(feed.authors()[0].uri(), Some("http://example.com/a b"));
+}
+
+#[test]
+fn read_self_closing_generator() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <generator uri="https://example.com/gen" version="1.0"/>
+            <title>After Generator</title>
+        </feed>
+    "#;
+    let feed = xml.parse::<Feed>().unwrap();
+    let generator = feed.generator().unwrap();
+    assert_eq!(generator.value(), "");
+    assert_eq!(generator.uri(), Some("https://example.com/gen"));
+    assert_eq!(generator.version(), Some("1.0"));
+    assert_eq!(feed.title(), "After Generator");
+
+    let expanded = xml.replace("/>", "></generator>").parse::<Feed>().unwrap();
+    assert_eq!(expanded, feed);
+}
+
+#[test]
+fn read_metadata_only() {
+    let mut expected = feed!("tests/data/feed.xml");
+    expected.set_entries(Vec::new());
+    let feed = Feed::read_metadata_only(BufReader::new(File::open("tests/data/feed.xml").unwrap()))
+        .unwrap();
+    assert_eq!(feed, expected);
+
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <content type="xhtml">
+                    <div xmlns="http://www.w3.org/1999/xhtml"><entry>nested</entry></div>
+                </content>
+                <updated>not a date</updated>
+            </entry>
+            <title>After Entries</title>
+        </feed>
+    "#;
+    let feed = Feed::read_metadata_only(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "After Entries");
+    assert!(feed.entries().is_empty());
+}
+
+#[test]
+fn read_from_best_effort() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Feed Title</title>
+            <entry><id>urn:1</id></entry>
+            <entry><id>urn:2</id></entry>
+            <entry><id>urn:3</id><updated>not a date</updated></entry>
+            <entry><id>urn:4</id></entry>
+        </feed>
+    "#;
+    let (feed, error) = Feed::read_from_best_effort(xml.as_bytes());
+    assert_eq!(feed.title(), "Feed Title");
+    let ids = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.id())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:1", "urn:2"]);
+    assert!(matches!(error, Some(Error::WrongDatetime(_))));
+
+    let (feed, error) = Feed::read_from_best_effort(&b"<rss></rss>"[..]);
+    assert_eq!(feed, Feed::default());
+    assert!(matches!(error, Some(Error::InvalidStartTag)));
+
+    let valid = feed!("tests/data/feed.xml");
+    let (feed, error) =
+        Feed::read_from_best_effort(BufReader::new(File::open("tests/data/feed.xml").unwrap()));
+    assert_eq!(feed, valid);
+    assert!(error.is_none());
+}
+
+#[test]
+fn read_stylesheet() {
+    let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<?xml-stylesheet type="text/xsl" href="/feed.xsl"?>
+<?other-pi ignored?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>Styled</title></feed>"#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "Styled");
+    assert!(feed.stylesheets().is_empty());
+
+    let mut options = ReadOptions::default();
+    options.preserve_stylesheets = true;
+    let feed = Feed::read_with(xml.as_bytes(), options.clone()).unwrap();
+    assert_eq!(
+        feed.stylesheets(),
+        &[r#"type="text/xsl" href="/feed.xsl""#.to_string()]
+    );
+
+    let written = feed.to_string();
+    assert!(written.starts_with(
+        "<?xml version=\"1.0\"?>\n<?xml-stylesheet type=\"text/xsl\" href=\"/feed.xsl\"?>\n<feed"
+    ));
+    assert_eq!(Feed::read_with(written.as_bytes(), options).unwrap(), feed);
+}
+
+#[test]
+fn read_declaration() {
+    let xml = r#"<?xml version="1.0" standalone="yes"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>Standalone</title></feed>"#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.xml_version(), None);
+    assert_eq!(feed.standalone(), None);
+    assert!(feed
+        .to_string()
+        .starts_with("<?xml version=\"1.0\"?>\n<feed"));
+
+    let mut options = ReadOptions::default();
+    options.preserve_declaration = true;
+    let feed = Feed::read_with(xml.as_bytes(), options.clone()).unwrap();
+    assert_eq!(feed.xml_version(), Some("1.0"));
+    assert_eq!(feed.standalone(), Some(true));
+
+    let written = feed.to_string();
+    assert!(written.starts_with("<?xml version=\"1.0\" standalone=\"yes\"?>\n<feed"));
+    assert_eq!(
+        Feed::read_with(written.as_bytes(), options.clone()).unwrap(),
+        feed
+    );
+
+    let xml = r#"<?xml version='1.1' encoding='utf-8' standalone='no'?><feed/>"#;
+    let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+    assert_eq!(feed.xml_version(), Some("1.1"));
+    assert_eq!(feed.standalone(), Some(false));
+    assert!(feed
+        .to_string()
+        .starts_with("<?xml version=\"1.1\" standalone=\"no\"?>"));
+}
+
+#[test]
+fn read_timestamp_without_offset() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <updated>2021-05-01T12:00:00</updated>
+            <entry>
+                <updated>2021-05-01T14:00:00+02:00</updated>
+                <published>2021-04-30 08:30:00.5</published>
+            </entry>
+        </feed>
+    "#;
+
+    let (feed, warnings) =
+        Feed::read_with_warnings(xml.as_bytes(), ReadOptions::default()).unwrap();
+    assert_eq!(feed.updated().to_rfc3339(), "2021-05-01T12:00:00+00:00");
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.updated().to_rfc3339(), "2021-05-01T14:00:00+02:00");
+    assert_eq!(
+        entry.published().map(|published| published.to_rfc3339()),
+        Some("2021-04-30T08:30:00.500+00:00".to_string())
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            Warning::AssumedUtc("2021-05-01T12:00:00".to_string()),
+            Warning::AssumedUtc("2021-04-30 08:30:00.5".to_string()),
+        ]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "timestamp `2021-05-01T12:00:00` has no UTC offset, assuming UTC"
+    );
+
+    let mut options = ReadOptions::default();
+    options.assume_utc_timestamps = false;
+    let rfc3339_like = xml.replace("2021-04-30 08:30:00.5", "2021-04-30T08:30:00");
+    let (feed, warnings) =
+        Feed::read_with_warnings(rfc3339_like.as_bytes(), options.clone()).unwrap();
+    assert_eq!(feed.updated().to_rfc3339(), "2021-05-01T12:00:00+00:00");
+    assert_eq!(warnings.len(), 2);
+
+    options.strict = true;
+    assert!(matches!(
+        Feed::read_with(xml.as_bytes(), options),
+        Err(Error::WrongDatetime(ref value)) if value == "2021-05-01T12:00:00"
+    ));
+}
+
+#[test]
+fn read_html_entities() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Caf&eacute;&nbsp;News</title>
+            <rights>&copy; 2021 &mdash; All rights reserved</rights>
+            <link href="http://example.com/" title="Fish &amp; Chips &hellip;"/>
+            <entry>
+                <summary type="html">&lt;p&gt;5 &euro; &times; 2&lt;/p&gt;</summary>
+            </entry>
+        </feed>
+    "#;
+
+    assert!(Feed::read_from(xml.as_bytes()).is_err());
+
+    let mut options = ReadOptions::default();
+    options.html_entities = true;
+    let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+
+    assert_eq!(feed.title(), "Caf\u{e9}\u{a0}News");
+    assert_eq!(
+        feed.rights().map(Text::as_str),
+        Some("\u{a9} 2021 \u{2014} All rights reserved")
+    );
+    assert_eq!(feed.links()[0].title(), Some("Fish & Chips \u{2026}"));
+    assert_eq!(
+        feed.entries()[0].summary().map(Text::as_str),
+        Some("<p>5 \u{20ac} \u{d7} 2</p>")
+    );
+}
+
+#[test]
+fn read_with_stats() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com/ext">
+    <updated>2021-05-01T12:00:00</updated>
+    <unknown><nested/></unknown>
+    <ext:known>kept as an extension</ext:known>
+    <author><name>Jane</name><nickname>jd</nickname></author>
+    <entry><id>1</id><unknown/></entry>
+    <entry><id>2</id><updated>2021-05-01T12:00:00</updated></entry>
+</feed>"#;
+
+    let (feed, stats) = Feed::read_from_with_stats(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries().len(), 2);
+    assert_eq!(stats.entries, 2);
+    assert_eq!(stats.unknown_elements, 3);
+    assert_eq!(stats.warnings, 2);
+    assert_eq!(stats.bytes, xml.len());
+
+    assert!(Feed::read_from_with_stats("<feed><entry>".as_bytes()).is_err());
+}
+
+#[test]
+fn read_with_text_stats() {
+    let xml = r#"<feed>
+    <title>Fish &amp; Chips</title>
+    <id>urn:feed</id>
+    <entry><title>A &#38; B</title><summary>Plain</summary></entry>
+</feed>"#;
+
+    let (feed, stats) = Feed::read_from_with_stats(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title().as_cow(), "Fish & Chips");
+    assert_eq!(stats.borrowable_texts, 2);
+    assert_eq!(stats.unescaped_texts, 2);
+}
+
+#[test]
+fn read_with_declared_encoding() {
+    let mut xml = b"<?xml version='1.0' encoding='iso-8859-1'?><feed><title>Caf".to_vec();
+    xml.extend_from_slice(b"\xe9</title></feed>");
+
+    let (feed, stats) = Feed::read_from_with_stats(&xml[..]).unwrap();
+    assert_eq!(feed.title().as_str(), "Caf\u{e9}");
+    assert_eq!(stats.declared_encoding.as_deref(), Some("iso-8859-1"));
+
+    let xml = r#"<?xml version="1.0"?><feed></feed>"#;
+    let (_, stats) = Feed::read_from_with_stats(xml.as_bytes()).unwrap();
+    assert_eq!(stats.declared_encoding, None);
+
+    let (_, stats) = Feed::read_from_with_stats(&b"<feed></feed>"[..]).unwrap();
+    assert_eq!(stats.declared_encoding, None);
+}
+
+#[test]
+fn read_redeclared_default_namespace() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title xmlns="http://www.w3.org/2005/Atom">Feed Title</title>
+            <entry xmlns="http://www.w3.org/2005/Atom">
+                <id>urn:1</id>
+                <title>First</title>
+                <author xmlns="http://www.w3.org/2005/Atom"><name>Jane</name></author>
+            </entry>
+            <entry xmlns="http://www.w3.org/2005/Atom">
+                <id>urn:2</id>
+                <title xmlns="http://www.w3.org/2005/Atom">Second</title>
+            </entry>
+        </feed>
+    "#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+    assert!(feed.title().unknown_attrs().is_empty());
+    assert_eq!(feed.entries().len(), 2);
+
+    let first = &feed.entries()[0];
+    assert_eq!(first.id(), "urn:1");
+    assert_eq!(first.title(), "First");
+    assert_eq!(first.authors()[0].name(), "Jane");
+    assert!(first.unknown_attrs().is_empty());
+
+    let second = &feed.entries()[1];
+    assert_eq!(second.id(), "urn:2");
+    assert_eq!(second.title(), "Second");
+
+    let reread = feed.to_string().parse::<Feed>().unwrap();
+    assert_eq!(reread, feed);
+}
+
+#[test]
+fn read_raw_text() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Fish &amp; Chips &#169;</title>
+            <link href="http://example.com/?a=1&amp;b=2"/>
+            <entry>
+                <summary type="html">&lt;p&gt;Fish &amp;amp; Chips&lt;/p&gt;</summary>
+                <content type="html"><![CDATA[<p>Fish &amp; Chips</p>]]></content>
+            </entry>
+        </feed>
+    "#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "Fish & Chips \u{a9}");
+
+    let mut options = ReadOptions::default();
+    options.raw_text = true;
+    let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+
+    assert_eq!(feed.title(), "Fish &amp; Chips &#169;");
+    assert_eq!(feed.links()[0].href(), "http://example.com/?a=1&b=2");
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.summary().map(Text::as_str),
+        Some("&lt;p&gt;Fish &amp;amp; Chips&lt;/p&gt;")
+    );
+    assert_eq!(
+        entry.content().and_then(|content| content.value()),
+        Some("<![CDATA[<p>Fish &amp; Chips</p>]]>")
+    );
+}
+
+#[test]
+fn read_large_content() {
+    let body = "Fish &amp; Chips. ".repeat(100_000);
+    let xml = format!(
+        r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <content type="html">{}<![CDATA[<p>end</p>]]></content>
+            </entry>
+        </feed>"#,
+        body
+    );
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    let value = feed.entries()[0].content().unwrap().value().unwrap();
+    assert_eq!(
+        value.len(),
+        "Fish & Chips. ".len() * 100_000 + "<p>end</p>".len()
+    );
+    assert!(value.starts_with("Fish & Chips. Fish & Chips."));
+    assert!(value.ends_with("Chips. <p>end</p>"));
+}
+
+#[test]
+fn read_html_page() {
+    let page = r#"<!DOCTYPE html>
+        <html lang="en">
+            <head><title>404 Not Found</title></head>
+            <body><h1>Not Found</h1></body>
+        </html>
+    "#;
+    let error = Feed::read_from(page.as_bytes()).unwrap_err();
+    assert!(matches!(error, Error::NotAFeed { ref found_root } if found_root == "html"));
+    assert_eq!(
+        error.to_string(),
+        "expected an Atom feed but got an HTML page with root element <html>"
+    );
+
+    assert!(matches!(
+        Feed::read_from(&b"<HTML><BODY>Oops</BODY></HTML>"[..]),
+        Err(Error::NotAFeed { found_root }) if found_root == "HTML"
+    ));
+    assert!(matches!(
+        Feed::read_from(&b"<!doctype html><body>Oops</body>"[..]),
+        Err(Error::NotAFeed { found_root }) if found_root == "body"
+    ));
+    assert!(matches!(
+        Feed::read_from(&b"<rss></rss>"[..]),
+        Err(Error::InvalidStartTag)
+    ));
+}
+
+#[test]
+fn read_error_kinds() {
+    struct FailingReader;
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "connection reset",
+            ))
+        }
+    }
+
+    let kind = |xml: &str| Feed::read_from(xml.as_bytes()).unwrap_err().kind();
+    assert_eq!(kind("<feed><title>Title</feed>"), ErrorKind::Xml);
+    assert_eq!(kind("<feed><title>Title"), ErrorKind::Xml);
+    assert_eq!(kind(""), ErrorKind::Xml);
+    assert_eq!(kind("<rss></rss>"), ErrorKind::Structure);
+    assert_eq!(kind("<html></html>"), ErrorKind::Structure);
+    assert_eq!(
+        kind("<feed><updated>yesterday</updated></feed>"),
+        ErrorKind::Structure
+    );
+
+    let error = Feed::read_from(BufReader::new(FailingReader)).unwrap_err();
+    assert!(
+        matches!(error, Error::Io(ref err) if err.kind() == std::io::ErrorKind::ConnectionReset)
+    );
+    assert_eq!(error.kind(), ErrorKind::Io);
+
+    let mut options = ReadOptions::default();
+    options.max_depth = 1;
+    let error = Feed::read_with("<feed><title>Title</title></feed>".as_bytes(), options);
+    assert_eq!(error.unwrap_err().kind(), ErrorKind::Limit);
+}
+
+#[test]
+fn read_all() {
+    let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>First</title>
+            <entry><id>urn:1</id></entry>
+        </feed>
+
+        <!-- archive 2 -->
+        <?xml version="1.0" encoding="utf-8"?>
+        <feed xmlns="http://www.w3.org/2005/Atom"><title>Second</title></feed>
+        <feed xmlns="http://www.w3.org/2005/Atom"><title>Third</title></feed>
+    "#;
+    let feeds = Feed::read_all(xml.as_bytes()).unwrap();
+    let titles = feeds
+        .iter()
+        .map(|feed| feed.title().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(titles, vec!["First", "Second", "Third"]);
+    assert_eq!(feeds[0].entries()[0].id(), "urn:1");
+
+    assert!(Feed::read_all(&b"  "[..]).unwrap().is_empty());
+    assert!(matches!(
+        Feed::read_all(&b"<feed></feed><rss></rss>"[..]),
+        Err(Error::InvalidStartTag)
+    ));
+    assert!(matches!(
+        Feed::read_all(&b"<feed></feed><feed><title>"[..]),
+        Err(Error::Eof)
+    ));
+}
+
+#[test]
+fn read_split_email_names() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <author><email>jane@example.com</email></author>
+            <author><email>Jane Doe jane@example.com</email></author>
+            <author><email>"Jane Doe" &lt;jane@example.com&gt;</email></author>
+            <author><email>jane@example.com (Jane Doe)</email></author>
+            <author><name>Jane</name><email>Jane Doe jane@example.com</email></author>
+            <author><email>Jane&lt;jane@example.com&gt;</email></author>
+            <author><email>Jane Doe</email></author>
+        </feed>
+    "#;
+    let people = |feed: &Feed| {
+        feed.authors()
+            .iter()
+            .map(|person| {
+                (
+                    person.name().to_string(),
+                    person.email().map(str::to_string),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let person = |name: &str, email: &str| (name.to_string(), Some(email.to_string()));
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(people(&feed)[0], person("", "jane@example.com"));
+    assert_eq!(people(&feed)[1], person("", "Jane Doe jane@example.com"));
+
+    let mut options = ReadOptions::default();
+    options.split_email_names = true;
+    let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+    assert_eq!(
+        people(&feed),
+        vec![
+            person("", "jane@example.com"),
+            person("Jane Doe", "jane@example.com"),
+            person("Jane Doe", "jane@example.com"),
+            person("Jane Doe", "jane@example.com"),
+            person("Jane", "jane@example.com"),
+            person("Jane", "jane@example.com"),
+            person("", "Jane Doe"),
+        ]
+    );
+}
+
+#[test]
+fn read_unknown_element_handler() {
+    use std::sync::{Arc, Mutex};
+
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com/ext">
+            <unknown><nested/></unknown>
+            <ext:known>extension</ext:known>
+            <author><name>Jane</name><nickname>jd</nickname></author>
+            <entry>
+                <rating xmlns="http://example.com/rating">5</rating>
+                <other/>
+            </entry>
+            <entry xmlns="">
+                <id>urn:2</id>
+            </entry>
+        </feed>
+    "#;
+    let skipped = Arc::new(Mutex::new(Vec::new()));
+    let names = Arc::clone(&skipped);
+    let mut options = ReadOptions::default();
+    options.unknown_element_handler = Some(UnknownElementHandler::new(move |ns, name| {
+        names
+            .lock()
+            .unwrap()
+            .push((ns.to_string(), name.to_string()));
+    }));
+
+    let feed = Feed::read_with(xml.as_bytes(), options.clone()).unwrap();
+    assert_eq!(feed.entries().len(), 2);
+    let atom = "http://www.w3.org/2005/Atom".to_string();
+    assert_eq!(
+        *skipped.lock().unwrap(),
+        vec![
+            (atom.clone(), "unknown".to_string()),
+            (atom.clone(), "nickname".to_string()),
+            (
+                "http://example.com/rating".to_string(),
+                "rating".to_string()
+            ),
+            (atom, "other".to_string()),
+        ]
+    );
+    assert_eq!(options.clone(), options);
+}
+
+#[test]
+fn read_epoch_timestamps() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:p="http://example.com/platform">
+            <entry>
+                <id>urn:1</id>
+                <published_timestamp>1620000000</published_timestamp>
+                <p:modified> 1620003600 </p:modified>
+            </entry>
+        </feed>
+    "#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries()[0].published(), None);
+    assert!(feed.entries()[0].extensions().contains_key("p"));
+
+    let mut options = ReadOptions::default();
+    options.epoch_published_element = Some("published_timestamp".to_string());
+    options.epoch_updated_element = Some("p:modified".to_string());
+    let feed = Feed::read_with(xml.as_bytes(), options.clone()).unwrap();
+
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.published().map(|date| date.to_rfc3339()),
+        Some("2021-05-03T00:00:00+00:00".to_string())
+    );
+    assert_eq!(entry.updated().to_rfc3339(), "2021-05-03T01:00:00+00:00");
+    assert!(entry.extensions().is_empty());
+    assert!(feed
+        .to_string()
+        .contains("<published>2021-05-03T00:00:00+00:00</published>"));
+
+    let invalid = r#"<feed><entry><published_timestamp>soon</published_timestamp></entry></feed>"#;
+    assert!(matches!(
+        Feed::read_with(invalid.as_bytes(), options),
+        Err(Error::WrongDatetime(ref value)) if value == "soon"
+    ));
+}
+
+#[test]
+fn read_rss_elements() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">
+            <lastBuildDate>Sat, 01 May 2021 12:00:00 GMT</lastBuildDate>
+            <entry>
+                <id>urn:1</id>
+                <pubDate>Sat, 01 May 2021 12:00:00 GMT</pubDate>
+                <dc:date>2021-05-02T08:30:00+02:00</dc:date>
+                <guid>urn:1</guid>
+            </entry>
+            <entry>
+                <id>urn:2</id>
+                <published>2021-04-01T00:00:00Z</published>
+                <pubDate>yesterday</pubDate>
+            </entry>
+        </feed>
+    "#;
+
+    let (feed, warnings) =
+        Feed::read_with_warnings(xml.as_bytes(), ReadOptions::default()).unwrap();
+    assert_eq!(feed.entries()[0].published(), None);
+    assert!(feed.entries()[0].extensions().contains_key("dc"));
+    assert_eq!(warnings.len(), 4);
+    let skipped = warnings
+        .iter()
+        .map(|warning| match warning {
+            Warning::SkippedRssElement { name, parent, .. } => (name.as_str(), parent.as_str()),
+            warning => panic!("unexpected warning: {}", warning),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        skipped[..3],
+        [
+            ("lastBuildDate", "feed"),
+            ("pubDate", "entry"),
+            ("guid", "entry")
+        ]
+    );
+    assert!(warnings[0]
+        .to_string()
+        .starts_with("skipped RSS element <lastBuildDate> in feed at byte "));
+
+    let mut options = ReadOptions::default();
+    options.map_rss_dates = true;
+    let (feed, warnings) = Feed::read_with_warnings(xml.as_bytes(), options).unwrap();
+
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.published().map(|date| date.to_rfc3339()),
+        Some("2021-05-01T12:00:00+00:00".to_string())
+    );
+    assert_eq!(entry.updated().to_rfc3339(), "2021-05-02T08:30:00+02:00");
+    assert_eq!(
+        entry.extensions()["dc"]["date"][0].value(),
+        Some("2021-05-02T08:30:00+02:00")
+    );
+
+    let entry = &feed.entries()[1];
+    assert_eq!(
+        entry.published().map(|date| date.to_rfc3339()),
+        Some("2021-04-01T00:00:00+00:00".to_string())
+    );
+    assert_eq!(warnings.len(), 3);
+    assert!(matches!(
+        warnings[2],
+        Warning::UnparseableRssDate { ref name, ref value, .. } if name == "pubDate" && value == "yesterday"
+    ));
+
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://example.com/not-dc">
+            <entry xmlns:dcterms="http://purl.org/dc/elements/1.1/">
+                <dcterms:date>2021-05-02T08:30:00Z</dcterms:date>
+            </entry>
+            <entry>
+                <dc:date>2021-05-02T08:30:00Z</dc:date>
+            </entry>
+        </feed>
+    "#;
+    let mut options = ReadOptions::default();
+    options.map_rss_dates = true;
+    let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+    assert_eq!(
+        feed.entries()[0].updated().to_rfc3339(),
+        "2021-05-02T08:30:00+00:00"
+    );
+    assert!(feed.entries()[0].extensions().contains_key("dcterms"));
+    assert_eq!(
+        feed.entries()[1].updated().to_rfc3339(),
+        "1970-01-01T00:00:00+00:00"
+    );
+}
+
+#[test]
+fn read_metadata_after_entries() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com/ext">
+            <entry><id>urn:entry:1</id><title>First</title></entry>
+            <title>Feed Title</title>
+            <id>urn:feed</id>
+            <entry><id>urn:entry:2</id></entry>
+            <updated>2021-05-01T12:00:00Z</updated>
+            <author><name>Jane Doe</name></author>
+            <link rel="self" href="http://example.com/feed.atom"/>
+            <subtitle>Feed Subtitle</subtitle>
+            <ext:note>Kept</ext:note>
+        </feed>
+    "#;
+
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.id(), "urn:feed");
+    assert_eq!(feed.updated().to_rfc3339(), "2021-05-01T12:00:00+00:00");
+    assert_eq!(feed.authors()[0].name(), "Jane Doe");
+    assert_eq!(feed.links()[0].href(), "http://example.com/feed.atom");
+    assert_eq!(feed.subtitle().map(|s| s.as_str()), Some("Feed Subtitle"));
+    assert_eq!(feed.extensions()["ext"]["note"][0].value(), Some("Kept"));
+
+    let ids = feed
+        .entries()
+        .iter()
+        .map(|entry| entry.id())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:entry:1", "urn:entry:2"]);
+    assert_eq!(feed.entries()[0].title(), "First");
+}
+
+#[test]
+fn read_from_chunks() {
+    use std::io;
+
+    let xml = std::fs::read("tests/data/feed.xml").unwrap();
+    let expected = Feed::read_from(&xml[..]).unwrap();
+
+    let one_byte = xml
+        .chunks(1)
+        .map(|chunk| Ok::<_, io::Error>(chunk.to_vec()));
+    assert_eq!(Feed::read_from_chunks(one_byte).unwrap(), expected);
+
+    let with_empty = xml
+        .chunks(100)
+        .flat_map(|chunk| vec![Ok::<_, io::Error>(&[][..]), Ok(chunk)]);
+    assert_eq!(Feed::read_from_chunks(with_empty).unwrap(), expected);
+
+    let failing = xml
+        .chunks(100)
+        .take(2)
+        .map(Ok)
+        .chain(std::iter::once(Err(io::Error::new(
+            io::ErrorKind::ConnectionReset,
+            "reset",
+        ))));
+    match Feed::read_from_chunks(failing) {
+        Err(atom_syndication::Error::Io(err)) => {
+            assert_eq!(err.kind(), io::ErrorKind::ConnectionReset)
+        }
+        other => panic!(
+            "unexpected result: {:?}",
+            other.map(|feed| feed.to_string())
+        ),
+    }
+}
+
+#[test]
+fn read_header() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Feed Title</title>
+            <updated>2021-05-01T12:00:00Z</updated>
+            <entry><id>urn:1</id><title>First</title></entry>
+            <subtitle>After entries</subtitle>
+            <entry><id>urn:2</id><updated>not a date</updated></entry>
+            <entry><id>urn:3</id></entry>
+        </feed>
+    "#;
+
+    let (feed, mut entries) = Feed::read_header(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.updated().to_rfc3339(), "2021-05-01T12:00:00+00:00");
+    assert!(feed.entries().is_empty());
+
+    assert_eq!(entries.next().unwrap().unwrap().title(), "First");
+    assert!(matches!(entries.next(), Some(Err(Error::WrongDatetime(_)))));
+    assert!(entries.next().is_none());
+
+    let xml = "<feed><title>Empty</title></feed>";
+    let (feed, entries) = Feed::read_header(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "Empty");
+    assert_eq!(entries.count(), 0);
+
+    let xml = "<feed><entry><id>urn:1</id></entry><entry><id>urn:2</id></entry></feed>";
+    let (_, entries) = Feed::read_header(xml.as_bytes()).unwrap();
+    let ids = entries
+        .map(|entry| entry.unwrap().id().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:1", "urn:2"]);
+
+    let (_, mut entries) = Feed::read_header("<feed><entry><id>1</id>".as_bytes()).unwrap();
+    assert!(matches!(entries.next(), Some(Err(Error::Eof))));
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn read_with_positions() {
+    use std::io::BufReader;
+
+    let xml = "<?xml version=\"1.0\"?>\r\n<feed>\r\n\t<title>Ünïcode</title><entry><id>1</id></entry>\n\n  <entry>\n<id>2</id></entry></feed>";
+
+    // A tiny buffer makes the parser consume the input in many small pieces.
+    let reader = BufReader::with_capacity(3, xml.as_bytes());
+    let (feed, positions) = Feed::read_from_with_positions(reader).unwrap();
+    assert_eq!(feed.entries().len(), 2);
+
+    let positions = positions
+        .iter()
+        .map(|position| (position.line, position.column, position.offset))
+        .collect::<Vec<_>>();
+    let first = xml.find("<entry>").unwrap();
+    let second = xml.rfind("<entry>").unwrap();
+    assert_eq!(positions, vec![(3, 26, first), (5, 3, second)]);
+
+    let (feed, positions) = Feed::read_from_with_positions("<feed/>".as_bytes()).unwrap();
+    assert!(feed.entries().is_empty());
+    assert!(positions.is_empty());
+}
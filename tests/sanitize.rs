@@ -0,0 +1,106 @@
+#![cfg(feature = "ammonia")]
+
+use atom_syndication::{Feed, Text, TextType};
+
+#[test]
+fn sanitize_html() {
+    let mut feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title type="html">&lt;script&gt;kept()&lt;/script&gt;</title>
+            <entry>
+                <summary type="html">&lt;a href="javascript:steal()"&gt;Link&lt;/a&gt;</summary>
+                <content type="html"><![CDATA[
+                    <p onmouseover="steal()">Hello <b>world</b></p>
+                    <script>steal()</script>
+                    <img src="http://example.com/a.png" onerror="steal()">
+                ]]></content>
+            </entry>
+            <entry>
+                <summary>&lt;script&gt;plain&lt;/script&gt;</summary>
+                <content type="text">&lt;script&gt;plain&lt;/script&gt;</content>
+            </entry>
+            <entry>
+                <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><br/></div></content>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+    feed.sanitize_html();
+
+    assert_eq!(feed.title().as_str(), "<script>kept()</script>");
+
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.summary(),
+        Some(&Text {
+            value: r#"<a rel="noopener noreferrer">Link</a>"#.to_string(),
+            r#type: TextType::Html,
+            ..Text::default()
+        })
+    );
+    let content = entry.content().and_then(|content| content.value()).unwrap();
+    assert!(content.contains("<p>Hello <b>world</b></p>"));
+    assert!(content.contains(r#"<img src="http://example.com/a.png">"#));
+    assert!(!content.contains("steal"));
+
+    let entry = &feed.entries()[1];
+    assert_eq!(
+        entry.summary().map(Text::as_str),
+        Some("<script>plain</script>")
+    );
+    assert_eq!(
+        entry.content().and_then(|content| content.value()),
+        Some("<script>plain</script>")
+    );
+
+    let entry = &feed.entries()[2];
+    let content = entry.content().unwrap();
+    assert_eq!(content.content_type(), Some("html"));
+    assert_eq!(content.value(), Some("<br>"));
+}
+
+#[test]
+fn sanitize_xhtml() {
+    let mut feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <summary type="xhtml">
+                    <div xmlns="http://www.w3.org/1999/xhtml"><b>Hi</b><script>steal()</script></div>
+                </summary>
+                <content type="xhtml">
+                    <div xmlns="http://www.w3.org/1999/xhtml"><p onclick="steal()">Hi</p><script>steal()</script></div>
+                </content>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+    feed.sanitize_html();
+
+    let entry = &feed.entries()[0];
+    let summary = entry.summary().unwrap();
+    assert_eq!(summary.r#type, TextType::Html);
+    assert_eq!(summary.as_str().trim(), "<b>Hi</b>");
+    let content = entry.content().unwrap();
+    assert_eq!(content.content_type(), Some("html"));
+    assert_eq!(content.value().map(str::trim), Some("<p>Hi</p>"));
+}
+
+#[test]
+fn sanitize_text_html() {
+    let mut feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <content type="text/html">&lt;p&gt;Hi&lt;/p&gt;&lt;script&gt;steal()&lt;/script&gt;</content>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+    feed.sanitize_html();
+
+    let content = feed.entries()[0].content().unwrap();
+    assert_eq!(content.content_type(), Some("text/html"));
+    assert_eq!(content.value(), Some("<p>Hi</p>"));
+}
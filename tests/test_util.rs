@@ -0,0 +1,31 @@
+#![cfg(feature = "test-util")]
+
+use atom_syndication::{Entry, Feed, Link};
+
+#[test]
+fn assert_round_trips() {
+    let feed = Feed::read_from(std::io::BufReader::new(
+        std::fs::File::open("tests/data/feed.xml").unwrap(),
+    ))
+    .unwrap();
+    feed.assert_round_trips().unwrap();
+
+    let mut entry = Entry::default();
+    entry.set_links(vec![Link::alternate("http://example.com/1")]);
+    entry.set_comments(vec![" note ".to_string()]);
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry]);
+    feed.assert_round_trips().unwrap();
+}
+
+#[test]
+fn assert_round_trips_mismatch() {
+    let mut feed = Feed::default();
+    feed.set_icon(String::new());
+
+    let error = feed.assert_round_trips().unwrap_err();
+    assert!(error.contains("`icon` differs"));
+    assert!(error.contains("written: Some(\"\")"));
+    assert!(error.contains("read:    None"));
+    assert!(!error.contains("`title`"));
+}
@@ -139,3 +139,148 @@ fn text_xhtml() {
     assert_eq!(title.lang, None);
     assert_eq!(title.r#type, TextType::Xhtml);
 }
+
+#[test]
+fn text_decoded_html() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title type="html">&amp;lt;b&amp;gt;Bold&amp;lt;/b&amp;gt; &amp;amp;#39;quoted&amp;amp;#39;</title>
+            <subtitle type="html">&lt;b&gt;Single&lt;/b&gt; &amp;amp; encoded</subtitle>
+            <rights>Plain &amp;amp; double</rights>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    assert_eq!(feed.title().decoded_html(), "<b>Bold</b> &#39;quoted&#39;");
+    assert_eq!(
+        feed.subtitle().unwrap().decoded_html(),
+        "<b>Single</b> &amp; encoded"
+    );
+    assert_eq!(feed.rights().unwrap().decoded_html(), "Plain & double");
+    assert_eq!(Text::plain("No entities").decoded_html(), "No entities");
+    assert_eq!(Text::plain("AT&T").decoded_html(), "AT&T");
+}
+
+#[test]
+fn subtitle_html_round_trip() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <subtitle type="html">&lt;em&gt;Fresh&lt;/em&gt; news &amp;amp; views</subtitle>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let subtitle = feed.subtitle().unwrap();
+    assert_eq!(subtitle.r#type, TextType::Html);
+    assert_eq!(feed.subtitle_str(), Some("<em>Fresh</em> news &amp; views"));
+
+    let xml = feed.to_string();
+    assert!(xml.contains(
+        r#"<subtitle type="html">&lt;em&gt;Fresh&lt;/em&gt; news &amp;amp; views</subtitle>"#
+    ));
+
+    let reread = xml.parse::<Feed>().unwrap();
+    assert_eq!(reread.subtitle(), feed.subtitle());
+    assert_eq!(Feed::default().subtitle_str(), None);
+}
+
+#[test]
+fn looks_like_html() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Foo &lt;b&gt;Bar&lt;/b&gt;</title>
+            <subtitle>Fish &amp; Chips &lt; 5 &gt; 3</subtitle>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    assert_eq!(feed.title().r#type, TextType::Text);
+    assert!(feed.title().looks_like_html());
+    assert!(!feed.subtitle().unwrap().looks_like_html());
+
+    assert!(Text::plain("line<br/>break").looks_like_html());
+    assert!(Text::plain("trailing </p>").looks_like_html());
+    assert!(Text::plain("<!-- comment -->").looks_like_html());
+    assert!(!Text::plain("a <b").looks_like_html());
+    assert!(!Text::plain("</ >").looks_like_html());
+    assert!(!Text::plain("").looks_like_html());
+}
+
+#[test]
+fn trim_modes() {
+    use atom_syndication::{ReadOptions, TrimMode};
+
+    let xml = r#"
+        <feed>
+            <title>
+                Feed   Title
+            </title>
+            <subtitle type="html"> <b>Bold</b>  <i>italic</i> </subtitle>
+            <rights type="xhtml"><div> Kept </div></rights>
+        </feed>
+    "#;
+    let read = |trim_text, trim_html| {
+        let mut options = ReadOptions::default();
+        options.trim_text = trim_text;
+        options.trim_html = trim_html;
+        let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+        (
+            feed.title().to_string(),
+            feed.subtitle().unwrap().to_string(),
+            feed.rights().unwrap().to_string(),
+        )
+    };
+
+    let (title, subtitle, rights) = read(TrimMode::None, TrimMode::None);
+    assert_eq!(title, "\n                Feed   Title\n            ");
+    assert_eq!(subtitle, " <b>Bold</b>  <i>italic</i> ");
+    assert_eq!(rights, "<div> Kept </div>");
+    assert_eq!(
+        Feed::read_from(xml.as_bytes()).unwrap().title(),
+        title.as_str()
+    );
+
+    let (title, subtitle, _) = read(TrimMode::Ends, TrimMode::Ends);
+    assert_eq!(title, "Feed   Title");
+    assert_eq!(subtitle, "<b>Bold</b>  <i>italic</i>");
+
+    let (title, subtitle, rights) = read(TrimMode::Ends, TrimMode::Collapse);
+    assert_eq!(title, "Feed   Title");
+    assert_eq!(subtitle, " <b>Bold</b> <i>italic</i> ");
+    assert_eq!(rights, "<div> Kept </div>");
+
+    let (title, _, _) = read(TrimMode::Collapse, TrimMode::None);
+    assert_eq!(title, " Feed Title ");
+}
+
+#[test]
+fn trim_before_max_text_len() {
+    use atom_syndication::{Error, ReadOptions, TrimMode};
+
+    let xml = "<feed><title>\n        Title   with   spaces\n    </title></feed>";
+    let read = |trim_text, truncate_long_text| {
+        let mut options = ReadOptions::default();
+        options.trim_text = trim_text;
+        options.max_text_len = Some(19);
+        options.truncate_long_text = truncate_long_text;
+        Feed::read_with(xml.as_bytes(), options).map(|feed| feed.title().to_string())
+    };
+
+    assert!(matches!(
+        read(TrimMode::None, false),
+        Err(Error::TextTooLong)
+    ));
+    assert!(matches!(
+        read(TrimMode::Ends, false),
+        Err(Error::TextTooLong)
+    ));
+    assert_eq!(read(TrimMode::Ends, true).unwrap(), "Title   with   spac");
+    assert_eq!(
+        read(TrimMode::Collapse, false).unwrap(),
+        " Title with spaces "
+    );
+    assert_eq!(read(TrimMode::None, true).unwrap(), "\n        Title   wi");
+}
@@ -0,0 +1,265 @@
+#![cfg(feature = "url")]
+
+use atom_syndication::{Entry, Feed, Link, Person, Source};
+
+fn link_to(href: &str) -> Link {
+    let mut link = Link::default();
+    link.set_href(href);
+    link
+}
+
+#[test]
+fn cleaned_href() {
+    let link = link_to("https://example.com/post?id=7&utm_source=rss&utm_campaign=x&fbclid=1");
+    assert_eq!(link.cleaned_href(), "https://example.com/post?id=7");
+
+    let link = link_to("https://example.com/post?utm_source=rss");
+    assert_eq!(link.cleaned_href(), "https://example.com/post");
+
+    let link = link_to("/relative?utm_source=rss");
+    assert_eq!(link.cleaned_href(), "/relative?utm_source=rss");
+
+    let link = link_to("https://example.com/post?b=2&a=1");
+    assert_eq!(link.cleaned_href(), "https://example.com/post?b=2&a=1");
+}
+
+#[test]
+fn strip_tracking_params() {
+    let mut source = Source::default();
+    source.set_links(vec![link_to("https://example.com/source?ref=feed")]);
+
+    let mut entry = Entry::default();
+    entry.set_links(vec![link_to("https://example.com/entry?ref=feed&page=2")]);
+    entry.set_source(source);
+
+    let mut feed = Feed::default();
+    feed.set_links(vec![link_to(
+        "https://example.com/?ref=feed&utm_medium=rss",
+    )]);
+    feed.set_entries(vec![entry]);
+
+    feed.strip_tracking_params(&["ref"]);
+
+    assert_eq!(
+        feed.links()[0].href(),
+        "https://example.com/?utm_medium=rss"
+    );
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.links()[0].href(), "https://example.com/entry?page=2");
+    assert_eq!(
+        entry.source().unwrap().links()[0].href(),
+        "https://example.com/source"
+    );
+}
+
+#[test]
+fn discover_feeds() {
+    let html = r#"
+        <!DOCTYPE html>
+        <html>
+            <head>
+                <base href="https://ignored.example.com/">
+                <LINK REL="alternate" TYPE="application/atom+xml" HREF="feed.atom" title="Atom">
+                <link rel='alternate home' type='application/rss+xml' href='/rss?a=1&amp;b=2' />
+                <link rel="alternate" type="text/html" href="/index.html">
+                <link rel="stylesheet" type="application/atom+xml" href="/not-a-feed">
+                <link rel="alternate" type="application/atom+xml" href="https://other.example.com/feed">
+                <link rel="alternate" type="application/atom+xml" href="feed.atom">
+            </head>
+        </html>
+    "#;
+
+    assert_eq!(
+        atom_syndication::discover_feeds(html, "https://example.com/blog/"),
+        vec![
+            "https://example.com/blog/feed.atom".to_string(),
+            "https://example.com/rss?a=1&b=2".to_string(),
+            "https://other.example.com/feed".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn person_homepage_url() {
+    let person = |uri: &str| {
+        let mut person = Person::default();
+        person.set_uri(uri.to_string());
+        person
+    };
+
+    assert_eq!(
+        person("https://example.com/~jane")
+            .homepage_url()
+            .map(String::from),
+        Some("https://example.com/~jane".to_string())
+    );
+    assert_eq!(person("not a uri").homepage_url(), None);
+    assert_eq!(person("/about").homepage_url(), None);
+    assert_eq!(person("javascript:alert(1)").homepage_url(), None);
+    assert_eq!(Person::default().homepage_url(), None);
+}
+
+#[test]
+fn icon_with_fallback() {
+    let mut feed = Feed::default();
+    assert_eq!(feed.icon_with_fallback(), None);
+
+    feed.set_links(vec![Link::self_link("http://example.com/feed")]);
+    assert_eq!(feed.icon_with_fallback(), None);
+
+    feed.set_links(vec![
+        Link::self_link("http://example.com/feed"),
+        Link::alternate("/relative"),
+        Link::alternate("http://example.org/"),
+    ]);
+    assert_eq!(feed.icon_with_fallback(), None);
+
+    feed.set_links(vec![Link::alternate("http://blog.example.com:8080/posts/")]);
+    assert_eq!(
+        feed.icon_with_fallback().as_deref(),
+        Some("https://blog.example.com:8080/favicon.ico")
+    );
+
+    feed.set_icon(Some(String::new()));
+    assert_eq!(
+        feed.icon_with_fallback().as_deref(),
+        Some("https://blog.example.com:8080/favicon.ico")
+    );
+
+    feed.set_icon(Some("/icon.png".to_string()));
+    assert_eq!(feed.icon_with_fallback().as_deref(), Some("/icon.png"));
+}
+
+#[test]
+fn href_hosts() {
+    use std::collections::HashSet;
+
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <link rel="self" href="https://Example.com/feed"/>
+            <link rel="alternate" href="/relative"/>
+            <entry>
+                <link href="http://127.0.0.1:8080/internal"/>
+                <link href="mailto:jane@example.com"/>
+                <link href="not a url"/>
+                <source>
+                    <link href="http://[::1]/"/>
+                </source>
+            </entry>
+            <entry>
+                <link rel="enclosure" href="https://cdn.example.net/audio.mp3"/>
+                <link href="https://example.com/post"/>
+            </entry>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let expected = ["example.com", "127.0.0.1", "[::1]", "cdn.example.net"]
+        .iter()
+        .map(|host| host.to_string())
+        .collect::<HashSet<_>>();
+    assert_eq!(feed.href_hosts(), expected);
+    assert!(Feed::default().href_hosts().is_empty());
+}
+
+#[test]
+fn read_resolve_links() {
+    use atom_syndication::ReadOptions;
+
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom" xml:base="/blog/">
+            <link rel="self" href="feed.atom"/>
+            <entry xml:base="http://cdn.example.org/posts/">
+                <link href="1.html"/>
+                <link href="../img/1.png" xml:base="media/"/>
+                <link href="https://other.example.net/"/>
+                <link href=""/>
+                <source><link href="origin.atom"/></source>
+                <content src="1.txt" xml:base="text/"/>
+            </entry>
+            <entry><link href="2.html"/></entry>
+        </feed>
+    "#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.links()[0].href(), "feed.atom");
+
+    let mut options = ReadOptions::default();
+    options.resolve_links = Some(url::Url::parse("http://example.com/feeds/main").unwrap());
+    let feed = Feed::read_with(xml.as_bytes(), options).unwrap();
+    assert_eq!(feed.links()[0].href(), "http://example.com/blog/feed.atom");
+
+    let entry = &feed.entries()[0];
+    let hrefs = entry.links().iter().map(Link::href).collect::<Vec<_>>();
+    assert_eq!(
+        hrefs,
+        vec![
+            "http://cdn.example.org/posts/1.html",
+            "http://cdn.example.org/posts/img/1.png",
+            "https://other.example.net/",
+            "",
+        ]
+    );
+    assert_eq!(
+        entry.source().unwrap().links()[0].href(),
+        "http://cdn.example.org/posts/origin.atom"
+    );
+    assert_eq!(
+        entry.content().unwrap().src(),
+        Some("http://cdn.example.org/posts/text/1.txt")
+    );
+    assert_eq!(
+        feed.entries()[1].links()[0].href(),
+        "http://example.com/blog/2.html"
+    );
+}
+
+#[test]
+fn read_all_pages_resolves_next_links() {
+    let page = |links: &str| {
+        format!(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom">{}<entry><id>urn:1</id></entry></feed>"#,
+            links
+        )
+        .parse::<Feed>()
+        .unwrap()
+    };
+
+    let initial = page(
+        r#"<link rel="self" href="http://example.com/feed"/><link rel="next" href="feed?page=2"/>"#,
+    );
+    let mut fetched = Vec::new();
+    let result = Feed::read_all_pages(initial, |url| {
+        fetched.push(url.to_string());
+        Ok(match url {
+            "http://example.com/feed?page=2" => page(r#"<link rel="next" href="?page=3"/>"#),
+            _ => page(r#"<link rel="next" href="/feed"/>"#),
+        })
+    });
+    assert_eq!(
+        fetched,
+        vec![
+            "http://example.com/feed?page=2",
+            "http://example.com/feed?page=3"
+        ]
+    );
+    assert!(matches!(
+        result,
+        Err(atom_syndication::Error::PagingCycle(ref url)) if url == "http://example.com/feed"
+    ));
+
+    let initial = format!(
+        r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:base="http://example.com/archive/">{}</feed>"#,
+        r#"<link rel="next" href="2.xml"/>"#
+    )
+    .parse::<Feed>()
+    .unwrap();
+    let mut fetched = Vec::new();
+    Feed::read_all_pages(initial, |url| {
+        fetched.push(url.to_string());
+        Ok(Feed::default())
+    })
+    .unwrap();
+    assert_eq!(fetched, vec!["http://example.com/archive/2.xml"]);
+}
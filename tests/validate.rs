@@ -0,0 +1,206 @@
+use std::str::FromStr;
+
+use atom_syndication::{Entry, Feed, Fix, FixedDateTime, Link, Person, ValidationError};
+
+#[test]
+fn validate_and_fix_repairs_timestamps_and_rels() {
+    let older = FixedDateTime::from_str("2017-06-01T00:00:00Z").unwrap();
+    let newer = FixedDateTime::from_str("2017-06-03T00:00:00Z").unwrap();
+
+    let mut link = Link::default();
+    link.set_href("http://example.com/");
+    link.set_rel("");
+
+    let mut first = Entry::default();
+    first.set_id("urn:example:1");
+    first.set_updated(older);
+
+    let mut second = Entry::default();
+    second.set_id("urn:example:2");
+    second.set_published(newer);
+
+    let mut feed = Feed::default();
+    feed.set_id("urn:example");
+    feed.set_links(vec![link]);
+    feed.set_entries(vec![first, second]);
+
+    let fixes = feed.validate_and_fix();
+    assert_eq!(
+        fixes,
+        vec![
+            Fix::LinkRel {
+                href: "http://example.com/".to_string()
+            },
+            Fix::EntryUpdated {
+                index: 1,
+                updated: newer
+            },
+            Fix::FeedUpdated(newer),
+        ]
+    );
+    assert_eq!(feed.links()[0].rel(), "alternate");
+    assert_eq!(feed.entries()[1].updated(), &newer);
+    assert_eq!(feed.updated(), &newer);
+
+    assert!(feed.validate_and_fix().is_empty());
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn validate_and_fix_generates_ids() {
+    let build = || {
+        let mut link = Link::default();
+        link.set_href("http://example.com/");
+
+        let mut entry = Entry::default();
+        entry.set_title("Entry");
+
+        let mut feed = Feed::default();
+        feed.set_links(vec![link]);
+        feed.set_entries(vec![entry]);
+        feed
+    };
+
+    let mut feed = build();
+    let fixes = feed.validate_and_fix();
+    assert_eq!(fixes.len(), 2);
+    assert!(feed.id().starts_with("urn:uuid:"));
+    assert!(feed.entries()[0].id().starts_with("urn:uuid:"));
+    assert_ne!(feed.id(), feed.entries()[0].id());
+
+    let mut again = build();
+    again.validate_and_fix();
+    assert_eq!(again, feed);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn validate_and_fix_distinct_entry_ids() {
+    let mut entry = Entry::default();
+    entry.set_title("Entry");
+
+    let mut feed = Feed::default();
+    feed.set_id("urn:example");
+    feed.set_entries(vec![entry.clone(), entry]);
+
+    feed.validate_and_fix();
+    assert!(feed.entries()[0].id().starts_with("urn:uuid:"));
+    assert!(feed.entries()[1].id().starts_with("urn:uuid:"));
+    assert_ne!(feed.entries()[0].id(), feed.entries()[1].id());
+}
+
+#[test]
+fn new_valid() {
+    let updated = FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap();
+    let feed = Feed::new_valid("urn:example", "Feed Title", updated);
+    assert_eq!(feed.id(), "urn:example");
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.updated(), &updated);
+    assert_eq!(feed.validate(), Ok(()));
+}
+
+#[test]
+fn validate_entries() {
+    let updated = FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap();
+    let mut feed = Feed::new_valid("urn:example", "Feed Title", updated);
+    feed.set_entries(vec![Entry::default()]);
+
+    assert_eq!(
+        feed.validate(),
+        Err(vec![
+            ValidationError::MissingEntryId(0),
+            ValidationError::MissingEntryTitle(0),
+            ValidationError::MissingEntryUpdated(0),
+            ValidationError::MissingAuthor(0),
+            ValidationError::MissingAlternateLink(0),
+        ])
+    );
+
+    let mut person = Person::default();
+    person.set_name("John Doe");
+    feed.set_authors(vec![person]);
+
+    let entry = &mut feed.entries[0];
+    entry.set_id("urn:example:1");
+    entry.set_title("Entry Title");
+    entry.set_updated(updated);
+    entry.set_links(vec![Link::default()]);
+    assert_eq!(feed.validate(), Ok(()));
+}
+
+#[test]
+fn read_and_validate() {
+    let valid = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <id>urn:feed</id>
+            <title>Feed Title</title>
+            <updated>2021-05-01T12:00:00Z</updated>
+            <author><name>Jane</name></author>
+            <entry>
+                <id>urn:entry</id>
+                <title>Entry Title</title>
+                <updated>2021-05-01T12:00:00Z</updated>
+                <link href="http://example.com/entry"/>
+            </entry>
+        </feed>
+    "#;
+    let feed = Feed::read_and_validate(valid.as_bytes()).unwrap();
+    assert_eq!(feed.entries().len(), 1);
+
+    let invalid = valid.replace("<title>Entry Title</title>", "");
+    let (feed, errors) = Feed::read_and_validate(invalid.as_bytes()).unwrap_err();
+    assert_eq!(feed.entries().len(), 1);
+    assert_eq!(errors, vec![ValidationError::MissingEntryTitle(0)]);
+
+    let truncated = &valid[..valid.find("<link").unwrap()];
+    let (feed, errors) = Feed::read_and_validate(truncated.as_bytes()).unwrap_err();
+    assert_eq!(feed.title(), "Feed Title");
+    assert!(feed.entries().is_empty());
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], ValidationError::Malformed(_)));
+}
+
+#[test]
+fn validate_timestamps() {
+    let xml = r#"
+        <a:feed xmlns:a="http://www.w3.org/2005/Atom" xmlns:ext="urn:ext">
+            <a:updated>2021-05-01 12:00:00</a:updated>
+            <ext:updated>not a timestamp</ext:updated>
+            <updated xmlns="urn:ext">not a timestamp</updated>
+            <a:entry>
+                <a:updated>2021-05-01T12:00:00+02:00</a:updated>
+                <a:published>  </a:published>
+            </a:entry>
+            <a:entry>
+                <a:updated> yesterday </a:updated>
+                <a:published>2021-05-01T12:00:00Z</a:published>
+                <a:source><a:updated>2021-05-01T12:00:00</a:updated><a:published/></a:source>
+                <a:content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><updated>no</updated></div></a:content>
+            </a:entry>
+        </a:feed>
+    "#;
+
+    let issues = Feed::validate_timestamps(xml.as_bytes()).unwrap();
+    let issues = issues
+        .iter()
+        .map(|issue| (issue.location.as_str(), issue.value.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        vec![
+            ("feed updated", "2021-05-01 12:00:00"),
+            ("entry[0] published", ""),
+            ("entry[1] updated", "yesterday"),
+            ("entry[1] source updated", "2021-05-01T12:00:00"),
+            ("entry[1] source published", ""),
+        ]
+    );
+
+    let issue = &Feed::validate_timestamps(xml.as_bytes()).unwrap()[2];
+    assert_eq!(
+        issue.to_string(),
+        "entry[1] updated is not an RFC 3339 timestamp: 'yesterday'"
+    );
+    assert!(Feed::validate_timestamps("<feed><updated>2021".as_bytes()).is_err());
+    assert!(Feed::validate_timestamps("<feed></entry>".as_bytes()).is_err());
+}
@@ -3,7 +3,7 @@ extern crate atom_syndication as atom;
 use std::fs::File;
 use std::io::BufReader;
 
-use crate::atom::{Content, Entry, Feed};
+use crate::atom::{Content, Entry, Feed, WriteOptions};
 
 macro_rules! feed {
     ($f:expr) => {{
@@ -76,3 +76,568 @@ fn write_content_roundtrip() {
 
     assert_eq!(feed.to_string().parse::<Feed>().unwrap(), feed);
 }
+
+fn element_names(xml: &[u8]) -> Vec<(Option<Vec<u8>>, Vec<u8>)> {
+    let mut reader = quick_xml::Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut ns_buf = Vec::new();
+    let mut names = Vec::new();
+
+    loop {
+        match reader.read_namespaced_event(&mut buf, &mut ns_buf).unwrap() {
+            (ns, quick_xml::events::Event::Start(element))
+            | (ns, quick_xml::events::Event::Empty(element)) => {
+                names.push((ns.map(<[u8]>::to_vec), element.local_name().to_vec()))
+            }
+            (_, quick_xml::events::Event::Eof) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    names
+}
+
+#[test]
+fn write_namespace_prefix() {
+    let feed = feed!("tests/data/feed.xml");
+    let unprefixed = feed.write_to(Vec::new()).unwrap();
+
+    let mut options = WriteOptions::default();
+    options.namespace_prefix = Some("a");
+    let prefixed = feed.write_with(Vec::new(), options).unwrap();
+    let xml = String::from_utf8(prefixed.clone()).unwrap();
+
+    assert!(xml.contains(r#"<a:feed xmlns:a="http://www.w3.org/2005/Atom""#));
+    assert!(xml.contains("</a:feed>"));
+    assert!(xml.contains(r#"<a:link href="http://example.com/feed" rel="self"/>"#));
+    assert!(!xml.contains("<title"));
+
+    let names = element_names(&prefixed);
+    assert!(names
+        .iter()
+        .all(|(ns, _)| ns.as_deref() == Some(&b"http://www.w3.org/2005/Atom"[..])));
+    assert_eq!(names, element_names(&unprefixed));
+    assert_eq!(Feed::read_from(&prefixed[..]).unwrap(), feed);
+
+    for file in &[
+        "tests/data/entry.xml",
+        "tests/data/entry_source.xml",
+        "tests/data/content_text_xhtml.xml",
+        "tests/data/extension.xml",
+    ] {
+        let feed = feed!(file);
+        let mut options = WriteOptions::default();
+        options.namespace_prefix = Some("a");
+        let prefixed = feed.write_with(Vec::new(), options).unwrap();
+        assert_eq!(Feed::read_from(&prefixed[..]).unwrap(), feed, "{}", file);
+    }
+}
+
+#[test]
+fn write_canonical_order() {
+    let feed = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <content>Content</content>
+                <summary>Summary</summary>
+                <link href="http://example.com/1"/>
+                <category term="term"/>
+                <author><name>Author</name></author>
+                <published>2017-06-01T15:15:44-05:00</published>
+                <updated>2017-06-03T15:15:44-05:00</updated>
+                <title>Title</title>
+                <id>urn:1</id>
+            </entry>
+            <updated>2017-06-03T15:15:44-05:00</updated>
+            <title>Feed</title>
+            <id>urn:feed</id>
+        </feed>
+    "#
+    .parse::<Feed>()
+    .unwrap();
+
+    let names = element_names(feed.to_string().as_bytes())
+        .into_iter()
+        .map(|(_, name)| String::from_utf8(name).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            "feed",
+            "id",
+            "title",
+            "updated",
+            "entry",
+            "id",
+            "title",
+            "updated",
+            "published",
+            "author",
+            "name",
+            "category",
+            "link",
+            "summary",
+            "content",
+        ]
+    );
+
+    let reparsed = feed.to_string().parse::<Feed>().unwrap();
+    assert_eq!(reparsed.to_string(), feed.to_string());
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn write_collapse_html_whitespace() {
+    let mut entry = Entry::default();
+    entry.set_content_html("<p>\n    Lots   of\n\n   space</p>\n<pre>  kept\n  as is</pre>");
+    entry.set_summary_text("plain   text   stays");
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry]);
+
+    let mut options = WriteOptions::default();
+    options.collapse_html_whitespace = true;
+    let xml = String::from_utf8(feed.write_with(Vec::new(), options).unwrap()).unwrap();
+    let written = xml.parse::<Feed>().unwrap();
+    let entry = &written.entries()[0];
+
+    assert_eq!(
+        entry.content().unwrap().value(),
+        Some("<p> Lots of space</p> <pre>  kept\n  as is</pre>")
+    );
+    assert_eq!(entry.summary().unwrap().as_str(), "plain   text   stays");
+
+    let xml = String::from_utf8(
+        feed.write_with(Vec::new(), WriteOptions::default())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(xml.parse::<Feed>().unwrap(), feed);
+}
+
+#[test]
+fn write_unknown_attrs() {
+    let feed = feed!("tests/data/unknown_attrs.xml");
+    let xml = feed.to_string();
+    assert!(xml.contains(r#"v:channel="news" data-x="1""#));
+    assert!(xml.contains(
+        r#"<link href="http://example.com" rel="alternate" v:rank="2" v:kind="primary"/>"#
+    ));
+    assert_eq!(xml.matches("xmlns:thr=").count(), 1);
+    assert_eq!(xml.parse::<Feed>().unwrap(), feed);
+}
+
+#[test]
+fn write_empty_optional_elements() {
+    use crate::atom::{Source, Text};
+
+    let mut source = Source::default();
+    source.set_icon(Some(String::new()));
+    source.set_logo(Some(String::new()));
+    source.set_rights(Some(Text::plain("")));
+    source.set_subtitle(Some(Text::html("")));
+
+    let mut entry = Entry::default();
+    entry.set_rights(Some(Text::plain("")));
+    entry.set_summary(Some(Text::plain("")));
+    entry.set_source(Some(source));
+
+    let mut feed = Feed::default();
+    feed.set_icon(Some(String::new()));
+    feed.set_logo(Some(String::new()));
+    feed.set_rights(Some(Text::plain("")));
+    feed.set_subtitle(Some(Text::plain("")));
+    feed.set_entries(vec![entry]);
+
+    let xml = feed.to_string();
+    for name in &["icon", "logo", "rights", "subtitle", "summary"] {
+        assert!(
+            !xml.contains(&format!("<{}", name)),
+            "{} written in {}",
+            name,
+            xml
+        );
+    }
+    assert!(xml.contains("<source>"));
+
+    feed.set_subtitle(Some(Text::plain("Subtitle")));
+    assert!(feed.to_string().contains("<subtitle>Subtitle</subtitle>"));
+}
+
+#[test]
+fn feed_writer() {
+    use crate::atom::FeedWriter;
+
+    let feed = feed!("tests/data/feed.xml");
+
+    let mut metadata = feed.clone();
+    metadata.set_entries(Vec::new());
+    let mut writer = FeedWriter::start(Vec::new(), &metadata).unwrap();
+    for entry in feed.entries() {
+        writer.write_entry(entry).unwrap();
+    }
+    let xml = writer.finish().unwrap();
+
+    assert_eq!(String::from_utf8(xml).unwrap(), feed.to_string());
+}
+
+#[test]
+fn write_link_order() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <link rel="enclosure" type="video/mp4" href="http://example.com/1080p.mp4"/>
+                <link rel="alternate" href="http://example.com/video"/>
+                <link rel="enclosure" type="video/mp4" href="http://example.com/720p.mp4"/>
+                <link rel="enclosure" type="video/mp4" href="http://example.com/480p.mp4"/>
+            </entry>
+        </feed>
+    "#;
+    let hrefs = |feed: &Feed| {
+        feed.entries()[0]
+            .links()
+            .iter()
+            .map(|link| link.href().to_string())
+            .collect::<Vec<_>>()
+    };
+    let expected = vec![
+        "http://example.com/1080p.mp4",
+        "http://example.com/video",
+        "http://example.com/720p.mp4",
+        "http://example.com/480p.mp4",
+    ];
+
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(hrefs(&feed), expected);
+
+    let written = feed.to_string();
+    let positions = expected
+        .iter()
+        .map(|href| written.find(href).unwrap())
+        .collect::<Vec<_>>();
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(hrefs(&written.parse::<Feed>().unwrap()), expected);
+}
+
+#[test]
+fn write_extension_namespaces() {
+    let xml = r#"
+        <feed xmlns="http://www.w3.org/2005/Atom"
+              xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd"
+              xmlns:dc="http://purl.org/dc/elements/1.1/">
+            <dc:creator>Jane Doe</dc:creator>
+            <entry>
+                <itunes:duration>12:34</itunes:duration>
+                <itunes:image href="http://example.com/cover.jpg"/>
+            </entry>
+        </feed>
+    "#;
+    let feed = xml.parse::<Feed>().unwrap();
+    let written = feed.to_string();
+    assert!(written.contains(r#"xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd""#));
+    assert!(written.contains(r#"xmlns:dc="http://purl.org/dc/elements/1.1/""#));
+    assert!(written.contains("<itunes:duration>12:34</itunes:duration>"));
+    assert!(written.contains(r#"<itunes:image href="http://example.com/cover.jpg"/>"#));
+    assert_eq!(written.parse::<Feed>().unwrap(), feed);
+
+    let mut entry = Entry::default();
+    entry.set_extensions(feed.entries()[0].extensions().clone());
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry]);
+    let written = feed.to_string();
+    assert!(written.contains(r#"xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd""#));
+    let read = written.parse::<Feed>().unwrap();
+    assert_eq!(
+        read.entries()[0].extensions(),
+        feed.entries()[0].extensions()
+    );
+
+    let mut writer = atom::FeedWriter::start(Vec::new(), &Feed::default()).unwrap();
+    writer.write_entry(&feed.entries()[0]).unwrap();
+    let written = String::from_utf8(writer.finish().unwrap()).unwrap();
+    assert!(
+        written.contains(r#"<entry xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">"#)
+    );
+    let read = written.parse::<Feed>().unwrap();
+    assert_eq!(
+        read.entries()[0].extensions(),
+        feed.entries()[0].extensions()
+    );
+}
+
+#[test]
+fn write_undeclared_prefix() {
+    use atom::extension::Extension;
+    use atom::Error;
+
+    let extension = Extension {
+        name: "ex:rating".to_string(),
+        value: Some("5".to_string()),
+        ..Extension::default()
+    };
+    let mut entry = Entry::default();
+    entry
+        .extensions
+        .entry("ex".to_string())
+        .or_default()
+        .insert("rating".to_string(), vec![extension]);
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry.clone()]);
+
+    match feed.write_with(Vec::new(), WriteOptions::default()) {
+        Err(Error::UndeclaredPrefix(prefix)) => assert_eq!(prefix, "ex"),
+        result => panic!("unexpected result {:?}", result),
+    }
+    let mut writer = atom::FeedWriter::start(Vec::new(), &Feed::default()).unwrap();
+    assert!(matches!(
+        writer.write_entry(&entry),
+        Err(Error::UndeclaredPrefix(_))
+    ));
+
+    let mut namespaces = std::collections::BTreeMap::new();
+    namespaces.insert("ex".to_string(), "http://example.com/ns".to_string());
+    feed.set_namespaces(namespaces.clone());
+    let written = feed
+        .write_with(Vec::new(), WriteOptions::default())
+        .unwrap();
+    let written = String::from_utf8(written).unwrap();
+    assert!(written.contains(r#"xmlns:ex="http://example.com/ns""#));
+    assert!(written.contains("<ex:rating>5</ex:rating>"));
+
+    entry.set_namespaces(namespaces);
+    let mut writer = atom::FeedWriter::start(Vec::new(), &Feed::default()).unwrap();
+    writer.write_entry(&entry).unwrap();
+    let written = String::from_utf8(writer.finish().unwrap()).unwrap();
+    assert!(written.contains(r#"<entry xmlns:ex="http://example.com/ns">"#));
+}
+
+#[test]
+fn write_missing_required_fields() {
+    use atom::{Error, ErrorKind, FixedDateTime};
+
+    let mut feed = Feed::default();
+    feed.set_title("Feed Title");
+    feed.set_entries(vec![Entry::default()]);
+
+    let error = feed.write_to(Vec::new()).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Structure);
+    assert_eq!(
+        error.to_string(),
+        "required fields are empty: id, entry[0] id, entry[0] title"
+    );
+    assert!(feed.write_unchecked(Vec::new()).is_ok());
+    assert!(feed.write_with(Vec::new(), WriteOptions::default()).is_ok());
+    assert!(feed.to_string().contains("<id></id>"));
+
+    let mut options = WriteOptions::default();
+    options.require_fields = true;
+    assert!(matches!(
+        feed.write_with(Vec::new(), options.clone()),
+        Err(Error::MissingRequiredField(ref fields)) if fields.len() == 3
+    ));
+    assert!(atom::FeedWriter::start(Vec::new(), &feed).is_ok());
+    assert!(matches!(
+        atom::FeedWriter::start_with(Vec::new(), &feed, options.clone()),
+        Err(Error::MissingRequiredField(ref fields)) if *fields == ["id"]
+    ));
+
+    let updated = FixedDateTime::parse_from_rfc3339("2021-05-01T12:00:00Z").unwrap();
+    let mut entry = Entry::default();
+    entry.set_id("urn:entry");
+    entry.set_title("Entry Title");
+    entry.set_updated(updated);
+    feed.set_id("urn:feed");
+    feed.set_updated(updated);
+    feed.set_entries(vec![entry]);
+    assert!(matches!(
+        feed.write_to(Vec::new()),
+        Ok(ref xml) if *xml == feed.write_unchecked(Vec::new()).unwrap()
+    ));
+
+    let mut writer = atom::FeedWriter::start_with(Vec::new(), &feed, options).unwrap();
+    assert!(matches!(
+        writer.write_entry(&Entry::default()),
+        Err(Error::MissingRequiredField(ref fields))
+            if *fields == ["entry[0] id", "entry[0] title"]
+    ));
+
+    // An `updated` timestamp at the Unix epoch is a value like any other.
+    feed.set_updated(FixedDateTime::parse_from_rfc3339("1970-01-01T00:00:00Z").unwrap());
+    assert!(feed.write_to(Vec::new()).is_ok());
+
+    feed.set_entries(Vec::new());
+    feed.set_title(" ");
+    assert!(matches!(
+        feed.write_to(Vec::new()),
+        Err(Error::MissingRequiredField(ref fields)) if *fields == ["title"]
+    ));
+}
+
+#[test]
+fn write_opml() {
+    use crate::atom::{feeds_to_opml, Link};
+
+    let feed = feed!("tests/data/feed.xml");
+    let mut other = Feed::default();
+    other.set_title("  <Other>  ");
+    other.set_links(vec![Link::alternate("http://example.org/")]);
+
+    let outline = feed.to_opml_outline();
+    assert_eq!(outline.text, feed.title().as_str());
+    assert_eq!(outline.title, outline.text);
+    assert_eq!(outline.xml_url.as_deref(), Some("http://example.com/feed"));
+    assert_eq!(outline.html_url.as_deref(), Some("http://example.com"));
+
+    let outline = other.to_opml_outline();
+    assert_eq!(outline.title, "<Other>");
+    assert_eq!(outline.xml_url, None);
+    assert_eq!(outline.html_url.as_deref(), Some("http://example.org/"));
+
+    let opml = feeds_to_opml(&[other]);
+    assert_eq!(
+        opml,
+        r#"<?xml version="1.0" encoding="UTF-8"?><opml version="2.0"><head><title>Subscriptions</title></head><body><outline type="rss" text="&lt;Other&gt;" title="&lt;Other&gt;" htmlUrl="http://example.org/"/></body></opml>"#
+    );
+    assert_eq!(
+        feeds_to_opml(&[feed.clone(), feed])
+            .matches("<outline ")
+            .count(),
+        2
+    );
+}
+
+fn feed_with_missing_id() -> Feed {
+    let mut first = Entry::default();
+    first.set_id("urn:entry:1");
+    let mut second = Entry::default();
+    second.set_title("Second");
+    second.set_links(vec![atom::Link::alternate("http://example.com/2")]);
+
+    let mut feed = Feed::default();
+    feed.set_entries(vec![first, second]);
+    feed
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn write_generated_ids() {
+    let feed = feed_with_missing_id();
+    let mut options = WriteOptions::default();
+    options.generate_missing_ids = true;
+
+    let write = || {
+        let xml = feed.write_with(Vec::new(), options.clone()).unwrap();
+        String::from_utf8(xml).unwrap()
+    };
+    let xml = write();
+    assert_eq!(xml, write());
+    assert!(xml.contains("<id>urn:entry:1</id>"));
+
+    let written = Feed::read_from(xml.as_bytes()).unwrap();
+    let id = written.entries()[1].id();
+    assert!(id.starts_with("urn:uuid:"));
+    assert_eq!(id.len(), "urn:uuid:".len() + 36);
+    assert_eq!(feed.entries()[1].id(), "");
+
+    let mut writer = atom::FeedWriter::start_with(Vec::new(), &feed, options.clone()).unwrap();
+    for entry in feed.entries() {
+        writer.write_entry(entry).unwrap();
+    }
+    assert_eq!(String::from_utf8(writer.finish().unwrap()).unwrap(), xml);
+
+    // Entries sharing a link and title, or whose parts run together, get different ids.
+    let mut first = Entry::default();
+    first.set_title("Title");
+    first.set_links(vec![atom::Link::alternate("http://example.com/\nTitle")]);
+    let mut second = Entry::default();
+    second.set_title("Title\nTitle");
+    second.set_links(vec![atom::Link::alternate("http://example.com/")]);
+    let mut feed = Feed::default();
+    feed.set_entries(vec![first.clone(), first, second]);
+    let xml = feed.write_with(Vec::new(), options).unwrap();
+    let written = Feed::read_from(&xml[..]).unwrap();
+    let ids = written
+        .entries()
+        .iter()
+        .map(|entry| entry.id())
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(ids.len(), 3);
+}
+
+#[cfg(not(feature = "uuid"))]
+#[test]
+fn write_generated_ids() {
+    let mut options = WriteOptions::default();
+    options.generate_missing_ids = true;
+
+    let feed = feed_with_missing_id();
+    let result = feed.write_with(Vec::new(), options.clone());
+    assert!(matches!(
+        result,
+        Err(atom::Error::MissingRequiredField(ref fields)) if fields == &["entry[1] id"]
+    ));
+
+    let mut writer = atom::FeedWriter::start_with(Vec::new(), &feed, options).unwrap();
+    writer.write_entry(&feed.entries()[0]).unwrap();
+    assert!(matches!(
+        writer.write_entry(&feed.entries()[1]),
+        Err(atom::Error::MissingRequiredField(ref fields)) if fields == &["entry[1] id"]
+    ));
+}
+
+#[test]
+fn write_person_round_trip() {
+    use atom::Person;
+
+    let mut full = Person::default();
+    full.set_name("Jane Doe");
+    full.set_email("jane@example.com".to_string());
+    full.set_uri("http://example.com/~jane".to_string());
+
+    let mut name_only = Person::default();
+    name_only.set_name("John Doe");
+
+    let mut feed = Feed::default();
+    feed.set_authors(vec![full, name_only]);
+
+    let xml = feed.to_string();
+    assert!(xml.contains("<author><name>John Doe</name></author>"));
+    assert_eq!(xml.parse::<Feed>().unwrap(), feed);
+}
+
+#[test]
+fn write_unnamed_persons() {
+    use atom::{Error, FeedWriter, Person, UnnamedPersons};
+
+    let mut named = Person::default();
+    named.set_name("Jane Doe");
+    let mut entry = Entry::default();
+    entry.set_authors(vec![named, Person::default()]);
+
+    let mut feed = Feed::default();
+    feed.set_contributors(vec![Person::default()]);
+    feed.set_entries(vec![Entry::default(), entry.clone()]);
+    assert!(feed.to_string().contains("<author><name></name></author>"));
+
+    let mut options = WriteOptions::default();
+    options.unnamed_persons = UnnamedPersons::Skip;
+    let xml = String::from_utf8(feed.write_with(Vec::new(), options).unwrap()).unwrap();
+    assert!(!xml.contains("<contributor>"));
+    assert!(!xml.contains("<name></name>"));
+    assert!(xml.contains("<author><name>Jane Doe</name></author>"));
+
+    let mut options = WriteOptions::default();
+    options.unnamed_persons = UnnamedPersons::Reject;
+    assert!(matches!(
+        feed.write_with(Vec::new(), options.clone()),
+        Err(Error::MissingRequiredField(ref fields))
+            if *fields == ["contributor[0] name", "entry[1] author[1] name"]
+    ));
+
+    let mut writer = FeedWriter::start_with(Vec::new(), &Feed::default(), options).unwrap();
+    writer.write_entry(&Entry::default()).unwrap();
+    assert!(matches!(
+        writer.write_entry(&entry),
+        Err(Error::MissingRequiredField(ref fields)) if *fields == ["entry[1] author[1] name"]
+    ));
+}